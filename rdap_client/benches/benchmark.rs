@@ -14,11 +14,28 @@ fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("bootstrap_dns_find", |b| {
         let file = File::open("test_data/bootstrap/dns.json").unwrap();
         let parsed = serde_json::from_reader(file).unwrap();
-        let dns = rdap_client::bootstrap::Dns::try_from(&parsed).unwrap();
+        let dns = rdap_client::bootstrap::Dns::from(&parsed);
 
         b.iter(|| dns.find(black_box("cz")).unwrap()[0].as_str())
     });
 
+    c.bench_function("bootstrap_dns_find_subdomain", |b| {
+        let file = File::open("test_data/bootstrap/dns.json").unwrap();
+        let parsed = serde_json::from_reader(file).unwrap();
+        let dns = rdap_client::bootstrap::Dns::from(&parsed);
+
+        b.iter(|| dns.find(black_box("www.mail.example.co.cz")).unwrap()[0].as_str())
+    });
+
+    c.bench_function("bootstrap_ip_find", |b| {
+        let ipv4 = serde_json::from_reader(File::open("test_data/bootstrap/ipv4.json").unwrap());
+        let ipv6 = serde_json::from_reader(File::open("test_data/bootstrap/ipv6.json").unwrap());
+        let ip = rdap_client::bootstrap::Ip::try_from((&ipv4.unwrap(), &ipv6.unwrap())).unwrap();
+        let address = std::net::Ipv4Addr::new(193, 0, 6, 139);
+
+        b.iter(|| ip.find(black_box(address)).unwrap()[0].as_str())
+    });
+
     c.bench_function("serialize_bootstrap_dns", |b| {
         let mut dns = rdap_client::bootstrap::Dns::new();
         dns.insert(
@@ -0,0 +1,7 @@
+#!/bin/sh
+# Refresh snapshot of IANA bootstrap registries compiled in with `embedded-bootstrap` feature.
+set -eu
+cd "$(dirname "$0")"
+for file in asn.json dns.json ipv4.json ipv6.json object-tags.json; do
+    curl --fail --silent --show-error --location --output "$file" "https://data.iana.org/rdap/$file"
+done
@@ -0,0 +1,376 @@
+//! Blocking RDAP client.
+//!
+//! The blocking `Client` wraps async [`crate::Client`] and drives it on its own single threaded
+//! runtime, so it must not be used from inside of async runtime.
+
+use crate::{
+    bootstrap, parser, ClientError, DomainWithRegistrar, FanOutResults, IpQuery, Lookup, RdapQuery,
+    Response, ReverseSearch, SearchDomain, SearchEntity, SearchNameserver,
+};
+use futures::StreamExt;
+use ip_network::IpNetwork;
+use reqwest::IntoUrl;
+use serde::de::DeserializeOwned;
+use std::convert::TryInto;
+use std::future::Future;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+/// Blocking RDAP client.
+pub struct Client {
+    inner: crate::Client,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::with_client(crate::Client::default())
+    }
+}
+
+impl From<crate::Client> for Client {
+    /// Creates blocking `Client` from async client, for example one created by
+    /// [`crate::ClientBuilder`].
+    fn from(client: crate::Client) -> Self {
+        Self::with_client(client)
+    }
+}
+
+impl Client {
+    /// Creates new `Client` with with default configuration.
+    pub fn new() -> Self {
+        Self::with_client(crate::Client::new())
+    }
+
+    /// Creates new `Client` with given [reqwest](https://docs.rs/reqwest/) client.
+    pub fn with_reqwest_client(client: reqwest::Client) -> Self {
+        Self::with_client(crate::Client::with_reqwest_client(client))
+    }
+
+    fn with_client(inner: crate::Client) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("error during creating runtime"); // Should never fail
+
+        Self { inner, runtime }
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Set bootstrap that will be used by `lookup_*` methods instead of fetching it from IANA.
+    pub fn set_bootstrap(&self, bootstrap: bootstrap::Bootstrap) {
+        self.block_on(self.inner.set_bootstrap(bootstrap))
+    }
+
+    /// Returns bootstrap used by `lookup_*` methods. When bootstrap was not set or fetched yet,
+    /// it is fetched from IANA and cached for next calls.
+    pub fn bootstrap(&self) -> Result<Arc<bootstrap::Bootstrap>, ClientError> {
+        self.block_on(self.inner.bootstrap())
+    }
+
+    /// Fetch bootstrap from IANA and use it for next `lookup_*` calls, unless it has older
+    /// publication time than the current one.
+    pub fn refresh_bootstrap(&self) -> Result<Arc<bootstrap::Bootstrap>, ClientError> {
+        self.block_on(self.inner.refresh_bootstrap())
+    }
+
+    /// Fetch bootstrap from IANA for ASN, IPv4 and IPV6, domains (DNS) and object tags.
+    pub fn fetch_bootstrap(
+        &self,
+    ) -> Result<bootstrap::Bootstrap, Box<dyn std::error::Error + Send + Sync>> {
+        self.block_on(self.inner.fetch_bootstrap())
+    }
+
+    /// Query given RDAP server for IPv4 or IPv6 address.
+    pub fn query_ip<I: Into<IpAddr>>(
+        &self,
+        server: &str,
+        ip: I,
+    ) -> Result<parser::IpNetwork, ClientError> {
+        self.block_on(self.inner.query_ip(server, ip))
+    }
+
+    /// Query given RDAP server for IP network.
+    pub fn query_ip_network<I: Into<IpNetwork>>(
+        &self,
+        server: &str,
+        ip_network: I,
+    ) -> Result<parser::IpNetwork, ClientError> {
+        self.block_on(self.inner.query_ip_network(server, ip_network))
+    }
+
+    /// Query given RDAP server for AS number.
+    pub fn query_asn(&self, server: &str, asn: u32) -> Result<parser::AutNum, ClientError> {
+        self.block_on(self.inner.query_asn(server, asn))
+    }
+
+    /// Query given RDAP server for nameserver handle.
+    pub fn query_nameserver(
+        &self,
+        server: &str,
+        nameserver: &str,
+    ) -> Result<parser::Nameserver, ClientError> {
+        self.block_on(self.inner.query_nameserver(server, nameserver))
+    }
+
+    /// Query given RDAP server for domain by name.
+    pub fn query_domain(&self, server: &str, domain: &str) -> Result<parser::Domain, ClientError> {
+        self.block_on(self.inner.query_domain(server, domain))
+    }
+
+    pub fn query_reverse_domain<I: Into<IpAddr>>(
+        &self,
+        server: &str,
+        ip: I,
+    ) -> Result<parser::Domain, ClientError> {
+        self.block_on(self.inner.query_reverse_domain(server, ip))
+    }
+
+    /// Query given RDAP server for entity by its name.
+    pub fn query_entity(&self, server: &str, entity: &str) -> Result<parser::Entity, ClientError> {
+        self.block_on(self.inner.query_entity(server, entity))
+    }
+
+    /// Search given RDAP server for nameserver by name or IP address.
+    pub fn search_nameserver(
+        &self,
+        server: &str,
+        search_nameserver: SearchNameserver,
+    ) -> Result<parser::NameserverSearchResults, ClientError> {
+        self.block_on(self.inner.search_nameserver(server, search_nameserver))
+    }
+
+    /// Search given RDAP server for domain by name, NS LDH name or NS IP address.
+    pub fn search_domain(
+        &self,
+        server: &str,
+        search_domain: SearchDomain,
+    ) -> Result<parser::DomainSearchResults, ClientError> {
+        self.block_on(self.inner.search_domain(server, search_domain))
+    }
+
+    /// Search for domains by name, that can contain wildcard `*`, at registry found in DNS
+    /// bootstrap.
+    pub fn search_domains(
+        &self,
+        search: SearchDomain,
+    ) -> Result<parser::DomainSearchResults, ClientError> {
+        self.block_on(self.inner.search_domains(search))
+    }
+
+    /// Search for domains at registry of given domain or TLD, for example by nameserver.
+    pub fn search_domains_in(
+        &self,
+        registry: &str,
+        search: SearchDomain,
+    ) -> Result<parser::DomainSearchResults, ClientError> {
+        self.block_on(self.inner.search_domains_in(registry, search))
+    }
+
+    /// Search for nameservers by name, that can contain wildcard `*`, at registry found in DNS
+    /// bootstrap.
+    pub fn search_nameservers(
+        &self,
+        search: SearchNameserver,
+    ) -> Result<parser::NameserverSearchResults, ClientError> {
+        self.block_on(self.inner.search_nameservers(search))
+    }
+
+    /// Search for nameservers at registry of given domain or TLD, for example by IP address.
+    pub fn search_nameservers_in(
+        &self,
+        registry: &str,
+        search: SearchNameserver,
+    ) -> Result<parser::NameserverSearchResults, ClientError> {
+        self.block_on(self.inner.search_nameservers_in(registry, search))
+    }
+
+    /// Search for entities by full name or handle, that can end with wildcard `*`, at given server
+    /// or server found by object tag of handle.
+    pub fn search_entities(
+        &self,
+        search: SearchEntity,
+        server: Option<&str>,
+    ) -> Result<parser::EntitySearchResults, ClientError> {
+        self.block_on(self.inner.search_entities(search, server))
+    }
+
+    /// Search for entities at all given servers or at all RIRs, results are merged.
+    pub fn search_entities_fan_out(
+        &self,
+        search: SearchEntity,
+        servers: Option<&[&str]>,
+    ) -> Result<FanOutResults<parser::Entity>, ClientError> {
+        self.block_on(self.inner.search_entities_fan_out(search, servers))
+    }
+
+    /// Search for nameservers at all given servers or at all RIRs, results are merged.
+    pub fn search_nameservers_fan_out(
+        &self,
+        search: SearchNameserver,
+        servers: Option<&[&str]>,
+    ) -> Result<FanOutResults<parser::Nameserver>, ClientError> {
+        self.block_on(self.inner.search_nameservers_fan_out(search, servers))
+    }
+
+    /// Search given RDAP server for domain by FN or handle.
+    pub fn search_entity(
+        &self,
+        server: &str,
+        search_entity: SearchEntity,
+    ) -> Result<parser::EntitySearchResults, ClientError> {
+        self.block_on(self.inner.search_entity(server, search_entity))
+    }
+
+    /// Method from [`arin_originas0` extension.](https://bitbucket.org/arin-specs/arin-rdap-originas/src/master/arin-rdap-originas.txt).
+    /// Given server must support this method. Returns result of `IpNetwork`s.
+    pub fn search_networks_by_origin_as(
+        &self,
+        server: &str,
+        asn: u32,
+    ) -> Result<parser::ArinOriginas0OriginautnumsResults, ClientError> {
+        self.block_on(self.inner.search_networks_by_origin_as(server, asn))
+    }
+
+    /// Help method.
+    pub fn help(&self, server: &str) -> Result<parser::Help, ClientError> {
+        self.block_on(self.inner.help(server))
+    }
+
+    /// Send reverse search (RFC 9536) to given RDAP server.
+    pub fn reverse_search<T: DeserializeOwned>(
+        &self,
+        server: &str,
+        search: ReverseSearch<T>,
+    ) -> Result<T, ClientError> {
+        self.block_on(self.inner.reverse_search(server, search))
+    }
+
+    /// Fetch RDAP response from given URL and parse it as object, search results or help.
+    pub fn fetch_url<U: IntoUrl>(
+        &self,
+        url: U,
+    ) -> Result<Response<parser::TopLevelResponse>, ClientError> {
+        self.block_on(self.inner.fetch_url(url))
+    }
+
+    /// Fetch target of link returned in other response.
+    pub fn follow(
+        &self,
+        link: &parser::Link,
+    ) -> Result<Response<parser::TopLevelResponse>, ClientError> {
+        self.block_on(self.inner.follow(link))
+    }
+
+    /// Send query built by `RdapQuery` and parse response as `T`.
+    pub fn send_query<T: DeserializeOwned>(
+        &self,
+        query: &RdapQuery,
+    ) -> Result<Response<T>, ClientError> {
+        self.block_on(self.inner.send_query(query))
+    }
+
+    /// Find RDAP server for given domain in bootstrap and query it.
+    pub fn domain(&self, domain: &str) -> Result<parser::Domain, ClientError> {
+        self.block_on(self.inner.domain(domain))
+    }
+
+    /// Find RDAP server for given domain in bootstrap and query it for domain by name.
+    pub fn lookup_domain(&self, domain: &str) -> Result<Response<parser::Domain>, ClientError> {
+        self.block_on(self.inner.lookup_domain(domain))
+    }
+
+    /// Check if domain is registered by HEAD request, falling back to GET.
+    pub fn exists_domain(&self, domain: &str) -> Result<bool, ClientError> {
+        self.block_on(self.inner.exists_domain(domain))
+    }
+
+    /// Like `lookup_domain`, but domain is fetched also from registrar, when registry response
+    /// contains `related` link to it.
+    pub fn lookup_domain_with_registrar(
+        &self,
+        domain: &str,
+    ) -> Result<DomainWithRegistrar, ClientError> {
+        self.block_on(self.inner.lookup_domain_with_registrar(domain))
+    }
+
+    /// Find RDAP server for given IP address or network in bootstrap and query it.
+    pub fn ip<Q>(&self, query: Q) -> Result<parser::IpNetwork, ClientError>
+    where
+        Q: TryInto<IpQuery>,
+        ClientError: From<Q::Error>,
+    {
+        self.block_on(self.inner.ip(query))
+    }
+
+    /// Find RDAP server for given IPv4 or IPv6 address in bootstrap and query it for IP network.
+    pub fn lookup_ip<I: Into<IpAddr>>(
+        &self,
+        ip: I,
+    ) -> Result<Response<parser::IpNetwork>, ClientError> {
+        self.block_on(self.inner.lookup_ip(ip))
+    }
+
+    /// Find RDAP server for given AS number in ASN bootstrap and query it.
+    pub fn autnum(&self, asn: u32) -> Result<Response<parser::AutNum>, ClientError> {
+        self.block_on(self.inner.autnum(asn))
+    }
+
+    /// Like `autnum`, but AS number is parsed from string like `AS1234`, `1234` or `AS1.10`.
+    pub fn autnum_str(&self, asn: &str) -> Result<Response<parser::AutNum>, ClientError> {
+        self.block_on(self.inner.autnum_str(asn))
+    }
+
+    /// Find RDAP server for given AS number in bootstrap and query it.
+    pub fn lookup_asn(&self, asn: u32) -> Result<Response<parser::AutNum>, ClientError> {
+        self.block_on(self.inner.lookup_asn(asn))
+    }
+
+    /// Query registry of domain of given nameserver host for that nameserver.
+    pub fn nameserver(&self, host: &str) -> Result<parser::Nameserver, ClientError> {
+        self.block_on(self.inner.nameserver(host))
+    }
+
+    /// Query entity by handle, server is found in object tags bootstrap unless it is given.
+    pub fn entity(
+        &self,
+        handle: &str,
+        server: Option<&str>,
+    ) -> Result<parser::Entity, ClientError> {
+        self.block_on(self.inner.entity(handle, server))
+    }
+
+    /// Find RDAP server for given entity handle in object tags bootstrap (RFC 8521) and query it.
+    pub fn lookup_entity(&self, entity: &str) -> Result<Response<parser::Entity>, ClientError> {
+        self.block_on(self.inner.lookup_entity(entity))
+    }
+
+    /// Lookup object given as string, like `192.0.2.1`, `AS15169`, `ABC123-ARIN` or `example.com`.
+    pub fn lookup_str(&self, query: &str) -> Result<Response<parser::Object>, ClientError> {
+        self.block_on(self.inner.lookup_str(query))
+    }
+
+    /// Lookup many objects concurrently, at most `max_concurrency` requests are in flight at the
+    /// same time. Results are returned in the same order as given lookups.
+    pub fn lookup_many<I: IntoIterator<Item = Lookup>>(
+        &self,
+        lookups: I,
+        max_concurrency: usize,
+    ) -> Vec<Result<Response<parser::Object>, ClientError>> {
+        self.block_on(self.inner.lookup_many(lookups, max_concurrency).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Client;
+
+    #[test]
+    fn test_send_sync() {
+        fn is_send_sync<T: Send + Sync>() {}
+        is_send_sync::<Client>(); // compiles only if true
+    }
+}
@@ -0,0 +1,228 @@
+//! Reading of response bodies.
+//!
+//! On native targets response is kept after body is read, so it can be returned in errors. On
+//! wasm32 reading body consumes response, so just URL, status code and headers are kept.
+
+use crate::metrics::PendingMetrics;
+use crate::{parser, ClientError};
+use bytes::{Bytes, BytesMut};
+use reqwest::header::HeaderMap;
+use serde::de::DeserializeOwned;
+
+/// Response without body.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) struct Head(reqwest::Response);
+
+/// Response without body.
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct Head {
+    url: reqwest::Url,
+    status: reqwest::StatusCode,
+    headers: HeaderMap,
+}
+
+impl Head {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn new(response: reqwest::Response) -> Self {
+        Self(response)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn new(response: &reqwest::Response) -> Self {
+        Self {
+            url: response.url().clone(),
+            status: response.status(),
+            headers: response.headers().clone(),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn headers(&self) -> &HeaderMap {
+        self.0.headers()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Convert to error for response with empty body (`error` is `None`) or invalid JSON.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn into_error(self, error: Option<serde_json::Error>) -> ClientError {
+        match error {
+            Some(error) => ClientError::JsonDecode(Box::new(self.0), error),
+            None => ClientError::Server(Box::new(self.0)),
+        }
+    }
+
+    /// Convert to error for response with empty body (`error` is `None`) or invalid JSON.
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn into_error(self, error: Option<serde_json::Error>) -> ClientError {
+        ClientError::InvalidBody {
+            url: Box::new(self.url),
+            status: self.status,
+            error,
+        }
+    }
+}
+
+/// Body of response that is read chunk by chunk.
+pub(crate) struct Chunks {
+    #[cfg(not(target_arch = "wasm32"))]
+    response: reqwest::Response,
+    #[cfg(target_arch = "wasm32")]
+    head: Head,
+    #[cfg(target_arch = "wasm32")]
+    stream: futures::stream::LocalBoxStream<'static, reqwest::Result<Bytes>>,
+    url: reqwest::Url,
+    /// Maximum size of whole body, `None` if not limited.
+    max_size: Option<u64>,
+    read: u64,
+    /// Metrics of request, recorded with number of read bytes when chunks are dropped.
+    metrics: Option<PendingMetrics>,
+}
+
+impl Chunks {
+    pub(crate) fn new(mut response: reqwest::Response, max_size: Option<u64>) -> Self {
+        let url = response.url().clone();
+        let metrics = PendingMetrics::take(&mut response);
+        #[cfg(target_arch = "wasm32")]
+        let (head, stream) = {
+            use futures::StreamExt;
+
+            (Head::new(&response), response.bytes_stream().boxed_local())
+        };
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            response,
+            #[cfg(target_arch = "wasm32")]
+            head,
+            #[cfg(target_arch = "wasm32")]
+            stream,
+            url,
+            max_size,
+            read: 0,
+            metrics,
+        }
+    }
+
+    /// Returns next chunk of body or `ClientError::ResponseTooLarge`, when body is larger than
+    /// maximum size.
+    pub(crate) async fn next(&mut self) -> Result<Option<Bytes>, ClientError> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let chunk = self.response.chunk().await?;
+        #[cfg(target_arch = "wasm32")]
+        let chunk = {
+            use futures::StreamExt;
+
+            self.stream.next().await.transpose()?
+        };
+
+        if let Some(chunk) = &chunk {
+            self.read += chunk.len() as u64;
+            if let Some(metrics) = &mut self.metrics {
+                metrics.add_bytes(chunk.len() as u64);
+            }
+            self.check_size(self.read)?;
+        }
+        Ok(chunk)
+    }
+
+    /// Returns error if given size exceeds maximum size. Used also for checking `Content-Length`,
+    /// so too large body is not downloaded at all.
+    fn check_size(&self, size: u64) -> Result<(), ClientError> {
+        match self.max_size {
+            Some(limit) if size > limit => Err(ClientError::ResponseTooLarge {
+                url: Box::new(self.url.clone()),
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn into_head(self) -> Head {
+        Head::new(self.response)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn into_head(self) -> Head {
+        self.head
+    }
+}
+
+/// Response with completely read body.
+pub(crate) struct Body {
+    pub(crate) head: Head,
+    pub(crate) bytes: Bytes,
+}
+
+impl Body {
+    /// Read whole body, but at most `max_size` bytes.
+    pub(crate) async fn read(
+        response: reqwest::Response,
+        max_size: Option<u64>,
+    ) -> Result<Self, ClientError> {
+        let content_length = response.content_length();
+        let mut chunks = Chunks::new(response, max_size);
+
+        // Preallocate buffer if content length is known, but 8 KB maximum.
+        let mut buf = if let Some(content_length) = content_length {
+            chunks.check_size(content_length)?;
+            BytesMut::with_capacity(8192.min(content_length) as usize)
+        } else {
+            BytesMut::new()
+        };
+
+        while let Some(chunk) = chunks.next().await? {
+            buf.extend(chunk);
+        }
+
+        Ok(Self {
+            head: chunks.into_head(),
+            bytes: buf.freeze(),
+        })
+    }
+
+    /// Use given body instead of body of response, for example cached one.
+    pub(crate) fn with_bytes(response: reqwest::Response, bytes: Bytes) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let head = Head::new(response);
+        #[cfg(target_arch = "wasm32")]
+        let head = Head::new(&response);
+        Self { head, bytes }
+    }
+
+    pub(crate) fn decode<T: DeserializeOwned>(
+        self,
+        options: parser::ParseOptions,
+    ) -> Result<T, ClientError> {
+        // Server returns empty response, doesnt make sense to try parse as JSON.
+        if self.bytes.is_empty() {
+            return Err(self.head.into_error(None));
+        }
+
+        #[cfg(feature = "tracing")]
+        let start = crate::rt::Instant::now();
+        match options.parse_slice(&self.bytes) {
+            Ok(object) => {
+                debug!(
+                    object_class = std::any::type_name::<T>(),
+                    bytes = self.bytes.len(),
+                    elapsed = ?start.elapsed(),
+                    "response deserialized"
+                );
+                Ok(object)
+            }
+            Err(e) => {
+                warn!(
+                    object_class = std::any::type_name::<T>(),
+                    bytes = self.bytes.len(),
+                    error = %e,
+                    "deserialization failed"
+                );
+                Err(self.head.into_error(Some(e)))
+            }
+        }
+    }
+}
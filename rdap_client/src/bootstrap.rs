@@ -1,10 +1,12 @@
 use crate::parser::{self, BootstrapService};
+use chrono::{DateTime, FixedOffset};
 use ip_network::{IpNetwork, IpNetworkParseError};
 use ip_network_table::IpNetworkTable;
 use serde::de::{SeqAccess, Visitor};
 use serde::ser::SerializeSeq;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
 use std::net::IpAddr;
@@ -33,12 +35,230 @@ fn hashmap_serializer<'a, S: Serializer, V: Serialize>(
     seq.end()
 }
 
-#[derive(Serialize, Deserialize)]
+/// Base URL of IANA bootstrap registries.
+pub const IANA_BOOTSTRAP_URL: &str = "https://data.iana.org/rdap/";
+
+/// NRO extended delegated stats, that contains all IP and ASN delegations of all RIRs.
+pub const NRO_DELEGATED_STATS_URL: &str =
+    "https://ftp.ripe.net/pub/stats/ripencc/nro-stats/latest/nro-delegated-stats";
+
+/// RDAP server base URLs of all five RIRs.
+pub const RIR_SERVERS: [&str; 5] = [
+    "https://rdap.afrinic.net/rdap/",
+    "https://rdap.apnic.net/",
+    "https://rdap.arin.net/registry/",
+    "https://rdap.lacnic.net/rdap/",
+    "https://rdap.db.ripe.net/",
+];
+
+/// Returns RDAP server base URL of RIR by its name used in delegated stats.
+fn rir_server(registry: &str) -> Option<&'static str> {
+    match registry {
+        "afrinic" => Some("https://rdap.afrinic.net/rdap/"),
+        "apnic" => Some("https://rdap.apnic.net/"),
+        "arin" => Some("https://rdap.arin.net/registry/"),
+        "lacnic" => Some("https://rdap.lacnic.net/rdap/"),
+        "ripencc" => Some("https://rdap.db.ripe.net/"),
+        _ => None,
+    }
+}
+
+/// All IANA bootstrap registries converted to structures for fast searching of RDAP servers.
+#[derive(Serialize, Deserialize, Default)]
 pub struct Bootstrap {
     pub dns: Dns,
     pub ip: Ip,
     pub asn: Asn,
     pub object_tags: ObjectTags,
+    /// The latest publication time of registries, `None` when it is not known.
+    #[serde(default)]
+    pub publication: Option<DateTime<FixedOffset>>,
+}
+
+impl Bootstrap {
+    /// Create bootstrap from parsed registries, for example loaded from files downloaded from
+    /// `IANA_BOOTSTRAP_URL`.
+    pub fn from_registries(
+        asn: &parser::BootstrapRfc7484,
+        dns: &parser::BootstrapRfc7484,
+        ipv4: &parser::BootstrapRfc7484,
+        ipv6: &parser::BootstrapRfc7484,
+        object_tags: &parser::BootstrapRfc8521,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let publication = [
+            asn.publication,
+            dns.publication,
+            ipv4.publication,
+            ipv6.publication,
+            object_tags.publication,
+        ]
+        .into_iter()
+        .max();
+        Ok(Self {
+            dns: Dns::from(dns),
+            ip: Ip::try_from((ipv4, ipv6))?,
+            asn: Asn::try_from(asn)?,
+            object_tags: ObjectTags::from(object_tags),
+            publication,
+        })
+    }
+}
+
+impl Bootstrap {
+    /// Create bootstrap from RIR delegated stats in extended format, like file downloaded from
+    /// `NRO_DELEGATED_STATS_URL`. Allocated and assigned IP networks and AS numbers are routed to
+    /// RDAP server of RIR that delegated them, so also legacy and ERX space that is not covered by
+    /// IANA bootstrap can be queried. Lines of other types and statuses are skipped.
+    pub fn from_delegated_stats(
+        stats: &str,
+    ) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut servers: HashMap<&str, Arc<Vec<String>>> = HashMap::new();
+        let mut bootstrap = Self::default();
+        for (number, line) in stats.lines().enumerate() {
+            // Line format is `registry|cc|type|start|value|date|status[|extensions]`.
+            let fields: Vec<_> = line.trim().split('|').collect();
+            if line.starts_with('#') || fields.len() < 7 || fields[3] == "*" {
+                continue;
+            }
+            let (registry, kind, start, value, status) =
+                (fields[0], fields[2], fields[3], fields[4], fields[6]);
+            let server = match rir_server(registry) {
+                Some(server) if matches!(status, "allocated" | "assigned") => server,
+                _ => continue,
+            };
+            let servers = servers
+                .entry(server)
+                .or_insert_with(|| Arc::new(vec![server.to_string()]));
+            let invalid = |e: &dyn fmt::Display| format!("invalid line {}: {}", number + 1, e);
+            match kind {
+                "asn" => {
+                    let start: u32 = start.parse().map_err(|e| invalid(&e))?;
+                    let count: u32 = value.parse().map_err(|e| invalid(&e))?;
+                    let end = count
+                        .checked_sub(1)
+                        .and_then(|count| start.checked_add(count))
+                        .ok_or_else(|| invalid(&"ASN range out of bounds"))?;
+                    bootstrap.asn.insert_one(start..=end, servers);
+                }
+                "ipv4" => {
+                    let start: std::net::Ipv4Addr = start.parse().map_err(|e| invalid(&e))?;
+                    let count: u64 = value.parse().map_err(|e| invalid(&e))?;
+                    for network in ipv4_networks(u32::from(start), count) {
+                        bootstrap.ip.table.insert(network, Arc::clone(servers));
+                    }
+                }
+                "ipv6" => {
+                    let start: std::net::Ipv6Addr = start.parse().map_err(|e| invalid(&e))?;
+                    let prefix: u8 = value.parse().map_err(|e| invalid(&e))?;
+                    let network = IpNetwork::new(start, prefix).map_err(|e| invalid(&e))?;
+                    bootstrap.ip.table.insert(network, Arc::clone(servers));
+                }
+                _ => {}
+            }
+        }
+        bootstrap.asn.sort();
+        bootstrap.ip.build_index();
+        Ok(bootstrap)
+    }
+}
+
+/// Split range of `count` IPv4 addresses from `start` to the smallest number of CIDR networks,
+/// because delegated stats contains ranges that are not aligned to prefix.
+fn ipv4_networks(start: u32, count: u64) -> Vec<ip_network::Ipv4Network> {
+    let mut networks = Vec::new();
+    let mut start = start as u64;
+    let end = start.saturating_add(count).min(1 << 32);
+    while start < end {
+        let mut bits = start.trailing_zeros().min(32);
+        while 1 << bits > end - start {
+            bits -= 1;
+        }
+        // Network is aligned to its prefix, so it is always valid.
+        let network = ip_network::Ipv4Network::new((start as u32).into(), 32 - bits as u8).unwrap();
+        networks.push(network);
+        start += 1 << bits;
+    }
+    networks
+}
+
+#[cfg(feature = "embedded-bootstrap")]
+impl Bootstrap {
+    /// Snapshot of IANA bootstrap registries compiled into the crate, check `publication` for its
+    /// age. `Client` uses it when bootstrap cannot be fetched. Snapshot is refreshed by
+    /// `bootstrap/update.sh`.
+    pub fn embedded() -> Self {
+        // Snapshot is tested, so parsing cannot fail.
+        Self::from_registries(
+            &serde_json::from_str(include_str!("../bootstrap/asn.json")).unwrap(),
+            &serde_json::from_str(include_str!("../bootstrap/dns.json")).unwrap(),
+            &serde_json::from_str(include_str!("../bootstrap/ipv4.json")).unwrap(),
+            &serde_json::from_str(include_str!("../bootstrap/ipv6.json")).unwrap(),
+            &serde_json::from_str(include_str!("../bootstrap/object-tags.json")).unwrap(),
+        )
+        .unwrap()
+    }
+}
+
+/// Custom mappings that take precedence over IANA bootstrap, for example for TLD which RDAP
+/// server is not registered in IANA yet or for internal RDAP server for private address space.
+///
+/// ```
+/// use rdap_client::bootstrap::Overrides;
+///
+/// let overrides = Overrides::new()
+///     .domain("example", "https://rdap.nic.example/")
+///     .ip("10.0.0.0/8".parse().unwrap(), "https://rdap.corp.example/")
+///     .asn(64512..=65534, "https://rdap.corp.example/");
+/// ```
+#[derive(Default)]
+pub struct Overrides(pub(crate) Bootstrap);
+
+impl Overrides {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Use server for domains with given suffix, like `example` or `sub.example`.
+    pub fn domain(mut self, suffix: &str, server: &str) -> Self {
+        let suffix = idna::domain_to_ascii(suffix).unwrap_or_else(|_| suffix.to_lowercase());
+        self.0.dns.insert(vec![base_url(server)], vec![suffix]);
+        self
+    }
+
+    /// Use server for addresses in given network.
+    pub fn ip(mut self, network: IpNetwork, server: &str) -> Self {
+        self.0.ip.insert(vec![base_url(server)], vec![network]);
+        self
+    }
+
+    /// Use server for AS numbers in given range.
+    pub fn asn(mut self, range: RangeInclusive<u32>, server: &str) -> Self {
+        self.0.asn.insert(vec![base_url(server)], vec![range]);
+        self
+    }
+
+    /// Use server for entity handles with given object tag, like `EXAMPLE` for `ABC-EXAMPLE`.
+    pub fn object_tag(mut self, tag: &str, server: &str) -> Self {
+        self.0
+            .object_tags
+            .insert(vec![base_url(server)], vec![tag.to_uppercase()]);
+        self
+    }
+}
+
+impl fmt::Debug for Overrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Overrides(..)")
+    }
+}
+
+/// Server base URLs from bootstrap end with slash, so query path can be just appended.
+pub(crate) fn base_url(server: &str) -> String {
+    if server.ends_with('/') {
+        server.to_string()
+    } else {
+        format!("{}/", server)
+    }
 }
 
 #[derive(Debug, Default)]
@@ -117,8 +337,41 @@ impl<'de> Deserialize<'de> for ArcHashMap {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Default)]
-pub struct Dns(ArcHashMap);
+/// Trie of domain labels from TLD, so the longest matching suffix is found by one walk from root.
+#[derive(Debug, Default)]
+struct LabelTrie {
+    servers: Option<Arc<Vec<String>>>,
+    children: HashMap<Box<str>, LabelTrie>,
+}
+
+impl LabelTrie {
+    fn insert(&mut self, suffix: &str, servers: Arc<Vec<String>>) {
+        let node = suffix.rsplit('.').fold(self, |node, label| {
+            node.children.entry(label.into()).or_default()
+        });
+        node.servers = Some(servers);
+    }
+
+    /// Returns servers of the longest suffix, domain must be lowercase and in A-labels.
+    fn find(&self, domain: &str) -> Option<&Vec<String>> {
+        let mut node = self;
+        let mut servers = None;
+        for label in domain.rsplit('.') {
+            node = match node.children.get(label) {
+                Some(child) => child,
+                None => break,
+            };
+            servers = node.servers.as_deref().or(servers);
+        }
+        servers
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Dns {
+    domains: ArcHashMap,
+    trie: LabelTrie,
+}
 
 impl Dns {
     pub fn new() -> Self {
@@ -126,39 +379,87 @@ impl Dns {
     }
 
     pub fn insert(&mut self, servers: Vec<String>, domains: Vec<String>) {
-        self.0.insert(servers, domains);
+        let servers = Arc::new(servers);
+        for domain in domains {
+            self.trie.insert(&domain, Arc::clone(&servers));
+            self.domains.0.insert(domain, Arc::clone(&servers));
+        }
     }
 
+    /// Returns servers for the longest matching label suffix of given domain. Domain is converted
+    /// to A-labels first, so for example `münchen.de` matches `de` and `пример.рф` matches
+    /// `xn--p1ai`.
     pub fn find(&self, domain: &str) -> Option<&Vec<String>> {
-        let domain_parts: Vec<_> = domain.split('.').collect();
-        for i in 0..domain_parts.len() {
-            let domain_to_check = domain_parts[i..].join(".");
-            if let Some(servers) = self.0.get(&domain_to_check) {
-                return Some(servers);
-            }
-        }
-
-        None
+        let domain = domain.trim_end_matches('.');
+        let is_ascii_lowercase = domain
+            .bytes()
+            .all(|b| matches!(b, b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.'));
+        // Most of domains are already in A-labels, so conversion is skipped for them. Invalid IDN
+        // can still match by its last labels.
+        let domain = if is_ascii_lowercase {
+            Cow::Borrowed(domain)
+        } else {
+            Cow::Owned(idna::domain_to_ascii(domain).unwrap_or_else(|_| domain.to_lowercase()))
+        };
+        self.trie.find(&domain)
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&String, &Vec<String>)> {
-        self.0.iter()
+        self.domains.iter()
+    }
+}
+
+impl From<ArcHashMap> for Dns {
+    fn from(domains: ArcHashMap) -> Self {
+        let mut trie = LabelTrie::default();
+        for (domain, servers) in &domains.0 {
+            trie.insert(domain, Arc::clone(servers));
+        }
+        Self { domains, trie }
     }
 }
 
 impl From<&parser::BootstrapRfc7484> for Dns {
     fn from(bootstrap: &parser::BootstrapRfc7484) -> Self {
-        Self(ArcHashMap::from(bootstrap))
+        Self::from(ArcHashMap::from(bootstrap))
+    }
+}
+
+impl Serialize for Dns {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.domains.serialize(serializer)
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+impl<'de> Deserialize<'de> for Dns {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ArcHashMap::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Object tags bootstrap (RFC 8521), that routes entity handles like `ABC123-ARIN` by their
+/// tag suffix.
+#[derive(Serialize, Deserialize, Debug, Default)]
 pub struct ObjectTags(ArcHashMap);
 
 impl ObjectTags {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn insert(&mut self, servers: Vec<String>, tags: Vec<String>) {
+        self.0.insert(servers, tags);
+    }
+
+    /// Returns tag of given entity handle, that is part after the last hyphen.
+    pub fn tag(handle: &str) -> Option<&str> {
+        parser::split_handle(handle).1
+    }
+
+    /// Returns servers for tag of given entity handle. Tags are matched case insensitively.
     pub fn find(&self, name: &str) -> Option<&Vec<String>> {
-        let handle_last_part = name.split('-').last().unwrap();
-        self.0.get(handle_last_part)
+        let tag = Self::tag(name)?;
+        self.0.get(tag).or_else(|| self.0.get(&tag.to_uppercase()))
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&String, &Vec<String>)> {
@@ -172,21 +473,191 @@ impl From<&parser::BootstrapRfc8521> for ObjectTags {
     }
 }
 
-pub struct Ip(IpNetworkTable<Arc<Vec<String>>>);
+/// Range of addresses, that are covered by the same longest prefix.
+struct Interval {
+    start: u128,
+    end: u128,
+    network: IpNetwork,
+    servers: Arc<Vec<String>>,
+}
+
+/// Split nested prefixes to disjoint intervals sorted by start, where each interval belongs to
+/// the longest prefix that contains it. Prefixes must be from one address family.
+fn intervals<'a>(
+    prefixes: impl Iterator<Item = (IpNetwork, &'a Arc<Vec<String>>)>,
+) -> Vec<Interval> {
+    fn push(intervals: &mut Vec<Interval>, next: &mut Option<u128>, end: u128, prefix: &Interval) {
+        if let Some(start) = *next {
+            if start <= end {
+                intervals.push(Interval {
+                    start,
+                    end,
+                    network: prefix.network,
+                    servers: Arc::clone(&prefix.servers),
+                });
+                *next = end.checked_add(1);
+            }
+        }
+    }
+
+    let mut prefixes: Vec<_> = prefixes
+        .map(|(network, servers)| {
+            let (start, end) = network_range(network);
+            Interval {
+                start,
+                end,
+                network,
+                servers: Arc::clone(servers),
+            }
+        })
+        .collect();
+    // Prefixes are either nested or disjoint, so wider prefix is always before its subnets.
+    prefixes.sort_by_key(|prefix| (prefix.start, std::cmp::Reverse(prefix.end)));
+
+    let mut intervals = Vec::with_capacity(prefixes.len());
+    // Prefixes that contain current address, the longest one is the last.
+    let mut stack: Vec<Interval> = Vec::new();
+    // The first address that is not covered by intervals yet, `None` after the last address.
+    let mut next = Some(0);
+    for prefix in prefixes {
+        while stack.last().map_or(false, |top| top.end < prefix.start) {
+            let top = stack.pop().unwrap();
+            push(&mut intervals, &mut next, top.end, &top);
+        }
+        if let (Some(top), Some(end)) = (stack.last(), prefix.start.checked_sub(1)) {
+            push(&mut intervals, &mut next, end, top);
+        }
+        next = Some(prefix.start);
+        stack.push(prefix);
+    }
+    while let Some(top) = stack.pop() {
+        push(&mut intervals, &mut next, top.end, &top);
+    }
+    intervals
+}
+
+/// IP address bootstrap, registry keys are parsed as CIDR prefixes.
+pub struct Ip {
+    table: IpNetworkTable<Arc<Vec<String>>>,
+    /// Index of `table` for longest prefix match by binary search.
+    ipv4: Vec<Interval>,
+    ipv6: Vec<Interval>,
+}
+
+impl Default for Ip {
+    fn default() -> Self {
+        Self::from(IpNetworkTable::new())
+    }
+}
+
+impl From<IpNetworkTable<Arc<Vec<String>>>> for Ip {
+    fn from(table: IpNetworkTable<Arc<Vec<String>>>) -> Self {
+        let mut ip = Self {
+            table,
+            ipv4: Vec::new(),
+            ipv6: Vec::new(),
+        };
+        ip.build_index();
+        ip
+    }
+}
 
 impl Ip {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Insert networks and rebuild index used by `find`.
+    pub fn insert(&mut self, servers: Vec<String>, networks: Vec<IpNetwork>) {
+        let servers = Arc::new(servers);
+        for network in networks {
+            self.table.insert(network, Arc::clone(&servers));
+        }
+        self.build_index();
+    }
+
+    fn build_index(&mut self) {
+        let (ipv4, ipv6): (Vec<_>, Vec<_>) = self
+            .table
+            .iter()
+            .partition(|(network, _)| matches!(network, IpNetwork::V4(_)));
+        self.ipv4 = intervals(ipv4.into_iter());
+        self.ipv6 = intervals(ipv6.into_iter());
+    }
+
+    /// Returns servers for the longest prefix that contains given address, as required by
+    /// RFC 7484 section 5.1.
     pub fn find<I: Into<IpAddr>>(&self, ip: I) -> Option<&Vec<String>> {
-        self.0.longest_match(ip.into()).map(|(_, v)| v.as_ref())
+        self.find_match(ip).map(|(_, servers)| servers)
+    }
+
+    /// Like `find`, but returns also matched prefix.
+    pub fn find_match<I: Into<IpAddr>>(&self, ip: I) -> Option<(IpNetwork, &Vec<String>)> {
+        let (intervals, address) = match ip.into() {
+            IpAddr::V4(address) => (&self.ipv4, u32::from(address) as u128),
+            IpAddr::V6(address) => (&self.ipv6, u128::from(address)),
+        };
+        let i = intervals
+            .partition_point(|interval| interval.start <= address)
+            .checked_sub(1)?;
+        let interval = &intervals[i];
+        (address <= interval.end).then(|| (interval.network, interval.servers.as_ref()))
+    }
+
+    /// Returns servers for the longest prefix that covers whole given network.
+    pub fn find_network(&self, network: IpNetwork) -> Option<&Vec<String>> {
+        self.table
+            .matches(network.network_address())
+            .filter(|(prefix, _)| prefix.netmask() <= network.netmask())
+            .max_by_key(|(prefix, _)| prefix.netmask())
+            .map(|(_, servers)| servers.as_ref())
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (IpNetwork, &Vec<String>)> {
-        self.0.iter().map(|(i, v)| (i, v.as_ref()))
+        self.table.iter().map(|(i, v)| (i, v.as_ref()))
+    }
+}
+
+/// Returns IP network of reverse DNS domain like `6.0.193.in-addr.arpa` (`193.0.6.0/24`) or
+/// `8.b.d.0.1.0.0.2.ip6.arpa` (`2001:db8::/32`). Reverse domains are served by RIRs, so they are
+/// routed by IP bootstrap. Returns `None` for other domains, including classless delegations.
+pub fn reverse_network(domain: &str) -> Option<IpNetwork> {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    if let Some(labels) = domain.strip_suffix(".in-addr.arpa") {
+        let octets: Vec<_> = labels.rsplit('.').collect();
+        if octets.len() > 4 {
+            return None;
+        }
+        let mut address = [0; 4];
+        for (i, octet) in octets.iter().enumerate() {
+            if octet.is_empty() || !octet.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            address[i] = octet.parse().ok()?;
+        }
+        IpNetwork::new(IpAddr::from(address), octets.len() as u8 * 8).ok()
+    } else if let Some(labels) = domain.strip_suffix(".ip6.arpa") {
+        let nibbles: Vec<_> = labels.rsplit('.').collect();
+        if nibbles.len() > 32 {
+            return None;
+        }
+        let mut address = 0u128;
+        for (i, nibble) in nibbles.iter().enumerate() {
+            if nibble.len() != 1 {
+                return None;
+            }
+            let nibble = u128::from_str_radix(nibble, 16).ok()?;
+            address |= nibble << (124 - i * 4);
+        }
+        IpNetwork::new(IpAddr::from(address.to_be_bytes()), nibbles.len() as u8 * 4).ok()
+    } else {
+        None
     }
 }
 
 impl Serialize for Ip {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        hashmap_serializer(serializer, self.0.iter())
+        hashmap_serializer(serializer, self.table.iter())
     }
 }
 
@@ -217,7 +688,7 @@ impl<'de> Deserialize<'de> for Ip {
                         ip_table.insert(network, Arc::clone(&servers));
                     }
                 }
-                Ok(Ip(ip_table))
+                Ok(Ip::from(ip_table))
             }
         }
 
@@ -242,12 +713,20 @@ impl TryFrom<(&parser::BootstrapRfc7484, &parser::BootstrapRfc7484)> for Ip {
             }
         }
 
-        Ok(Self(table))
+        Ok(Self::from(table))
     }
 }
 
+/// ASN bootstrap, registry keys like `64496-64511` or `15169` are parsed as inclusive ranges.
+/// Whole 32-bit ASN space is supported.
 #[derive(Default)]
-pub struct Asn(Vec<(RangeInclusive<u32>, Arc<Vec<String>>)>);
+pub struct Asn {
+    /// Ranges sorted by start.
+    ranges: Vec<(RangeInclusive<u32>, Arc<Vec<String>>)>,
+    /// The highest end of ranges up to given index, so `find` knows when no earlier range can
+    /// contain ASN.
+    max_ends: Vec<u32>,
+}
 
 impl Asn {
     pub fn new() -> Self {
@@ -255,45 +734,81 @@ impl Asn {
     }
 
     pub fn with_capacity(capacity: usize) -> Self {
-        Asn(Vec::with_capacity(capacity))
+        Asn {
+            ranges: Vec::with_capacity(capacity),
+            max_ends: Vec::with_capacity(capacity),
+        }
     }
 
+    /// Returns servers for range that contains given ASN. Registry should not contain
+    /// overlapping ranges, when it does, the range with the highest start of ranges that contain
+    /// ASN is used, so nested range (like from `Overrides`) takes precedence over wider one.
     pub fn find(&self, asn: u32) -> Option<&Vec<String>> {
-        let pos = self
-            .0
-            .binary_search_by_key(&asn, |(range, _)| *range.start())
-            .unwrap_or_else(|e| e.saturating_sub(1));
-        let (range, servers) = &self.0[pos];
-        if range.contains(&asn) {
-            Some(servers.as_ref())
-        } else {
-            None
-        }
+        let end = self
+            .ranges
+            .partition_point(|(range, _)| *range.start() <= asn);
+        (0..end)
+            .rev()
+            .take_while(|i| self.max_ends[*i] >= asn)
+            .map(|i| &self.ranges[i])
+            .find(|(range, _)| range.contains(&asn))
+            .map(|(_, servers)| servers.as_ref())
     }
 
+    /// Insert ranges and sort table, so `find` can use binary search. Use `insert_one` and `sort`
+    /// when inserting many ranges.
     pub fn insert(&mut self, servers: Vec<String>, ranges: Vec<RangeInclusive<u32>>) {
         let servers = Arc::new(servers);
         for range in ranges {
             self.insert_one(range, &servers);
         }
+        self.sort();
     }
 
+    /// Insert range without sorting table, `sort` must be called after last insert.
     pub fn insert_one(&mut self, range: RangeInclusive<u32>, servers: &Arc<Vec<String>>) {
-        self.0.push((range, Arc::clone(servers)));
+        self.ranges.push((range, Arc::clone(servers)));
     }
 
+    /// Sort ranges and build index used by `find`.
     pub fn sort(&mut self) {
-        self.0.sort_by_key(|(a, _)| *a.start());
+        self.ranges.sort_by_key(|(a, _)| *a.start());
+        self.max_ends = self
+            .ranges
+            .iter()
+            .scan(0, |max_end, (range, _)| {
+                *max_end = (*max_end).max(*range.end());
+                Some(*max_end)
+            })
+            .collect();
+    }
+
+    /// Returns pairs of ranges that overlap, for validating registry. Ranges must be sorted.
+    pub fn overlapping(&self) -> Vec<(&RangeInclusive<u32>, &RangeInclusive<u32>)> {
+        let mut overlapping = Vec::new();
+        let mut widest: Option<&RangeInclusive<u32>> = None;
+        for (range, _) in &self.ranges {
+            if let Some(previous) = widest {
+                if range.start() <= previous.end() {
+                    overlapping.push((previous, range));
+                }
+                if range.end() <= previous.end() {
+                    continue;
+                }
+            }
+            widest = Some(range);
+        }
+        overlapping
     }
 
     pub fn iter(&self) -> impl Iterator<Item = (&RangeInclusive<u32>, &Vec<String>)> {
-        self.0.iter().map(|(i, v)| (i, v.as_ref()))
+        self.ranges.iter().map(|(i, v)| (i, v.as_ref()))
     }
 }
 
 impl Serialize for Asn {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        let i = self.0.iter().map(|(range, arc)| {
+        let i = self.ranges.iter().map(|(range, arc)| {
             let range = (*range.start(), *range.end());
             (range, arc)
         });
@@ -324,12 +839,10 @@ impl<'de> Deserialize<'de> for Asn {
                 let mut asn = Asn::with_capacity(seq.size_hint().unwrap_or(0));
 
                 while let Some((ranges, servers)) = seq.next_element::<InnerType>()? {
-                    let ranges = ranges
-                        .iter()
-                        .map(|(start, stop)| RangeInclusive::new(*start, *stop))
-                        .collect();
-
-                    asn.insert(servers, ranges);
+                    let servers = Arc::new(servers);
+                    for (start, stop) in ranges {
+                        asn.insert_one(RangeInclusive::new(start, stop), &servers);
+                    }
                 }
 
                 asn.sort();
@@ -350,17 +863,7 @@ impl TryFrom<&parser::BootstrapRfc7484> for Asn {
         for service in &bootstrap.services {
             let servers = Arc::new(service.servers().to_vec());
             for key in service.keys() {
-                let range = if key.contains('-') {
-                    let parts: Vec<_> = key.splitn(2, '-').collect();
-                    RangeInclusive::new(
-                        parts.get(0).unwrap_or(&"").parse::<u32>()?,
-                        parts.get(1).unwrap_or(&"").parse::<u32>()?,
-                    )
-                } else {
-                    let asn = key.parse::<u32>()?;
-                    RangeInclusive::new(asn, asn)
-                };
-                asn.insert_one(range, &servers);
+                asn.insert_one(parse_asn_range(key)?, &servers);
             }
         }
 
@@ -370,6 +873,275 @@ impl TryFrom<&parser::BootstrapRfc7484> for Asn {
     }
 }
 
+/// Parse ASN registry key like `64496-64511` or `15169`.
+fn parse_asn_range(key: &str) -> Result<RangeInclusive<u32>, std::num::ParseIntError> {
+    match key.split_once('-') {
+        Some((start, end)) => Ok(start.parse()?..=end.parse()?),
+        None => {
+            let asn = key.parse()?;
+            Ok(asn..=asn)
+        }
+    }
+}
+
+/// IANA bootstrap registry file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Registry {
+    Asn,
+    Dns,
+    Ipv4,
+    Ipv6,
+    ObjectTags,
+}
+
+impl Registry {
+    /// File name of registry, like `dns.json`.
+    pub fn file(&self) -> &'static str {
+        match self {
+            Self::Asn => "asn.json",
+            Self::Dns => "dns.json",
+            Self::Ipv4 => "ipv4.json",
+            Self::Ipv6 => "ipv6.json",
+            Self::ObjectTags => "object-tags.json",
+        }
+    }
+}
+
+/// Problem in bootstrap registry found by `validate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Key is listed more times, so only one of its services is used.
+    DuplicateKey(String),
+    /// Key overlaps with key from the same or earlier service, like `10.0.0.0/8` and
+    /// `10.1.0.0/16` or `100-200` and `150`.
+    OverlappingKeys(String, String),
+    /// Key cannot be parsed as IP network or ASN range.
+    InvalidKey(String),
+    /// Server is not valid `http` or `https` URL.
+    InvalidUrl(String),
+    /// Service doesn't contain any server.
+    NoServers,
+}
+
+/// Problem found in given service of bootstrap registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub registry: Registry,
+    /// Index of service in registry `services`.
+    pub service: usize,
+    pub problem: Problem,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} service {}: ", self.registry.file(), self.service)?;
+        match &self.problem {
+            Problem::DuplicateKey(key) => write!(f, "duplicate key {}", key),
+            Problem::OverlappingKeys(a, b) => write!(f, "key {} overlaps with {}", b, a),
+            Problem::InvalidKey(key) => write!(f, "invalid key {}", key),
+            Problem::InvalidUrl(url) => write!(f, "invalid server URL {}", url),
+            Problem::NoServers => f.write_str("no servers"),
+        }
+    }
+}
+
+/// Returns first and last address of network as numbers, so IPv4 and IPv6 networks can be
+/// compared like ASN ranges.
+fn network_range(network: IpNetwork) -> (u128, u128) {
+    let (start, host_mask) = match network {
+        IpNetwork::V4(network) => (
+            u32::from(network.network_address()) as u128,
+            u32::MAX.checked_shr(network.netmask() as u32).unwrap_or(0) as u128,
+        ),
+        IpNetwork::V6(network) => (
+            u128::from(network.network_address()),
+            u128::MAX.checked_shr(network.netmask() as u32).unwrap_or(0),
+        ),
+    };
+    (start, start | host_mask)
+}
+
+/// Check parsed bootstrap registry for problems that would cause wrong routing of queries, like
+/// duplicate or overlapping keys, invalid server URLs or services without servers. Returns empty
+/// vector when registry is valid.
+pub fn validate<T: BootstrapService>(
+    registry: Registry,
+    bootstrap: &parser::Bootstrap<T>,
+) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut keys = HashSet::new();
+    let mut ranges = Vec::new();
+    for (service, entry) in bootstrap.services.iter().enumerate() {
+        let mut report = |problem| {
+            diagnostics.push(Diagnostic {
+                registry,
+                service,
+                problem,
+            })
+        };
+        if entry.servers().is_empty() {
+            report(Problem::NoServers);
+        }
+        for server in entry.servers() {
+            let valid = reqwest::Url::parse(server).map_or(false, |url| {
+                matches!(url.scheme(), "http" | "https") && url.has_host()
+            });
+            if !valid {
+                report(Problem::InvalidUrl(server.clone()));
+            }
+        }
+        for key in entry.keys() {
+            if !keys.insert(key.to_lowercase()) {
+                report(Problem::DuplicateKey(key.clone()));
+                continue;
+            }
+            let range = match registry {
+                Registry::Asn => parse_asn_range(key)
+                    .ok()
+                    .map(|range| (*range.start() as u128, *range.end() as u128)),
+                Registry::Ipv4 | Registry::Ipv6 => IpNetwork::from_str(key).ok().map(network_range),
+                Registry::Dns | Registry::ObjectTags => continue,
+            };
+            match range {
+                Some(range) if range.0 <= range.1 => ranges.push((range, key, service)),
+                _ => report(Problem::InvalidKey(key.clone())),
+            }
+        }
+    }
+
+    ranges.sort_by_key(|((start, end), _, service)| (*start, std::cmp::Reverse(*end), *service));
+    let mut widest: Option<(u128, &String)> = None;
+    for ((start, end), key, service) in ranges {
+        if let Some((widest_end, widest_key)) = widest {
+            if start <= widest_end {
+                diagnostics.push(Diagnostic {
+                    registry,
+                    service,
+                    problem: Problem::OverlappingKeys(widest_key.clone(), key.clone()),
+                });
+            }
+            if end <= widest_end {
+                continue;
+            }
+        }
+        widest = Some((end, key));
+    }
+    diagnostics
+}
+
+/// Servers of one key that are different in two bootstraps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change<K> {
+    pub key: K,
+    pub old: Vec<String>,
+    pub new: Vec<String>,
+}
+
+/// Differences between two versions of one bootstrap registry. Keys are sorted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changes<K> {
+    /// Keys only in newer bootstrap, with their servers.
+    pub added: Vec<(K, Vec<String>)>,
+    /// Keys only in older bootstrap, with their servers.
+    pub removed: Vec<(K, Vec<String>)>,
+    /// Keys with different servers.
+    pub changed: Vec<Change<K>>,
+}
+
+impl<K> Changes<K> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    fn compute<'a, I, J>(old: I, new: J) -> Self
+    where
+        K: Ord,
+        I: Iterator<Item = (K, &'a Vec<String>)>,
+        J: Iterator<Item = (K, &'a Vec<String>)>,
+    {
+        let mut old: BTreeMap<_, _> = old.collect();
+        let mut changes = Self {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        let new: BTreeMap<_, _> = new.collect();
+        for (key, new) in new {
+            match old.remove(&key) {
+                None => changes.added.push((key, new.clone())),
+                Some(old) if old != new => changes.changed.push(Change {
+                    key,
+                    old: old.clone(),
+                    new: new.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        changes.removed = old
+            .into_iter()
+            .map(|(key, servers)| (key, servers.clone()))
+            .collect();
+        changes
+    }
+
+    fn map<L, F: Fn(K) -> L>(self, f: F) -> Changes<L> {
+        Changes {
+            added: self.added.into_iter().map(|(k, s)| (f(k), s)).collect(),
+            removed: self.removed.into_iter().map(|(k, s)| (f(k), s)).collect(),
+            changed: self
+                .changed
+                .into_iter()
+                .map(|change| Change {
+                    key: f(change.key),
+                    old: change.old,
+                    new: change.new,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Differences between two bootstraps, returned by `Bootstrap::diff`. For example, added or
+/// removed TLDs, changed server URLs, or IP networks and ASN ranges allocated to other registry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootstrapDiff {
+    pub dns: Changes<String>,
+    pub ip: Changes<IpNetwork>,
+    pub asn: Changes<RangeInclusive<u32>>,
+    pub object_tags: Changes<String>,
+}
+
+impl BootstrapDiff {
+    /// Returns true if bootstraps have the same content.
+    pub fn is_empty(&self) -> bool {
+        self.dns.is_empty()
+            && self.ip.is_empty()
+            && self.asn.is_empty()
+            && self.object_tags.is_empty()
+    }
+}
+
+impl Bootstrap {
+    /// Compare this bootstrap with newer one.
+    pub fn diff(&self, newer: &Bootstrap) -> BootstrapDiff {
+        let asn_key =
+            |(range, servers): (&RangeInclusive<u32>, _)| ((*range.start(), *range.end()), servers);
+        BootstrapDiff {
+            dns: Changes::compute(
+                self.dns.iter().map(|(k, s)| (k.clone(), s)),
+                newer.dns.iter().map(|(k, s)| (k.clone(), s)),
+            ),
+            ip: Changes::compute(self.ip.iter(), newer.ip.iter()),
+            asn: Changes::compute(self.asn.iter().map(asn_key), newer.asn.iter().map(asn_key))
+                .map(|(start, end)| start..=end),
+            object_tags: Changes::compute(
+                self.object_tags.iter().map(|(k, s)| (k.clone(), s)),
+                newer.object_tags.iter().map(|(k, s)| (k.clone(), s)),
+            ),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -387,7 +1159,7 @@ mod tests {
     fn test_asn() {
         let parsed = parse("asn.json");
         let asn = Asn::try_from(&parsed).unwrap();
-        assert!(asn.find(std::u32::MAX).is_none());
+        assert!(asn.find(u32::MAX).is_none());
         assert!(asn.find(0).is_none());
         assert_eq!("https://rdap.apnic.net/", asn.find(4608).unwrap()[0]);
         assert_eq!("https://rdap.db.ripe.net/", asn.find(2043).unwrap()[0]);
@@ -396,12 +1168,58 @@ mod tests {
             asn.find(267676).unwrap()[0]
         );
 
+        assert!(asn.overlapping().is_empty());
+
         let ser_json = serde_json::to_string(&asn).unwrap();
         let asn_de: Asn = serde_json::from_str(&ser_json).unwrap();
         assert_eq!("https://rdap.apnic.net/", asn_de.find(4608).unwrap()[0]);
         assert_eq!("https://rdap.db.ripe.net/", asn_de.find(2043).unwrap()[0]);
     }
 
+    #[test]
+    fn test_asn_ranges() {
+        let mut parsed: parser::BootstrapRfc7484 = parse("asn.json");
+        parsed.services = serde_json::from_str(
+            r#"[
+                [["64496-64511", "65536"], ["https://rdap.a.example/"]],
+                [["64500-64505", "4200000000-4294967295"], ["https://rdap.b.example/"]]
+            ]"#,
+        )
+        .unwrap();
+        let asn = Asn::try_from(&parsed).unwrap();
+        assert_eq!("https://rdap.a.example/", asn.find(64496).unwrap()[0]);
+        assert_eq!("https://rdap.a.example/", asn.find(65536).unwrap()[0]);
+        assert_eq!("https://rdap.b.example/", asn.find(64501).unwrap()[0]);
+        assert_eq!("https://rdap.b.example/", asn.find(u32::MAX).unwrap()[0]);
+        assert!(asn.find(65537).is_none());
+        assert_eq!(
+            vec![(&(64496..=64511), &(64500..=64505))],
+            asn.overlapping()
+        );
+
+        parsed.services =
+            serde_json::from_str(r#"[[["4294967296"], ["https://rdap.example/"]]]"#).unwrap();
+        assert!(Asn::try_from(&parsed).is_err());
+
+        // Table stays searchable when ranges are inserted out of order.
+        let mut asn = Asn::new();
+        asn.insert(vec!["https://rdap.b.example/".into()], vec![200..=299]);
+        asn.insert(vec!["https://rdap.a.example/".into()], vec![100..=199]);
+        assert_eq!("https://rdap.a.example/", asn.find(150).unwrap()[0]);
+        assert_eq!("https://rdap.b.example/", asn.find(250).unwrap()[0]);
+
+        // Nested range is used for ASNs it contains, wider range for the rest.
+        let mut asn = Asn::new();
+        asn.insert(vec!["https://rdap.wide.example/".into()], vec![100..=300]);
+        asn.insert(vec!["https://rdap.nested.example/".into()], vec![150..=160]);
+        asn.insert(vec!["https://rdap.other.example/".into()], vec![400..=500]);
+        assert_eq!("https://rdap.nested.example/", asn.find(155).unwrap()[0]);
+        assert_eq!("https://rdap.wide.example/", asn.find(200).unwrap()[0]);
+        assert_eq!("https://rdap.wide.example/", asn.find(100).unwrap()[0]);
+        assert!(asn.find(350).is_none());
+        assert!(asn.find(99).is_none());
+    }
+
     #[test]
     fn test_dns() {
         let parsed = parse("dns.json");
@@ -412,12 +1230,45 @@ mod tests {
             dns.find("a.b.com").unwrap()[0]
         );
         assert!(dns.find("com.a.b.xxxxxxxxxxxxxx").is_none());
+        assert_eq!("https://rdap.nic.cz/", dns.find("A.NIC.CZ.").unwrap()[0]);
+        assert!(dns.find("").is_none());
+        assert!(dns.find("cz.example").is_none());
 
         let ser_json = serde_json::to_string(&dns).unwrap();
         let dns_de: Dns = serde_json::from_str(&ser_json).unwrap();
         assert_eq!("https://rdap.nic.cz/", dns_de.find("a.nic.cz").unwrap()[0]);
     }
 
+    #[test]
+    fn test_dns_idn() {
+        let mut parsed: parser::BootstrapRfc7484 = parse("dns.json");
+        parsed.services = serde_json::from_str(
+            r#"[
+                [["de"], ["https://rdap.denic.example/"]],
+                [["xn--p1ai"], ["https://rdap.rf.example/"]],
+                [["xn--mnchen-3ya.de"], ["https://rdap.muenchen.example/"]]
+            ]"#,
+        )
+        .unwrap();
+        let dns = Dns::from(&parsed);
+        assert_eq!(
+            "https://rdap.muenchen.example/",
+            dns.find("www.MÜNCHEN.de").unwrap()[0]
+        );
+        assert_eq!(
+            "https://rdap.denic.example/",
+            dns.find("köln.de").unwrap()[0]
+        );
+        assert_eq!(
+            "https://rdap.rf.example/",
+            dns.find("example.xn--p1ai").unwrap()[0]
+        );
+        assert_eq!(
+            "https://rdap.rf.example/",
+            dns.find("пример.рф").unwrap()[0]
+        );
+    }
+
     #[test]
     fn test_ip() {
         let ipv4_bootstrap = parse("ipv4.json");
@@ -436,6 +1287,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ip_longest_prefix() {
+        let mut ipv4: parser::BootstrapRfc7484 = parse("ipv4.json");
+        ipv4.services = serde_json::from_str(
+            r#"[
+                [["10.0.0.0/8"], ["https://rdap.wide.example/"]],
+                [["10.1.0.0/16"], ["https://rdap.narrow.example/"]]
+            ]"#,
+        )
+        .unwrap();
+        let ipv6 = parse("ipv6.json");
+        let ip = Ip::try_from((&ipv4, &ipv6)).unwrap();
+
+        let (network, servers) = ip.find_match(Ipv4Addr::new(10, 1, 2, 3)).unwrap();
+        assert_eq!("10.1.0.0/16", network.to_string());
+        assert_eq!("https://rdap.narrow.example/", servers[0]);
+        assert_eq!(
+            "https://rdap.wide.example/",
+            ip.find(Ipv4Addr::new(10, 2, 0, 1)).unwrap()[0]
+        );
+        assert!(ip.find(Ipv4Addr::new(11, 0, 0, 1)).is_none());
+
+        ipv4.services =
+            serde_json::from_str(r#"[[["10.0.0.0/33"], ["https://rdap.example/"]]]"#).unwrap();
+        assert!(Ip::try_from((&ipv4, &ipv6)).is_err());
+    }
+
+    #[test]
+    fn test_ip_index() {
+        let mut ip = Ip::new();
+        for network in [
+            "0.0.0.0/0",
+            "10.0.0.0/8",
+            "10.1.0.0/16",
+            "10.1.2.0/24",
+            "11.0.0.0/8",
+            "::/0",
+            "2001:db8::/32",
+        ] {
+            ip.insert(vec![network.into()], vec![network.parse().unwrap()]);
+        }
+        let find = |address: &str| {
+            let address: IpAddr = address.parse().unwrap();
+            let (network, servers) = ip.find_match(address).unwrap();
+            assert_eq!(network.to_string(), servers[0]);
+            servers[0].clone()
+        };
+        assert_eq!("10.1.2.0/24", find("10.1.2.255"));
+        assert_eq!("10.1.0.0/16", find("10.1.3.0"));
+        assert_eq!("10.0.0.0/8", find("10.2.0.0"));
+        assert_eq!("11.0.0.0/8", find("11.255.255.255"));
+        assert_eq!("0.0.0.0/0", find("9.255.255.255"));
+        assert_eq!("0.0.0.0/0", find("255.255.255.255"));
+        assert_eq!("2001:db8::/32", find("2001:db8::1"));
+        assert_eq!("::/0", find("2001:db9::"));
+        assert_eq!("::/0", find("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"));
+
+        // Index returns the same prefixes as lookup in table around all registry prefixes.
+        let ip = Ip::try_from((&parse("ipv4.json"), &parse("ipv6.json"))).unwrap();
+        for (network, _) in ip.table.iter() {
+            let (start, end) = network_range(network);
+            for address in [start.wrapping_sub(1), start, end, end.wrapping_add(1)] {
+                let address = match network {
+                    IpNetwork::V4(_) => IpAddr::from(Ipv4Addr::from(address as u32)),
+                    IpNetwork::V6(_) => IpAddr::from(Ipv6Addr::from(address)),
+                };
+                let expected = ip
+                    .table
+                    .longest_match(address)
+                    .map(|(network, servers)| (network, servers.as_ref()));
+                assert_eq!(expected, ip.find_match(address), "{}", address);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reverse_network() {
+        let network = |domain| reverse_network(domain).map(|network| network.to_string());
+        assert_eq!(Some("193.0.6.0/24".into()), network("6.0.193.in-addr.arpa"));
+        assert_eq!(Some("10.0.0.0/8".into()), network("10.IN-ADDR.ARPA."));
+        assert_eq!(
+            Some("2001:db8::/32".into()),
+            network("8.b.d.0.1.0.0.2.ip6.arpa")
+        );
+        assert_eq!(None, network("0/25.2.0.192.in-addr.arpa"));
+        assert_eq!(None, network("256.in-addr.arpa"));
+        assert_eq!(None, network("1.2.3.4.5.in-addr.arpa"));
+        assert_eq!(None, network("10.ip6.arpa"));
+        assert_eq!(None, network("example.com"));
+
+        let mut ip = Ip::new();
+        ip.insert(
+            vec!["https://rdap.wide.example/".into()],
+            vec!["10.0.0.0/8".parse().unwrap()],
+        );
+        ip.insert(
+            vec!["https://rdap.narrow.example/".into()],
+            vec!["10.1.1.0/24".parse().unwrap()],
+        );
+        let find = |network: &str| ip.find_network(network.parse().unwrap()).unwrap()[0].clone();
+        assert_eq!("https://rdap.wide.example/", find("10.1.0.0/16"));
+        assert_eq!("https://rdap.narrow.example/", find("10.1.1.0/24"));
+    }
+
     #[test]
     fn test_object_tags() {
         let parsed = parse("object-tags.json");
@@ -447,6 +1402,20 @@ mod tests {
         assert!(object_tags.find("TEST").is_none());
         assert!(object_tags.find("").is_none());
         assert!(object_tags.find("TEST-TEST").is_none());
+        assert!(object_tags.find("RIPE").is_none());
+        assert!(object_tags.find("TEST-").is_none());
+        assert_eq!(
+            "https://rdap.arin.net/registry/",
+            object_tags.find("ABC123-arin").unwrap()[0]
+        );
+        assert_eq!(
+            "https://rdap.lacnic.net/rdap/",
+            object_tags.find("A-B-LACNIC").unwrap()[0]
+        );
+        assert_eq!(Some("ARIN"), ObjectTags::tag("ABC123-ARIN"));
+        assert_eq!(None, ObjectTags::tag("ABC123"));
+        let handle = parser::Handle::from("ABC123-arin");
+        assert_eq!(object_tags.find(&handle), object_tags.find("ABC123-ARIN"));
 
         let ser_json = serde_json::to_string(&object_tags).unwrap();
         let object_tags_de: ObjectTags = serde_json::from_str(&ser_json).unwrap();
@@ -509,6 +1478,7 @@ mod tests {
             dns,
             ip,
             object_tags,
+            publication: None,
         };
 
         validate_bootstrap(&bootstrap);
@@ -519,6 +1489,231 @@ mod tests {
         validate_bootstrap(&bootstrap_de);
     }
 
+    #[test]
+    fn test_from_registries() {
+        let bootstrap = Bootstrap::from_registries(
+            &parse("asn.json"),
+            &parse("dns.json"),
+            &parse("ipv4.json"),
+            &parse("ipv6.json"),
+            &parse("object-tags.json"),
+        )
+        .unwrap();
+        validate_bootstrap(&bootstrap);
+        let dns: parser::BootstrapRfc7484 = parse("dns.json");
+        assert!(bootstrap.publication.unwrap() >= dns.publication);
+
+        let mut invalid: parser::BootstrapRfc7484 = parse("asn.json");
+        invalid.services =
+            serde_json::from_str(r#"[[["not-asn"], ["https://rdap.example/"]]]"#).unwrap();
+        assert!(Bootstrap::from_registries(
+            &invalid,
+            &parse("dns.json"),
+            &parse("ipv4.json"),
+            &parse("ipv6.json"),
+            &parse("object-tags.json"),
+        )
+        .is_err());
+    }
+
+    #[cfg(feature = "embedded-bootstrap")]
+    #[test]
+    fn test_embedded() {
+        let bootstrap = Bootstrap::embedded();
+        assert!(bootstrap.publication.is_some());
+        assert!(bootstrap.asn.overlapping().is_empty());
+        assert!(bootstrap.dns.find("example.com").is_some());
+    }
+
+    #[cfg(feature = "embedded-bootstrap")]
+    #[test]
+    fn test_embedded_is_not_test_data() {
+        // Snapshot has to be refreshed by `bootstrap/update.sh`, not copied from test data.
+        for file in [
+            "asn.json",
+            "dns.json",
+            "ipv4.json",
+            "ipv6.json",
+            "object-tags.json",
+        ] {
+            let embedded = std::fs::read(format!("bootstrap/{}", file)).unwrap();
+            let test_data = std::fs::read(format!("test_data/bootstrap/{}", file)).unwrap();
+            assert_ne!(embedded, test_data, "{} is copy of test data", file);
+        }
+    }
+
+    #[test]
+    fn test_overrides() {
+        let overrides = Overrides::new()
+            .domain("Example", "https://rdap.nic.example")
+            .domain("рф", "https://rdap.rf.example/")
+            .ip("10.0.0.0/8".parse().unwrap(), "https://rdap.corp.example/")
+            .asn(64512..=65534, "https://rdap.corp.example/")
+            .object_tag("corp", "https://rdap.corp.example/");
+        let bootstrap = &overrides.0;
+        assert_eq!(
+            "https://rdap.nic.example/",
+            bootstrap.dns.find("a.example").unwrap()[0]
+        );
+        assert!(bootstrap.dns.find("пример.рф").is_some());
+        assert!(bootstrap.ip.find(Ipv4Addr::new(10, 1, 1, 1)).is_some());
+        assert!(bootstrap.ip.find(Ipv4Addr::new(8, 8, 8, 8)).is_none());
+        assert!(bootstrap.asn.find(64512).is_some());
+        assert!(bootstrap.object_tags.find("ABC-CORP").is_some());
+    }
+
+    #[test]
+    fn test_delegated_stats() {
+        let stats = "\
+2.3|nro|20240101|5|19830705|20240101|+0000
+nro|*|asn|*|2|summary
+nro|*|ipv4|*|2|summary
+arin|US|asn|1|10|19840101|assigned|abc
+ripencc|NL|ipv4|193.0.0.0|2048|19930901|allocated|def
+ietf|ZZ|ipv4|192.0.2.0|256|19930901|reserved|
+apnic|JP|ipv6|2001:200::|35|19990813|allocated|ghi
+lacnic||ipv4|200.0.0.0|768|00000000|available|
+afrinic|ZA|ipv4|196.0.0.0|768|19930901|allocated|jkl
+";
+        let bootstrap = Bootstrap::from_delegated_stats(stats).unwrap();
+        let ip = |address: &str| {
+            let address: IpAddr = address.parse().unwrap();
+            bootstrap
+                .ip
+                .find(address)
+                .map(|servers| servers[0].as_str())
+        };
+        assert_eq!(Some("https://rdap.db.ripe.net/"), ip("193.0.7.255"));
+        assert_eq!(None, ip("193.0.8.0"));
+        assert_eq!(Some("https://rdap.apnic.net/"), ip("2001:200::1"));
+        assert_eq!(Some("https://rdap.afrinic.net/rdap/"), ip("196.0.2.255"));
+        assert_eq!(None, ip("196.0.3.0"));
+        assert_eq!(None, ip("192.0.2.1"));
+        assert_eq!(None, ip("200.0.0.1"));
+        assert_eq!(
+            "https://rdap.arin.net/registry/",
+            bootstrap.asn.find(10).unwrap()[0]
+        );
+        assert!(bootstrap.asn.find(11).is_none());
+
+        let networks: Vec<_> = ipv4_networks(u32::from(Ipv4Addr::new(196, 0, 0, 0)), 768)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(vec!["196.0.0.0/23", "196.0.2.0/24"], networks);
+        assert_eq!("0.0.0.0/0", ipv4_networks(0, 1 << 32)[0].to_string());
+        let networks = ipv4_networks(u32::from(Ipv4Addr::new(255, 255, 255, 0)), u64::MAX);
+        assert_eq!("255.255.255.0/24", networks[0].to_string());
+        assert_eq!(1, networks.len());
+        assert!(Bootstrap::from_delegated_stats(&format!(
+            "ripencc|NL|ipv4|193.0.0.0|{}|19930901|allocated",
+            u64::MAX
+        ))
+        .is_ok());
+
+        assert!(Bootstrap::from_delegated_stats("arin|US|asn|x|1|19840101|assigned").is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        for registry in [Registry::Asn, Registry::Dns, Registry::Ipv4, Registry::Ipv6] {
+            let parsed: parser::BootstrapRfc7484 = parse(registry.file());
+            assert_eq!(Vec::<Diagnostic>::new(), validate(registry, &parsed));
+        }
+        let parsed: parser::BootstrapRfc8521 = parse("object-tags.json");
+        assert!(validate(Registry::ObjectTags, &parsed).is_empty());
+
+        let mut parsed: parser::BootstrapRfc7484 = parse("ipv4.json");
+        parsed.services = serde_json::from_str(
+            r#"[
+                [["10.0.0.0/8", "192.0.2.0/33"], ["https://rdap.a.example/"]],
+                [["10.1.0.0/16", "10.0.0.0/8"], ["ftp://rdap.b.example/", "rdap.b.example"]],
+                [["198.51.100.0/24"], []]
+            ]"#,
+        )
+        .unwrap();
+        let problems: Vec<_> = validate(Registry::Ipv4, &parsed)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.service, diagnostic.problem))
+            .collect();
+        assert_eq!(
+            vec![
+                (0, Problem::InvalidKey("192.0.2.0/33".into())),
+                (1, Problem::InvalidUrl("ftp://rdap.b.example/".into())),
+                (1, Problem::InvalidUrl("rdap.b.example".into())),
+                (1, Problem::DuplicateKey("10.0.0.0/8".into())),
+                (2, Problem::NoServers),
+                (
+                    1,
+                    Problem::OverlappingKeys("10.0.0.0/8".into(), "10.1.0.0/16".into())
+                ),
+            ],
+            problems
+        );
+
+        let mut parsed: parser::BootstrapRfc7484 = parse("asn.json");
+        parsed.services =
+            serde_json::from_str(r#"[[["100-200", "150", "300-250"], ["https://rdap.example/"]]]"#)
+                .unwrap();
+        let diagnostics = validate(Registry::Asn, &parsed);
+        assert_eq!(
+            vec![
+                Problem::InvalidKey("300-250".into()),
+                Problem::OverlappingKeys("100-200".into(), "150".into())
+            ],
+            diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic.problem.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "asn.json service 0: key 150 overlaps with 100-200",
+            diagnostics[1].to_string()
+        );
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = Bootstrap::from_registries(
+            &parse("asn.json"),
+            &parse("dns.json"),
+            &parse("ipv4.json"),
+            &parse("ipv6.json"),
+            &parse("object-tags.json"),
+        )
+        .unwrap();
+        assert!(old.diff(&old).is_empty());
+
+        let mut new = Bootstrap::from_registries(
+            &parse("asn.json"),
+            &parse("dns.json"),
+            &parse("ipv4.json"),
+            &parse("ipv6.json"),
+            &parse("object-tags.json"),
+        )
+        .unwrap();
+        let server = vec!["https://rdap.new.example/".to_string()];
+        new.dns
+            .insert(server.clone(), vec!["zz".into(), "cz".into()]);
+        new.ip
+            .insert(server.clone(), vec!["8.0.0.0/9".parse().unwrap()]);
+        new.asn.insert(server.clone(), vec![4608..=4608]);
+
+        let diff = old.diff(&new);
+        assert_eq!(vec![("zz".to_string(), server.clone())], diff.dns.added);
+        assert!(diff.dns.removed.is_empty());
+        assert_eq!(1, diff.dns.changed.len());
+        assert_eq!("cz", diff.dns.changed[0].key);
+        assert_eq!("https://rdap.nic.cz/", diff.dns.changed[0].old[0]);
+        assert_eq!(server, diff.dns.changed[0].new);
+        assert_eq!("8.0.0.0/9", diff.ip.added[0].0.to_string());
+        assert!(diff.object_tags.is_empty());
+
+        let diff = new.diff(&old);
+        assert_eq!("zz", diff.dns.removed[0].0);
+        assert_eq!(4608..=4608, diff.asn.removed[0].0);
+    }
+
     #[test]
     fn test_send_sync() {
         fn is_send_sync<T: Send + Sync>() {}
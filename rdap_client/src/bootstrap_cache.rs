@@ -0,0 +1,133 @@
+//! Bootstrap registries stored on disk, so short-lived processes don't download them every time.
+
+use crate::parser;
+use crate::rt::{SystemTime, UNIX_EPOCH};
+use reqwest::{header, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// HTTP validators of cached registry and time when it was downloaded or revalidated.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Meta {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Unix timestamp in seconds.
+    fetched: u64,
+}
+
+impl Meta {
+    fn from_headers(headers: &header::HeaderMap) -> Self {
+        let get = |name| {
+            headers
+                .get(name)
+                .and_then(|value: &header::HeaderValue| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        Self {
+            etag: get(header::ETAG),
+            last_modified: get(header::LAST_MODIFIED),
+            fetched: now(),
+        }
+    }
+
+    fn is_fresh(&self, ttl: Option<Duration>) -> bool {
+        ttl.map_or(false, |ttl| {
+            now().saturating_sub(self.fetched) < ttl.as_secs()
+        })
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+/// Directory with registry files like `dns.json` and their metadata like `dns.json.meta`.
+#[derive(Debug, Clone)]
+pub(crate) struct BootstrapCache {
+    pub(crate) dir: PathBuf,
+}
+
+impl BootstrapCache {
+    /// Returns registry from cache when it is younger than `ttl`, otherwise revalidates it by
+    /// conditional request or downloads it from `url`. Cache that cannot be read or parsed is
+    /// ignored and overwritten.
+    pub(crate) async fn get<T: DeserializeOwned>(
+        &self,
+        client: &reqwest::Client,
+        url: &str,
+        file: &str,
+        ttl: Option<Duration>,
+    ) -> Result<parser::Bootstrap<T>, Box<dyn std::error::Error + Send + Sync>> {
+        let cached = match self.read::<T>(file) {
+            Some((bootstrap, meta)) if meta.is_fresh(ttl) => {
+                debug!(file, "using cached bootstrap registry");
+                return Ok(bootstrap);
+            }
+            cached => cached,
+        };
+
+        let mut request = client.get(url);
+        if let Some((_, meta)) = &cached {
+            if let Some(etag) = &meta.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &meta.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let response = request.send().await?;
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            if let Some((bootstrap, mut meta)) = cached {
+                debug!(file, "cached bootstrap registry not modified");
+                meta.fetched = now();
+                self.store(file, None, &meta);
+                return Ok(bootstrap);
+            }
+        }
+
+        let response = response.error_for_status()?;
+        let meta = Meta::from_headers(response.headers());
+        let body = response.bytes().await?;
+        let bootstrap = serde_json::from_slice(&body)?;
+        self.store(file, Some(&body), &meta);
+        Ok(bootstrap)
+    }
+
+    fn read<T: DeserializeOwned>(&self, file: &str) -> Option<(parser::Bootstrap<T>, Meta)> {
+        let meta = std::fs::read(self.meta_path(file)).ok()?;
+        let body = std::fs::read(self.dir.join(file)).ok()?;
+        match (serde_json::from_slice(&meta), serde_json::from_slice(&body)) {
+            (Ok(meta), Ok(bootstrap)) => Some((bootstrap, meta)),
+            _ => {
+                warn!(file, "bootstrap cache is corrupted, ignoring it");
+                None
+            }
+        }
+    }
+
+    /// Write registry body (when changed) and its metadata. Failure is just logged, because
+    /// registry can be still used.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    fn store(&self, file: &str, body: Option<&[u8]>, meta: &Meta) {
+        // Serializing of plain structure cannot fail.
+        let meta = serde_json::to_vec(meta).unwrap();
+        let result = std::fs::create_dir_all(&self.dir)
+            .and_then(|_| match body {
+                Some(body) => std::fs::write(self.dir.join(file), body),
+                None => Ok(()),
+            })
+            .and_then(|_| std::fs::write(self.meta_path(file), meta));
+        if let Err(e) = result {
+            warn!(file, error = %e, "writing bootstrap cache failed");
+        }
+    }
+
+    fn meta_path(&self, file: &str) -> PathBuf {
+        self.dir.join(format!("{}.meta", file))
+    }
+}
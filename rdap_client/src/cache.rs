@@ -0,0 +1,163 @@
+//! Cache of response bodies for conditional requests, with the oldest entries evicted first.
+
+use bytes::Bytes;
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+/// Cached response body together with validators used for revalidation.
+#[derive(Clone)]
+pub(crate) struct CachedResponse {
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    pub(crate) body: Bytes,
+}
+
+impl CachedResponse {
+    /// Returns `None` when response doesn't contain any validator, so it cannot be revalidated.
+    pub(crate) fn new(headers: &HeaderMap, body: Bytes) -> Option<Self> {
+        let etag = headers.get(header::ETAG).cloned();
+        let last_modified = headers.get(header::LAST_MODIFIED).cloned();
+        if etag.is_none() && last_modified.is_none() {
+            return None;
+        }
+        Some(Self {
+            etag,
+            last_modified,
+            body,
+        })
+    }
+
+    /// Add `If-None-Match` and `If-Modified-Since` headers to request.
+    pub(crate) fn add_validators(&self, headers: &mut HeaderMap) {
+        if let Some(etag) = &self.etag {
+            headers.insert(header::IF_NONE_MATCH, etag.clone());
+        }
+        if let Some(last_modified) = &self.last_modified {
+            headers.insert(header::IF_MODIFIED_SINCE, last_modified.clone());
+        }
+    }
+}
+
+#[derive(Default)]
+struct Entries {
+    map: HashMap<reqwest::Url, CachedResponse>,
+    /// Keys in order of insertion, used for evicting oldest entries.
+    order: VecDeque<reqwest::Url>,
+}
+
+/// Cache of responses keyed by request URL. Responses with `ETag` or `Last-Modified` header are
+/// stored and next request for the same URL is sent with `If-None-Match` or `If-Modified-Since`
+/// header. When server returns 304 Not Modified, cached response is used.
+///
+/// Cloned cache shares stored responses.
+#[derive(Clone)]
+pub struct ResponseCache {
+    entries: Arc<Mutex<Entries>>,
+    max_entries: usize,
+}
+
+impl ResponseCache {
+    /// Creates new cache that holds at most `max_entries` responses, oldest responses are evicted
+    /// first.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: Default::default(),
+            max_entries,
+        }
+    }
+
+    /// Number of cached responses.
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all cached responses.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.map.clear();
+        entries.order.clear();
+    }
+
+    pub(crate) fn get(&self, url: &reqwest::Url) -> Option<CachedResponse> {
+        self.entries.lock().unwrap().map.get(url).cloned()
+    }
+
+    pub(crate) fn insert(&self, url: reqwest::Url, response: CachedResponse) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.map.insert(url.clone(), response).is_none() {
+            entries.order.push_back(url);
+        }
+        while entries.map.len() > self.max_entries {
+            match entries.order.pop_front() {
+                Some(oldest) => entries.map.remove(&oldest),
+                None => break,
+            };
+        }
+    }
+}
+
+impl fmt::Debug for ResponseCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResponseCache")
+            .field("len", &self.len())
+            .field("max_entries", &self.max_entries)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CachedResponse, ResponseCache};
+    use bytes::Bytes;
+    use reqwest::header::{self, HeaderMap, HeaderValue};
+
+    fn cached(etag: &'static str) -> CachedResponse {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ETAG, HeaderValue::from_static(etag));
+        CachedResponse::new(&headers, Bytes::from_static(b"{}")).unwrap()
+    }
+
+    #[test]
+    fn test_without_validators() {
+        assert!(CachedResponse::new(&HeaderMap::new(), Bytes::new()).is_none());
+    }
+
+    #[test]
+    fn test_add_validators() {
+        let mut headers = HeaderMap::new();
+        cached("\"abc\"").add_validators(&mut headers);
+        assert_eq!("\"abc\"", headers[header::IF_NONE_MATCH]);
+        assert!(!headers.contains_key(header::IF_MODIFIED_SINCE));
+    }
+
+    #[test]
+    fn test_eviction() {
+        let cache = ResponseCache::new(2);
+        let url = |path| {
+            reqwest::Url::parse("https://example.com/")
+                .unwrap()
+                .join(path)
+                .unwrap()
+        };
+        cache.insert(url("a"), cached("a"));
+        cache.insert(url("b"), cached("b"));
+        cache.insert(url("b"), cached("b2"));
+        assert_eq!(2, cache.len());
+        cache.insert(url("c"), cached("c"));
+        assert_eq!(2, cache.len());
+        assert!(cache.get(&url("a")).is_none());
+        assert!(cache.get(&url("b")).is_some());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
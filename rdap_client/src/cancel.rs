@@ -0,0 +1,93 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    wakers: Mutex<Vec<Waker>>,
+}
+
+/// Token for cooperative cancellation of client operations. When cancelled, all operations of
+/// client created by `Client::with_cancellation` are aborted and return `ClientError::Cancelled`.
+///
+/// Cloned tokens share state, so token can be cancelled from other task or thread.
+#[derive(Clone, Default)]
+pub struct CancellationToken(Arc<Inner>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Cancel all operations that use this token, including future ones.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        for waker in self.0.wakers.lock().unwrap().drain(..) {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Returns future that completes when token is cancelled.
+    pub fn cancelled(&self) -> Cancelled {
+        Cancelled(self.clone())
+    }
+}
+
+impl std::fmt::Debug for CancellationToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CancellationToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Future returned by `CancellationToken::cancelled`.
+#[derive(Debug)]
+pub struct Cancelled(CancellationToken);
+
+impl Future for Cancelled {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let inner = &(self.0).0;
+        if inner.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+
+        let mut wakers = inner.wakers.lock().unwrap();
+        // Check again with lock held, so `cancel` cannot drain wakers between check and push.
+        if inner.cancelled.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CancellationToken;
+    use futures::FutureExt;
+
+    #[test]
+    fn test_cancel() {
+        let token = CancellationToken::new();
+        let mut cancelled = token.cancelled();
+        assert!(!token.is_cancelled());
+        assert!((&mut cancelled).now_or_never().is_none());
+
+        token.clone().cancel();
+        assert!(token.is_cancelled());
+        assert!(cancelled.now_or_never().is_some());
+        assert!(token.cancelled().now_or_never().is_some());
+    }
+}
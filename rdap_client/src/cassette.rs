@@ -0,0 +1,205 @@
+//! VCR-style recording of live responses to JSON cassettes and their replay.
+
+use crate::replay::query_key;
+use crate::{ClientError, Middleware, MiddlewareFuture, Next};
+use reqwest::header::{self, HeaderName};
+use reqwest::ResponseBuilderExt;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Response headers that are not recorded. Body is stored decompressed and cookies can contain
+/// secrets.
+const SKIPPED_HEADERS: [HeaderName; 4] = [
+    header::CONTENT_ENCODING,
+    header::CONTENT_LENGTH,
+    header::TRANSFER_ENCODING,
+    header::SET_COOKIE,
+];
+
+/// How `Cassette` handles requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+    /// When cassette file exists, requests are replayed from it. Otherwise, requests are sent and
+    /// recorded to a new cassette.
+    RecordOnce,
+    /// Requests are just replayed from cassette, request without recorded response fails with
+    /// `ClientError::Cassette`.
+    ReplayOnly,
+    /// Requests are sent to servers, cassette is not used.
+    Passthrough,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Interaction {
+    key: String,
+    method: String,
+    url: String,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+/// Recorded request and response pairs stored in JSON file. Requests are matched by key built
+/// from server host, query type and argument, so the same query matches regardless of argument
+/// case or order of search parameters.
+///
+/// ```no_run
+/// use rdap_client::{Cassette, CassetteMode, Client};
+///
+/// let cassette = Cassette::load("tests/cassettes/lookup.json", CassetteMode::RecordOnce).unwrap();
+/// let client = Client::builder().cassette(cassette).build().unwrap();
+/// ```
+pub struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    recording: bool,
+    interactions: Mutex<Vec<Interaction>>,
+}
+
+impl Cassette {
+    /// Load cassette from given file. File doesn't have to exist, unless mode is `ReplayOnly`.
+    pub fn load<P: AsRef<Path>>(path: P, mode: CassetteMode) -> std::io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = match std::fs::read(&path) {
+            Ok(content) => Some(serde_json::from_slice::<CassetteFile>(&content)?),
+            Err(e)
+                if e.kind() == std::io::ErrorKind::NotFound && mode != CassetteMode::ReplayOnly =>
+            {
+                None
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            path,
+            mode,
+            recording: mode == CassetteMode::RecordOnce && file.is_none(),
+            interactions: Mutex::new(file.unwrap_or_default().interactions),
+        })
+    }
+
+    /// Returns true if cassette records new interactions.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Mode cassette was loaded with.
+    pub fn mode(&self) -> CassetteMode {
+        self.mode
+    }
+
+    fn key(method: &reqwest::Method, url: &reqwest::Url) -> String {
+        let host = url.host_str().unwrap_or_default().to_lowercase();
+        let query = query_key(url).unwrap_or_else(|| url.path().to_owned());
+        format!("{} {}/{}", method, host, query)
+    }
+
+    fn replay(&self, request: &reqwest::Request) -> Result<reqwest::Response, ClientError> {
+        let key = Self::key(request.method(), request.url());
+        let interactions = self.interactions.lock().unwrap();
+        let interaction = interactions
+            .iter()
+            .find(|interaction| interaction.key == key)
+            .ok_or_else(|| {
+                ClientError::Cassette(format!("no recorded response for {}", key).into())
+            })?;
+
+        let mut response = http::Response::builder()
+            .url(request.url().clone())
+            .status(interaction.status);
+        for (name, value) in &interaction.headers {
+            response = response.header(name.as_str(), value.as_str());
+        }
+        let response = response
+            .body(interaction.body.clone().into_bytes())
+            .map_err(|e| ClientError::Cassette(e.into()))?;
+        Ok(response.into())
+    }
+
+    async fn record(
+        &self,
+        response: reqwest::Response,
+        method: &reqwest::Method,
+    ) -> Result<reqwest::Response, ClientError> {
+        let url = response.url().clone();
+        let status = response.status();
+        let mut headers = response.headers().clone();
+        for name in &SKIPPED_HEADERS {
+            headers.remove(name);
+        }
+        let body = response.bytes().await?;
+
+        let interaction = Interaction {
+            key: Self::key(method, &url),
+            method: method.to_string(),
+            url: url.to_string(),
+            status: status.as_u16(),
+            headers: headers
+                .iter()
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_owned()))
+                })
+                .collect(),
+            body: String::from_utf8_lossy(&body).into_owned(),
+        };
+        self.save(interaction)
+            .map_err(|e| ClientError::Cassette(e.into()))?;
+
+        let mut builder = http::Response::builder().url(url).status(status);
+        if let Some(builder_headers) = builder.headers_mut() {
+            *builder_headers = headers;
+        }
+        let response = builder
+            .body(body.to_vec())
+            .map_err(|e| ClientError::Cassette(e.into()))?;
+        Ok(response.into())
+    }
+
+    /// Add interaction and write whole cassette to file, so it is complete even when process
+    /// doesn't finish normally.
+    fn save(&self, interaction: Interaction) -> std::io::Result<()> {
+        let mut interactions = self.interactions.lock().unwrap();
+        interactions.retain(|recorded| recorded.key != interaction.key);
+        interactions.push(interaction);
+        let file = CassetteFile {
+            interactions: interactions.clone(),
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&file)?)
+    }
+}
+
+impl fmt::Debug for Cassette {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cassette")
+            .field("path", &self.path)
+            .field("mode", &self.mode)
+            .field("recording", &self.recording)
+            .finish()
+    }
+}
+
+impl Middleware for Cassette {
+    fn handle<'a>(&'a self, request: reqwest::Request, next: Next<'a>) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            if self.mode == CassetteMode::Passthrough {
+                return next.run(request).await;
+            }
+            if !self.recording {
+                debug!(url = %request.url(), "replaying response from cassette");
+                return self.replay(&request);
+            }
+            let method = request.method().clone();
+            let response = next.run(request).await?;
+            self.record(response, &method).await
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use base64::Engine;
+use reqwest::header::{self, HeaderValue, InvalidHeaderValue};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Credentials sent in `Authorization` header to RDAP server.
+#[derive(Clone)]
+pub struct Credentials(HeaderValue);
+
+impl Credentials {
+    /// HTTP basic authentication.
+    pub fn basic(username: &str, password: Option<&str>) -> Self {
+        let credentials = format!("{}:{}", username, password.unwrap_or_default());
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+        // Base64 encoded string contains only valid header characters.
+        Self::new(HeaderValue::from_str(&format!("Basic {}", encoded)).unwrap())
+    }
+
+    /// Bearer token, for example OAuth access token. Returns error when token contains
+    /// characters that are not allowed in header value.
+    pub fn bearer(token: &str) -> Result<Self, InvalidHeaderValue> {
+        Ok(Self::new(HeaderValue::from_str(&format!(
+            "Bearer {}",
+            token
+        ))?))
+    }
+
+    fn new(mut value: HeaderValue) -> Self {
+        value.set_sensitive(true);
+        Self(value)
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Credentials(..)")
+    }
+}
+
+/// Credentials for RDAP servers keyed by lowercase host name.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CredentialStore(HashMap<String, Credentials>);
+
+impl CredentialStore {
+    pub(crate) fn insert(&mut self, host: &str, credentials: Credentials) {
+        self.0.insert(host.to_lowercase(), credentials);
+    }
+
+    /// Add `Authorization` header to request, if there are credentials for its host.
+    pub(crate) fn add_to(&self, request: &mut reqwest::Request) {
+        let credentials = request.url().host_str().and_then(|host| self.0.get(host));
+        if let Some(credentials) = credentials {
+            request
+                .headers_mut()
+                .insert(header::AUTHORIZATION, credentials.0.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CredentialStore, Credentials};
+    use reqwest::header;
+
+    #[test]
+    fn test_credentials() {
+        let mut store = CredentialStore::default();
+        store.insert("RDAP.example.com", Credentials::basic("user", Some("pass")));
+        store.insert("other.example.com", Credentials::bearer("token").unwrap());
+        assert!(Credentials::bearer("invalid\ntoken").is_err());
+
+        let header = |url: &str| {
+            let mut request = reqwest::Request::new(reqwest::Method::GET, url.parse().unwrap());
+            store.add_to(&mut request);
+            request.headers().get(header::AUTHORIZATION).cloned()
+        };
+        assert_eq!(
+            "Basic dXNlcjpwYXNz",
+            header("https://rdap.example.com/domain/example.com").unwrap()
+        );
+        assert_eq!(
+            "Bearer token",
+            header("https://other.example.com/help").unwrap()
+        );
+        assert!(header("https://example.com/help").is_none());
+    }
+}
@@ -0,0 +1,331 @@
+//! Comparison of nameservers and DNSSEC data from RDAP responses of domains with records
+//! published in DNS.
+
+use crate::{parser, ClientError};
+use base64::Engine;
+use hickory_resolver::error::ResolveErrorKind;
+use hickory_resolver::proto::rr::{RData, RecordType};
+use hickory_resolver::proto::serialize::binary::BinEncodable;
+use hickory_resolver::TokioAsyncResolver;
+
+/// Difference between RDAP response of domain and its records in DNS, returned by
+/// `DnsChecker::check`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DnsMismatch {
+    /// Nameserver listed in RDAP response doesn't have NS record.
+    NameserverNotInDns(String),
+    /// NS record of nameserver that is not listed in RDAP response.
+    NameserverNotInRdap(String),
+    /// DS record from `secureDNS` is not published in parent zone.
+    DsNotInDns(parser::DsData),
+    /// DS record published in parent zone is not listed in `secureDNS` and doesn't reference any
+    /// key from `keyData`.
+    DsNotInRdap(parser::DsData),
+    /// Key from `keyData` is not published as DNSKEY record of zone.
+    KeyNotInDns(parser::KeyData),
+    /// `delegationSigned` doesn't correspond to presence of DS records in parent zone.
+    DelegationSigned { rdap: bool, dns: bool },
+}
+
+/// Records of domain resolved by `DnsChecker::resolve`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DnsRecords {
+    /// Hosts of NS records, in lowercase and without trailing dot.
+    pub nameservers: Vec<String>,
+    /// DS records from parent zone.
+    pub ds_data: Vec<parser::DsData>,
+    /// DNSKEY records of zone.
+    pub key_data: Vec<parser::KeyData>,
+}
+
+impl DnsRecords {
+    /// Returns differences between `domain` and these records. Keys published in DNS, that are
+    /// not listed in `keyData`, are not reported, because registries usually contain just keys
+    /// referenced by DS records.
+    pub fn compare(&self, domain: &parser::Domain) -> Vec<DnsMismatch> {
+        let mut mismatches = Vec::new();
+
+        let hosts = domain.nameserver_hosts();
+        for host in &hosts {
+            if !self.nameservers.contains(host) {
+                mismatches.push(DnsMismatch::NameserverNotInDns(host.clone()));
+            }
+        }
+        for host in &self.nameservers {
+            if !hosts.contains(host) {
+                mismatches.push(DnsMismatch::NameserverNotInRdap(host.clone()));
+            }
+        }
+
+        let secure_dns = domain.secure_dns.as_ref();
+        let ds_data = secure_dns
+            .map(parser::SecureDns::ds_data)
+            .unwrap_or_default();
+        let key_data = secure_dns
+            .map(parser::SecureDns::key_data)
+            .unwrap_or_default();
+        let owner = domain.ldh_name.as_deref().unwrap_or_default();
+        for ds in ds_data {
+            if !self.ds_data.iter().any(|published| same_ds(ds, published)) {
+                mismatches.push(DnsMismatch::DsNotInDns(ds.clone()));
+            }
+        }
+        for ds in &self.ds_data {
+            let listed = ds_data.iter().any(|listed| same_ds(listed, ds))
+                || key_data
+                    .iter()
+                    .any(|key| ds.matches(owner, key) == Ok(true));
+            if !listed {
+                mismatches.push(DnsMismatch::DsNotInRdap(ds.clone()));
+            }
+        }
+        for key in key_data {
+            if !self
+                .key_data
+                .iter()
+                .any(|published| same_key(key, published))
+            {
+                mismatches.push(DnsMismatch::KeyNotInDns(key.clone()));
+            }
+        }
+
+        if let Some(signed) = secure_dns.and_then(parser::SecureDns::delegation_signed) {
+            let published = !self.ds_data.is_empty();
+            if signed != published {
+                mismatches.push(DnsMismatch::DelegationSigned {
+                    rdap: signed,
+                    dns: published,
+                });
+            }
+        }
+
+        mismatches
+    }
+}
+
+/// Compares DS records, key tag is ignored when RDAP response doesn't contain it.
+fn same_ds(listed: &parser::DsData, published: &parser::DsData) -> bool {
+    listed.algorithm() == published.algorithm()
+        && listed.digest_type() == published.digest_type()
+        && listed
+            .key_tag()
+            .map_or(true, |tag| Some(tag) == published.key_tag())
+        && without_whitespace(listed.digest()).eq_ignore_ascii_case(published.digest())
+}
+
+fn same_key(listed: &parser::KeyData, published: &parser::KeyData) -> bool {
+    listed.flags() == published.flags()
+        && listed.protocol() == published.protocol()
+        && listed.algorithm() == published.algorithm()
+        && without_whitespace(listed.public_key()) == published.public_key()
+}
+
+// Some registries split digests and keys to more lines.
+fn without_whitespace(value: &str) -> String {
+    value.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Parses DS RDATA in wire format (RFC 4034 section 5.1).
+fn parse_ds(rdata: &[u8]) -> Option<parser::DsData> {
+    if rdata.len() < 4 {
+        return None;
+    }
+    let digest: String = rdata[4..].iter().map(|b| format!("{:02X}", b)).collect();
+    Some(parser::DsData::new(
+        u16::from_be_bytes([rdata[0], rdata[1]]),
+        rdata[2].into(),
+        rdata[3].into(),
+        digest,
+    ))
+}
+
+/// Parses DNSKEY RDATA in wire format (RFC 4034 section 2.1).
+fn parse_dnskey(rdata: &[u8]) -> Option<parser::KeyData> {
+    if rdata.len() < 4 {
+        return None;
+    }
+    Some(parser::KeyData::new(
+        u16::from_be_bytes([rdata[0], rdata[1]]),
+        rdata[2],
+        rdata[3].into(),
+        base64::engine::general_purpose::STANDARD.encode(&rdata[4..]),
+    ))
+}
+
+/// Checks that nameservers and DNSSEC data in RDAP responses of domains correspond to records
+/// published in DNS.
+///
+/// ```no_run
+/// # async fn run(client: rdap_client::Client) -> Result<(), rdap_client::ClientError> {
+/// let checker = rdap_client::DnsChecker::new()?;
+/// let domain = client.domain("example.com").await?;
+/// for mismatch in checker.check(&domain).await? {
+///     println!("{:?}", mismatch);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DnsChecker {
+    resolver: TokioAsyncResolver,
+}
+
+impl DnsChecker {
+    /// Creates checker that uses resolver from system configuration (`/etc/resolv.conf` on Unix).
+    pub fn new() -> Result<Self, ClientError> {
+        TokioAsyncResolver::tokio_from_system_conf()
+            .map(Self::with_resolver)
+            .map_err(|e| ClientError::Dns(Box::new(e)))
+    }
+
+    pub fn with_resolver(resolver: TokioAsyncResolver) -> Self {
+        Self { resolver }
+    }
+
+    /// Resolves NS, DS and DNSKEY records of `domain`. Missing records are not error.
+    pub async fn resolve(&self, domain: &str) -> Result<DnsRecords, ClientError> {
+        // Fully qualified name, so search domains of resolver are not used.
+        let name = format!("{}.", domain.trim_end_matches('.'));
+        let (nameservers, ds_data, key_data) = futures::try_join!(
+            self.lookup(&name, RecordType::NS),
+            self.lookup(&name, RecordType::DS),
+            self.lookup(&name, RecordType::DNSKEY),
+        )?;
+        Ok(DnsRecords {
+            nameservers: nameservers
+                .iter()
+                .filter_map(|rdata| match rdata {
+                    RData::NS(host) => {
+                        Some(host.0.to_ascii().trim_end_matches('.').to_ascii_lowercase())
+                    }
+                    _ => None,
+                })
+                .collect(),
+            ds_data: ds_data
+                .iter()
+                .filter_map(|rdata| parse_ds(&rdata.to_bytes().ok()?))
+                .collect(),
+            key_data: key_data
+                .iter()
+                .filter_map(|rdata| parse_dnskey(&rdata.to_bytes().ok()?))
+                .collect(),
+        })
+    }
+
+    /// Resolves records of domain and returns differences between them and `domain`, see
+    /// `DnsRecords::compare`.
+    pub async fn check(&self, domain: &parser::Domain) -> Result<Vec<DnsMismatch>, ClientError> {
+        let name = domain
+            .ldh_name
+            .as_deref()
+            .ok_or_else(|| ClientError::InvalidQuery("domain doesn't contain ldhName".into()))?;
+        Ok(self.resolve(name).await?.compare(domain))
+    }
+
+    /// Returns data of records of type `record_type`. DS and DNSKEY records are parsed from wire
+    /// format by `parse_ds` and `parse_dnskey`, because resolver parses them only with its
+    /// `dnssec` feature.
+    async fn lookup(&self, name: &str, record_type: RecordType) -> Result<Vec<RData>, ClientError> {
+        match self.resolver.lookup(name, record_type).await {
+            Ok(lookup) => Ok(lookup
+                .record_iter()
+                .filter(|record| record.record_type() == record_type)
+                .filter_map(|record| record.data().cloned())
+                .collect()),
+            Err(e) if matches!(e.kind(), ResolveErrorKind::NoRecordsFound { .. }) => Ok(Vec::new()),
+            Err(e) => Err(ClientError::Dns(Box::new(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_dnskey, parse_ds, DnsMismatch, DnsRecords};
+    use crate::parser;
+    use rdap_types::{DigestType, DnssecAlgorithm};
+
+    // Example from RFC 4034 section 5.4.
+    const PUBLIC_KEY: &str = "AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMzNXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbtG9DMBmADjFDc2w/rljwvFw==";
+    const DIGEST: &str = "2BB183AF5F22588179A53B0A98631FAD1A292118";
+
+    #[test]
+    fn test_parse_records() {
+        let key = parser::KeyData::new(256, 3, DnssecAlgorithm::RsaSha1, PUBLIC_KEY);
+        assert_eq!(Some(key.clone()), parse_dnskey(&key.rdata().unwrap()));
+
+        let mut rdata = vec![0xec, 0x45, 5, 1];
+        rdata.extend(
+            (0..DIGEST.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&DIGEST[i..i + 2], 16).unwrap()),
+        );
+        let ds = parse_ds(&rdata).unwrap();
+        assert_eq!(Some(60485), ds.key_tag());
+        assert_eq!(DnssecAlgorithm::RsaSha1, ds.algorithm());
+        assert_eq!(DigestType::Sha1, ds.digest_type());
+        assert_eq!(DIGEST, ds.digest());
+        assert_eq!(None, parse_ds(&[0, 1]));
+    }
+
+    #[test]
+    fn test_compare() {
+        let json = r#"{
+            "objectClassName": "domain",
+            "ldhName": "dskey.example.com",
+            "nameservers": [
+                {"objectClassName": "nameserver", "ldhName": "NS1.example.com."},
+                {"objectClassName": "nameserver", "ldhName": "ns2.example.com"}
+            ],
+            "secureDNS": {
+                "delegationSigned": true,
+                "dsData": [{"keyTag": 60485, "algorithm": 5, "digestType": 1, "digest": "2bb183af5f22588179a53b0a98631fad1a292118"}]
+            }
+        }"#;
+        let domain: parser::Domain = serde_json::from_str(json).unwrap();
+        let ds = parser::DsData::new(60485, DnssecAlgorithm::RsaSha1, DigestType::Sha1, DIGEST);
+        let key = parser::KeyData::new(256, 3, DnssecAlgorithm::RsaSha1, PUBLIC_KEY);
+
+        let mut records = DnsRecords {
+            nameservers: vec!["ns1.example.com".into(), "ns2.example.com".into()],
+            ds_data: vec![ds.clone()],
+            key_data: vec![key.clone()],
+        };
+        assert_eq!(Vec::<DnsMismatch>::new(), records.compare(&domain));
+
+        records.nameservers[1] = "ns3.example.com".into();
+        let other_ds = parser::DsData::new(1, DnssecAlgorithm::RsaSha1, DigestType::Sha1, "00");
+        records.ds_data = vec![other_ds.clone()];
+        assert_eq!(
+            vec![
+                DnsMismatch::NameserverNotInDns("ns2.example.com".into()),
+                DnsMismatch::NameserverNotInRdap("ns3.example.com".into()),
+                DnsMismatch::DsNotInDns(domain.secure_dns.as_ref().unwrap().ds_data()[0].clone()),
+                DnsMismatch::DsNotInRdap(other_ds),
+            ],
+            records.compare(&domain)
+        );
+
+        // DS record computed from listed key is not reported.
+        let json = r#"{
+            "objectClassName": "domain",
+            "ldhName": "dskey.example.com",
+            "secureDNS": {"delegationSigned": false, "keyData": [{"flags": 256, "protocol": 3, "algorithm": 5, "publicKey": "AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMz NXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbt G9DMBmADjFDc2w/rljwvFw=="}]}
+        }"#;
+        let domain: parser::Domain = serde_json::from_str(json).unwrap();
+        let records = DnsRecords {
+            nameservers: Vec::new(),
+            ds_data: vec![ds],
+            key_data: Vec::new(),
+        };
+        assert_eq!(
+            vec![
+                DnsMismatch::KeyNotInDns(domain.secure_dns.as_ref().unwrap().key_data()[0].clone()),
+                DnsMismatch::DelegationSigned {
+                    rdap: false,
+                    dns: true
+                },
+            ],
+            records.compare(&domain)
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+struct State {
+    max_in_flight: usize,
+    in_flight: usize,
+    next_id: u64,
+    /// Hosts with waiting requests in order in which they get next free slot.
+    hosts: VecDeque<String>,
+    waiting: HashMap<String, VecDeque<u64>>,
+    wakers: HashMap<u64, Waker>,
+    /// Waiting requests that got slot, but their future was not polled yet.
+    granted: HashSet<u64>,
+}
+
+impl State {
+    fn is_waiting(&self) -> bool {
+        !self.hosts.is_empty()
+    }
+
+    /// Give free slots to waiting requests, taking one request from every host in turn.
+    fn dispatch(&mut self) {
+        while self.in_flight < self.max_in_flight {
+            let host = match self.hosts.pop_front() {
+                Some(host) => host,
+                None => break,
+            };
+            let queue = self.waiting.get_mut(&host).unwrap();
+            let id = queue.pop_front().unwrap();
+            if queue.is_empty() {
+                self.waiting.remove(&host);
+            } else {
+                self.hosts.push_back(host);
+            }
+            self.in_flight += 1;
+            self.granted.insert(id);
+            if let Some(waker) = self.wakers.remove(&id) {
+                waker.wake();
+            }
+        }
+    }
+
+    fn release(&mut self) {
+        self.in_flight -= 1;
+        self.dispatch();
+    }
+
+    /// Remove waiting request from queue of its host.
+    fn remove(&mut self, host: &str, id: u64) {
+        self.wakers.remove(&id);
+        if let Some(queue) = self.waiting.get_mut(host) {
+            queue.retain(|waiting| *waiting != id);
+            if queue.is_empty() {
+                self.waiting.remove(host);
+                self.hosts.retain(|waiting| waiting != host);
+            }
+        }
+    }
+}
+
+/// Throttle that limits number of requests in flight across all servers. When limit is reached,
+/// requests wait in queue per server host and free slots are given to hosts in turn, so one slow
+/// server with many queued requests doesn't block requests to other servers.
+///
+/// Cloned queues share state, so one queue can throttle more clients. Slot is held until response
+/// headers are received (including retries, but not delays between them).
+#[derive(Clone)]
+pub struct FairQueue(Arc<Mutex<State>>);
+
+impl FairQueue {
+    /// Creates new queue with at most `max_in_flight` concurrent requests. Zero is handled like
+    /// one.
+    pub fn new(max_in_flight: usize) -> Self {
+        Self(Arc::new(Mutex::new(State {
+            max_in_flight: max_in_flight.max(1),
+            in_flight: 0,
+            next_id: 0,
+            hosts: VecDeque::new(),
+            waiting: HashMap::new(),
+            wakers: HashMap::new(),
+            granted: HashSet::new(),
+        })))
+    }
+
+    /// Number of requests currently in flight.
+    pub fn in_flight(&self) -> usize {
+        self.0.lock().unwrap().in_flight
+    }
+
+    /// Number of requests waiting for free slot.
+    pub fn queued(&self) -> usize {
+        self.0
+            .lock()
+            .unwrap()
+            .waiting
+            .values()
+            .map(VecDeque::len)
+            .sum()
+    }
+
+    /// Returns future that completes when request to given host can be sent.
+    pub(crate) fn acquire(&self, host: &str) -> Acquire {
+        Acquire {
+            queue: self.clone(),
+            host: host.to_lowercase(),
+            id: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for FairQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.0.lock().unwrap();
+        f.debug_struct("FairQueue")
+            .field("max_in_flight", &state.max_in_flight)
+            .field("in_flight", &state.in_flight)
+            .finish()
+    }
+}
+
+/// Future returned by `FairQueue::acquire`.
+pub(crate) struct Acquire {
+    queue: FairQueue,
+    host: String,
+    /// Set when request waits in queue.
+    id: Option<u64>,
+}
+
+impl Future for Acquire {
+    type Output = Permit;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Permit> {
+        let this = &mut *self;
+        let mut state = this.queue.0.lock().unwrap();
+        match this.id {
+            None if state.in_flight < state.max_in_flight && !state.is_waiting() => {
+                state.in_flight += 1;
+            }
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                if !state.waiting.contains_key(&this.host) {
+                    state.hosts.push_back(this.host.clone());
+                }
+                state
+                    .waiting
+                    .entry(this.host.clone())
+                    .or_default()
+                    .push_back(id);
+                state.wakers.insert(id, cx.waker().clone());
+                this.id = Some(id);
+                return Poll::Pending;
+            }
+            Some(id) if state.granted.remove(&id) => {
+                this.id = None;
+            }
+            Some(id) => {
+                state.wakers.insert(id, cx.waker().clone());
+                return Poll::Pending;
+            }
+        }
+        drop(state);
+        Poll::Ready(Permit(this.queue.clone()))
+    }
+}
+
+impl Drop for Acquire {
+    fn drop(&mut self) {
+        let id = match self.id {
+            Some(id) => id,
+            None => return,
+        };
+        let mut state = self.queue.0.lock().unwrap();
+        if state.granted.remove(&id) {
+            // Slot was given to this request, but it will be never sent.
+            state.release();
+        } else {
+            state.remove(&self.host, id);
+        }
+    }
+}
+
+/// Slot for one request, released when dropped.
+pub(crate) struct Permit(FairQueue);
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.0 .0.lock().unwrap().release();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FairQueue;
+
+    #[tokio::test]
+    async fn test_fair_queue() {
+        let queue = FairQueue::new(1);
+        let first = queue.acquire("a").await;
+        let mut a2 = Box::pin(queue.acquire("a"));
+        let mut a3 = Box::pin(queue.acquire("A"));
+        let mut b1 = Box::pin(queue.acquire("b"));
+        let mut c1 = Box::pin(queue.acquire("c"));
+        assert!(futures::poll!(&mut a2).is_pending());
+        assert!(futures::poll!(&mut a3).is_pending());
+        assert!(futures::poll!(&mut b1).is_pending());
+        assert!(futures::poll!(&mut c1).is_pending());
+        assert_eq!(1, queue.in_flight());
+        assert_eq!(4, queue.queued());
+
+        // Cancelled request doesn't take slot.
+        drop(c1);
+        assert_eq!(3, queue.queued());
+
+        // Hosts take turns, even when host "a" was queued first.
+        drop(first);
+        let permit = a2.await;
+        assert!(futures::poll!(&mut a3).is_pending());
+        drop(permit);
+        assert!(futures::poll!(&mut a3).is_pending());
+        let permit = b1.await;
+        drop(permit);
+        drop(a3.await);
+        assert_eq!(0, queue.in_flight());
+        assert_eq!(0, queue.queued());
+    }
+}
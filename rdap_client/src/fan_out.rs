@@ -0,0 +1,118 @@
+//! Search sent to many servers at once, for example to all RIRs, because entity or nameserver can
+//! be registered at any of them.
+
+use crate::{
+    bootstrap, encode_search_value, parser, Client, ClientError, SearchEntity, SearchNameserver,
+};
+use futures::TryStreamExt;
+use parser::RdapObject;
+use serde::de::DeserializeOwned;
+use std::collections::HashSet;
+
+/// Object found by `Client::search_entities_fan_out` or `Client::search_nameservers_fan_out`.
+#[derive(Debug)]
+pub struct SearchHit<T> {
+    /// Base URL of server that returned object.
+    pub server: String,
+    pub object: T,
+}
+
+/// Merged results of search sent to many servers.
+#[derive(Debug)]
+pub struct FanOutResults<T> {
+    /// Found objects in order of servers. Object already returned by previous server, that is
+    /// object with the same handle and the same `self` link (or `port43` when it doesn't have
+    /// `self` link), is left out.
+    pub hits: Vec<SearchHit<T>>,
+    /// Servers that failed, their results are not included in `hits`.
+    pub errors: Vec<(String, ClientError)>,
+}
+
+/// Identity of object across servers, `None` when it is not known.
+fn identity<T: RdapObject>(object: &T) -> Option<(String, String)> {
+    let handle = object.handle()?;
+    let source = object
+        .self_link()
+        .map(|link| link.href.as_str())
+        .or_else(|| object.port43())?;
+    Some((handle.to_ascii_lowercase(), source.to_owned()))
+}
+
+impl Client {
+    /// Search for entities at all given servers concurrently, or at all RIRs
+    /// (`bootstrap::RIR_SERVERS`) when `servers` is `None`. Failure of one server doesn't fail
+    /// whole search, it is just reported in `errors`. Fails just when search is not valid.
+    pub async fn search_entities_fan_out(
+        &self,
+        search: SearchEntity,
+        servers: Option<&[&str]>,
+    ) -> Result<FanOutResults<parser::Entity>, ClientError> {
+        search.validate()?;
+        let mut path = format!("entities?{}=", search.key());
+        encode_search_value(&search.value(), &mut path);
+        Ok(self.fan_out(servers, &path, "entitySearchResults").await)
+    }
+
+    /// Search for nameservers at all given servers concurrently, or at all RIRs when `servers` is
+    /// `None`, like `search_entities_fan_out`.
+    pub async fn search_nameservers_fan_out(
+        &self,
+        search: SearchNameserver,
+        servers: Option<&[&str]>,
+    ) -> Result<FanOutResults<parser::Nameserver>, ClientError> {
+        search.validate()?;
+        let mut path = format!("nameservers?{}=", search.key());
+        encode_search_value(&search.value(), &mut path);
+        Ok(self
+            .fan_out(servers, &path, "nameserverSearchResults")
+            .await)
+    }
+
+    /// Sends search request with `path` including query to all servers.
+    async fn fan_out<T: DeserializeOwned + RdapObject>(
+        &self,
+        servers: Option<&[&str]>,
+        path: &str,
+        key: &'static str,
+    ) -> FanOutResults<T> {
+        let servers = servers.unwrap_or(&bootstrap::RIR_SERVERS);
+        let responses = futures::future::join_all(servers.iter().map(|server| async move {
+            let server = bootstrap::base_url(server);
+            let url = format!("{}{}", server, path);
+            let result = match self.search_stream(url, key).await {
+                Ok(stream) => stream.try_collect::<Vec<T>>().await,
+                Err(e) => Err(e),
+            };
+            (server, result)
+        }))
+        .await;
+
+        let mut results = FanOutResults {
+            hits: Vec::new(),
+            errors: Vec::new(),
+        };
+        let mut identities = HashSet::new();
+        for (server, result) in responses {
+            let objects = match result {
+                Ok(objects) => objects,
+                Err(e) => {
+                    debug!(%server, error = ?e, "search failed");
+                    results.errors.push((server, e));
+                    continue;
+                }
+            };
+            for object in objects {
+                if let Some(identity) = identity(&object) {
+                    if !identities.insert(identity) {
+                        continue;
+                    }
+                }
+                results.hits.push(SearchHit {
+                    server: server.clone(),
+                    object,
+                });
+            }
+        }
+        results
+    }
+}
@@ -0,0 +1,203 @@
+//! Federated authentication for RDAP using OpenID Connect (farv1,
+//! [RFC 9560](https://www.rfc-editor.org/rfc/rfc9560)).
+//!
+//! Non-browser clients can use session-oriented device flow: `Client::farv1_device` starts
+//! the flow, end-user has to visit returned verification URL and enter user code, then
+//! `Client::farv1_device_poll` waits until the end-user is authenticated. Queries sent by client
+//! returned from `Client::with_farv1_session` use the session, so server returns data
+//! available to the authenticated user.
+//!
+//! For token-oriented flow, when access token is obtained directly from OpenID provider, pass it
+//! to `ClientBuilder::credentials` as `Credentials::bearer`.
+
+use crate::{parser, rt, Client, ClientError};
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use std::time::Duration;
+
+/// Polling interval used when server doesn't provide one, as RFC 8628 recommends.
+const DEFAULT_POLL_INTERVAL: u64 = 5;
+
+/// Session with one RDAP server, identified by session cookie.
+#[derive(Debug)]
+pub struct Farv1Session {
+    server: String,
+    cookie: Option<SessionCookie>,
+    /// Last response from session endpoint.
+    pub info: parser::Farv1Session,
+}
+
+impl Farv1Session {
+    /// Base URL of RDAP server that created session.
+    pub fn server(&self) -> &str {
+        &self.server
+    }
+}
+
+/// Session cookie that is sent only to the server that set it.
+#[derive(Debug, Clone)]
+pub(crate) struct SessionCookie {
+    url: reqwest::Url,
+    value: HeaderValue,
+}
+
+impl SessionCookie {
+    /// Add `Cookie` header to request, when it is sent to the same host and port.
+    pub(crate) fn add_to(&self, request: &mut reqwest::Request) {
+        let url = request.url();
+        if url.host() == self.url.host()
+            && url.port_or_known_default() == self.url.port_or_known_default()
+        {
+            request
+                .headers_mut()
+                .insert(header::COOKIE, self.value.clone());
+        }
+    }
+}
+
+/// Convert `Set-Cookie` headers to value of `Cookie` header, cookie attributes are ignored.
+fn session_cookie(headers: &HeaderMap) -> Option<HeaderValue> {
+    let cookies: Vec<_> = headers
+        .get_all(header::SET_COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .filter_map(|value| value.split(';').next())
+        .map(str::trim)
+        .filter(|cookie| !cookie.is_empty())
+        .collect();
+    if cookies.is_empty() {
+        return None;
+    }
+    HeaderValue::from_str(&cookies.join("; ")).ok()
+}
+
+impl Client {
+    /// Start device authorization flow at given RDAP server. `issuer` selects OpenID provider
+    /// from `farv1_openidcProviders` in help response, when `None`, default provider is used.
+    ///
+    /// End-user should visit `verification_url` from returned `info.farv1_device_info` and
+    /// enter `user_code`, then call `farv1_device_poll`.
+    pub async fn farv1_device(
+        &self,
+        server: &str,
+        issuer: Option<&str>,
+    ) -> Result<Farv1Session, ClientError> {
+        let url = format!("{}farv1_session/device", server);
+        let request = match issuer {
+            Some(issuer) => self.client.get(url).query(&[("farv1_iss", issuer)]),
+            None => self.client.get(url),
+        };
+        let (info, cookie) = self.send_farv1(request, None).await?;
+        Ok(Farv1Session {
+            server: server.to_owned(),
+            cookie,
+            info,
+        })
+    }
+
+    /// Poll server until end-user completes device authorization flow started by
+    /// `farv1_device`. Returns last error response when device code expires before that.
+    pub async fn farv1_device_poll(&self, session: &mut Farv1Session) -> Result<(), ClientError> {
+        let (interval, expires_in) = match &session.info.farv1_device_info {
+            Some(info) => (
+                info.interval.unwrap_or(DEFAULT_POLL_INTERVAL),
+                info.expires_in,
+            ),
+            None => (DEFAULT_POLL_INTERVAL, 0),
+        };
+        let interval = Duration::from_secs(interval);
+        let expires = rt::Instant::now() + Duration::from_secs(expires_in);
+        loop {
+            rt::sleep(interval).await;
+            match self
+                .farv1_request(session, "farv1_session/devicepoll")
+                .await
+            {
+                // Server responds with error until authorization is pending.
+                Err(ClientError::Rdap(..) | ClientError::Server(..))
+                    if rt::Instant::now() + interval < expires =>
+                {
+                    debug!("device authorization pending");
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Refresh session information, like token expiration.
+    pub async fn farv1_status(&self, session: &mut Farv1Session) -> Result<(), ClientError> {
+        self.farv1_request(session, "farv1_session/status").await
+    }
+
+    /// Ask server to refresh access token of session.
+    pub async fn farv1_refresh(&self, session: &mut Farv1Session) -> Result<(), ClientError> {
+        self.farv1_request(session, "farv1_session/refresh").await
+    }
+
+    /// End session at server.
+    pub async fn farv1_logout(&self, mut session: Farv1Session) -> Result<(), ClientError> {
+        self.farv1_request(&mut session, "farv1_session/logout")
+            .await
+    }
+
+    /// Returns clone of this `Client` that sends session cookie with requests to session server.
+    pub fn with_farv1_session(&self, session: &Farv1Session) -> Self {
+        let mut client = self.clone();
+        client.session_cookie = session.cookie.clone();
+        client
+    }
+
+    async fn farv1_request(
+        &self,
+        session: &mut Farv1Session,
+        path: &str,
+    ) -> Result<(), ClientError> {
+        let request = self.client.get(format!("{}{}", session.server, path));
+        let (info, cookie) = self.send_farv1(request, session.cookie.as_ref()).await?;
+        session.info = info;
+        if cookie.is_some() {
+            session.cookie = cookie;
+        }
+        Ok(())
+    }
+
+    /// Send request to session endpoint, returns parsed response and new session cookie.
+    async fn send_farv1(
+        &self,
+        request: reqwest::RequestBuilder,
+        cookie: Option<&SessionCookie>,
+    ) -> Result<(parser::Farv1Session, Option<SessionCookie>), ClientError> {
+        self.within_deadline(async {
+            let mut request = self.build_request(request)?;
+            if let Some(cookie) = cookie {
+                cookie.add_to(&mut request);
+            }
+            let url = request.url().clone();
+            let response = self.send_raw(request).await?;
+            let response = self.check_response(response.object).await?;
+            let body = self.read_body(response).await?;
+            let cookie =
+                session_cookie(body.head.headers()).map(|value| SessionCookie { url, value });
+            Ok((body.decode(self.parse_options)?, cookie))
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::session_cookie;
+    use reqwest::header::{self, HeaderMap, HeaderValue};
+
+    #[test]
+    fn test_session_cookie() {
+        let mut headers = HeaderMap::new();
+        assert!(session_cookie(&headers).is_none());
+
+        headers.append(
+            header::SET_COOKIE,
+            HeaderValue::from_static("id=abc; Path=/; Secure; HttpOnly"),
+        );
+        headers.append(header::SET_COOKIE, HeaderValue::from_static("lang=en"));
+        assert_eq!("id=abc; lang=en", session_cookie(&headers).unwrap());
+    }
+}
@@ -1,19 +1,78 @@
 //! Async and fast RDAP client and parser.
 
-use bytes::BytesMut;
+use body::Body;
+use cache::CachedResponse;
+use credentials::CredentialStore;
+use futures::{Stream, StreamExt};
 use ip_network::IpNetwork;
 use rdap_types as parser;
 use reqwest::{header, IntoUrl};
+use rt::Instant;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use std::convert::TryFrom;
+use std::collections::HashMap;
+use std::convert::{Infallible, TryFrom, TryInto};
+use std::future::Future;
+use std::marker::PhantomData;
 use std::net::IpAddr;
+use std::ops::Deref;
+use std::sync::Arc;
 use std::time::Duration;
+use wire::WireDebug;
 
+#[macro_use]
+mod trace;
+
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+mod body;
 pub mod bootstrap;
+#[cfg(not(target_arch = "wasm32"))]
+mod bootstrap_cache;
+mod cache;
+mod cancel;
+#[cfg(not(target_arch = "wasm32"))]
+mod cassette;
+mod credentials;
+#[cfg(all(feature = "dns", not(target_arch = "wasm32")))]
+mod dns;
+mod fair_queue;
+mod fan_out;
+mod farv1;
+mod metrics;
+mod middleware;
+mod query;
+mod raw;
+#[cfg(not(target_arch = "wasm32"))]
+mod replay;
+mod retry;
+mod rt;
+#[cfg(feature = "tower")]
+mod service;
+mod stream;
+mod wire;
+
+pub use cache::ResponseCache;
+pub use cancel::{CancellationToken, Cancelled};
+#[cfg(not(target_arch = "wasm32"))]
+pub use cassette::{Cassette, CassetteMode};
+pub use credentials::Credentials;
+#[cfg(all(feature = "dns", not(target_arch = "wasm32")))]
+pub use dns::{DnsChecker, DnsMismatch, DnsRecords};
+pub use fair_queue::FairQueue;
+pub use fan_out::{FanOutResults, SearchHit};
+pub use farv1::Farv1Session;
+use metrics::PendingMetrics;
+pub use metrics::{MetricsSink, RequestMetrics};
+pub use middleware::{Middleware, MiddlewareFuture, Next};
+pub use query::{QueryTarget, RdapQuery};
+pub use raw::Raw;
+pub use retry::{RetryClass, RetryPolicy};
+#[cfg(feature = "tower")]
+pub use service::{RdapRequest, RdapResponse};
 
 /// Query value for search domain request.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
 pub enum SearchDomain {
     Name(String),
     NsLdhName(String),
@@ -36,10 +95,58 @@ impl SearchDomain {
             Self::NsIp(value) => value.to_string(),
         }
     }
+
+    /// Returns domain which registry is searched, that is name pattern without labels with
+    /// wildcard.
+    fn registry_domain(&self) -> Result<&str, ClientError> {
+        match self {
+            Self::Name(pattern) => search_pattern_suffix(pattern),
+            Self::NsLdhName(..) | Self::NsIp(..) => Err(ClientError::InvalidQuery(
+                "registry to search for domains by nameserver is unknown, use search_domains_in"
+                    .into(),
+            )),
+        }
+    }
+
+    fn validate(&self) -> Result<(), ClientError> {
+        match self {
+            Self::Name(pattern) | Self::NsLdhName(pattern) => {
+                search_pattern_suffix(pattern).map(drop)
+            }
+            Self::NsIp(..) => Ok(()),
+        }
+    }
+}
+
+/// Validate partial match pattern of search (RFC 9082 section 4.1), where asterisk matches
+/// trailing characters of label, and return suffix following the last label with asterisk.
+fn search_pattern_suffix(pattern: &str) -> Result<&str, ClientError> {
+    let invalid = |reason: &str| {
+        ClientError::InvalidQuery(
+            format!("invalid search pattern {:?}: {}", pattern, reason).into(),
+        )
+    };
+    let name = pattern.trim_end_matches('.');
+    let mut suffix = name;
+    let mut end = 0;
+    for label in name.split('.') {
+        end += label.len();
+        match label.find('*') {
+            _ if label.is_empty() => return Err(invalid("empty label")),
+            Some(position) if position + 1 == label.len() => suffix = &name[end..],
+            Some(_) => return Err(invalid("asterisk must be at end of label")),
+            None => {}
+        }
+        end += 1;
+    }
+    match suffix.trim_start_matches('.') {
+        "" => Err(invalid("top-level domain must be given without asterisk")),
+        suffix => Ok(suffix),
+    }
 }
 
 /// Query value for search entity request.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
 pub enum SearchEntity {
     Fn(String),
     Handle(String),
@@ -59,10 +166,50 @@ impl SearchEntity {
             Self::Handle(value) => value,
         }
     }
+
+    /// Check that value is not empty and contains wildcard `*` only as its last character, or
+    /// before object tag of handle like `ABC*-ARIN`.
+    fn validate(&self) -> Result<(), ClientError> {
+        let (value, pattern) = match self {
+            Self::Fn(value) => (value, value.as_str()),
+            Self::Handle(value) => match value.rsplit_once('-') {
+                Some((prefix, tag)) if prefix.ends_with('*') && !tag.contains('*') => {
+                    (value, prefix)
+                }
+                _ => (value, value.as_str()),
+            },
+        };
+        let reason = match pattern.find('*') {
+            _ if pattern.trim_end_matches('*').trim().is_empty() => "empty value",
+            Some(position) if position + 1 != pattern.len() => "asterisk must be at end of value",
+            _ => return Ok(()),
+        };
+        Err(ClientError::InvalidQuery(
+            format!("invalid search pattern {:?}: {}", value, reason).into(),
+        ))
+    }
+}
+
+/// Percent encode search query value like URL path segment, so space is encoded as `%20` and not
+/// as `+` that servers can take literally. Wildcard `*` is kept as it is.
+fn encode_search_value(value: &str, out: &mut String) {
+    for (i, part) in value.split('*').enumerate() {
+        if i > 0 {
+            out.push('*');
+        }
+        encode_segment(part, out);
+    }
+}
+
+/// Returns URL of search request with query value encoded by `encode_search_value`.
+fn search_url(server: &str, path: &str, key: &str, value: &str) -> String {
+    let mut url = format!("{}{}?{}=", server, path, key);
+    encode_search_value(value, &mut url);
+    url
 }
 
 /// Query value for search nameserver request.
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
 pub enum SearchNameserver {
     Name(String),
     Ip(IpAddr),
@@ -82,10 +229,419 @@ impl SearchNameserver {
             Self::Ip(value) => value.to_string(),
         }
     }
+
+    /// Returns domain which registry is searched, that is name pattern without labels with
+    /// wildcard.
+    fn registry_domain(&self) -> Result<&str, ClientError> {
+        match self {
+            Self::Name(pattern) => search_pattern_suffix(pattern),
+            Self::Ip(..) => Err(ClientError::InvalidQuery(
+                "registry to search for nameservers by IP is unknown, use search_nameservers_in"
+                    .into(),
+            )),
+        }
+    }
+
+    fn validate(&self) -> Result<(), ClientError> {
+        match self {
+            Self::Name(pattern) => search_pattern_suffix(pattern).map(drop),
+            Self::Ip(..) => Ok(()),
+        }
+    }
+}
+
+/// Reverse search (RFC 9536) for objects related to entity with given properties, like domains
+/// which registrant has given name. Type parameter is type of search results. Supported
+/// properties are listed in `reverse_search_properties` of server help.
+///
+/// ```no_run
+/// # async fn example(client: rdap_client::Client) -> Result<(), rdap_client::ClientError> {
+/// use rdap_client::ReverseSearch;
+///
+/// let search = ReverseSearch::domains().role("registrant").full_name("Bobby*");
+/// let results = client.reverse_search("https://rdap.example/", search).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ReverseSearch<T> {
+    resource_type: &'static str,
+    conditions: Vec<(String, String)>,
+    results: PhantomData<fn() -> T>,
+}
+
+impl ReverseSearch<parser::DomainSearchResults> {
+    /// Search for domains.
+    pub fn domains() -> Self {
+        Self::new("domains")
+    }
+}
+
+impl ReverseSearch<parser::NameserverSearchResults> {
+    /// Search for nameservers.
+    pub fn nameservers() -> Self {
+        Self::new("nameservers")
+    }
+}
+
+impl ReverseSearch<parser::EntitySearchResults> {
+    /// Search for entities.
+    pub fn entities() -> Self {
+        Self::new("entities")
+    }
+}
+
+impl<T> ReverseSearch<T> {
+    fn new(resource_type: &'static str) -> Self {
+        Self {
+            resource_type,
+            conditions: Vec::new(),
+            results: PhantomData,
+        }
+    }
+
+    /// Full name of related entity, that can end with wildcard `*`.
+    pub fn full_name<S: Into<String>>(self, full_name: S) -> Self {
+        self.property("fn", full_name)
+    }
+
+    /// Handle of related entity, that can end with wildcard `*`.
+    pub fn handle<S: Into<String>>(self, handle: S) -> Self {
+        self.property("handle", handle)
+    }
+
+    /// Email address of related entity, that can end with wildcard `*`.
+    pub fn email<S: Into<String>>(self, email: S) -> Self {
+        self.property("email", email)
+    }
+
+    /// Role of related entity, like `registrant`.
+    pub fn role<S: Into<String>>(self, role: S) -> Self {
+        self.property("role", role)
+    }
+
+    /// Other property of related entity, that server supports. Can be called multiple times.
+    pub fn property<N: Into<String>, S: Into<String>>(mut self, name: N, value: S) -> Self {
+        self.conditions.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns path of query relative to server base URL.
+    fn path(&self) -> Result<String, ClientError> {
+        if self.conditions.is_empty() {
+            return Err(ClientError::InvalidQuery(
+                "reverse search needs at least one property".into(),
+            ));
+        }
+        let mut path = format!("{}/reverse_search/entity", self.resource_type);
+        for (i, (name, value)) in self.conditions.iter().enumerate() {
+            path.push(if i == 0 { '?' } else { '&' });
+            encode_segment(name, &mut path);
+            path.push('=');
+            encode_search_value(value, &mut path);
+        }
+        Ok(path)
+    }
+}
+
+/// Order of sorted search results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// Sorting and paging parameters (RFC 8977) and field set (RFC 8982) sent with search requests, set by
+/// `Client::with_search_params`. Properties that server can sort by are listed in
+/// `sorting_metadata` of search results.
+///
+/// ```no_run
+/// # async fn example(client: rdap_client::Client) -> Result<(), rdap_client::ClientError> {
+/// use rdap_client::{SearchDomain, SearchParams, SortOrder};
+///
+/// let mut params = SearchParams::new().sort("registrationDate", SortOrder::Descending);
+/// loop {
+///     let search = SearchDomain::Name("exa*.com".into());
+///     let results = client.with_search_params(params.clone()).search_domains(search).await?;
+///     match results.paging_metadata.as_ref().and_then(|paging| params.next_page(paging)) {
+///         Some(next) => params = next,
+///         None => break,
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct SearchParams {
+    sort: Vec<(String, SortOrder)>,
+    count: bool,
+    cursor: Option<String>,
+    field_set: Option<String>,
+}
+
+impl SearchParams {
+    /// Creates parameters without sorting and paging, same as default ones.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sort results by given property, like `name` or `registrationDate`. Can be called multiple
+    /// times, results with equal value of previous property are sorted by the next one.
+    pub fn sort<S: Into<String>>(mut self, property: S, order: SortOrder) -> Self {
+        self.sort.push((property.into(), order));
+        self
+    }
+
+    /// Ask server to return number of all results in `total_count` of paging metadata.
+    pub fn count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Request page of results at given cursor, that is taken from `next` link of previous page.
+    pub fn cursor<S: Into<String>>(mut self, cursor: S) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// Request given field set (RFC 8982) of results, like `id`, `brief` or `full`, so server
+    /// returns just subset of members of found objects.
+    pub fn field_set<S: Into<String>>(mut self, field_set: S) -> Self {
+        self.field_set = Some(field_set.into());
+        self
+    }
+
+    /// Returns parameters for the next page of results with cursor from `next` link of given
+    /// paging metadata, or `None` when there is no next page.
+    pub fn next_page(&self, paging: &parser::PagingMetadata) -> Option<Self> {
+        let url = reqwest::Url::parse(&paging.next_page()?.href).ok()?;
+        let (_, cursor) = url.query_pairs().find(|(name, _)| name == "cursor")?;
+        Some(self.clone().cursor(cursor))
+    }
+
+    /// Returns query parameters, empty when no parameter is set.
+    fn query(&self) -> Vec<(&'static str, String)> {
+        let mut query = Vec::new();
+        if !self.sort.is_empty() {
+            let sort: Vec<_> = self
+                .sort
+                .iter()
+                .map(|(property, order)| match order {
+                    SortOrder::Ascending => format!("{}:a", property),
+                    SortOrder::Descending => format!("{}:d", property),
+                })
+                .collect();
+            query.push(("sort", sort.join(",")));
+        }
+        if self.count {
+            query.push(("count", "true".to_owned()));
+        }
+        if let Some(cursor) = &self.cursor {
+            query.push(("cursor", cursor.clone()));
+        }
+        if let Some(field_set) = &self.field_set {
+            query.push(("fieldSet", field_set.clone()));
+        }
+        query
+    }
+}
+
+/// How are servers with `http` and `https` URLs from bootstrap used, set by
+/// `ClientBuilder::scheme_policy`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SchemePolicy {
+    /// Servers with `https` URL are tried first, servers with `http` URL only when they are
+    /// unavailable. Default, as recommended by RFC 7481.
+    #[default]
+    PreferHttps,
+    /// Plaintext `http` URLs are never queried, including redirects and explicitly given servers.
+    /// Such request fails with `ClientError::InsecureUrl` and servers with `http` URL from
+    /// bootstrap are skipped.
+    HttpsOnly,
+    /// Servers are tried in order in which they are listed in bootstrap.
+    AsListed,
+}
+
+/// IP address or network queried by `Client::ip`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IpQuery {
+    Address(IpAddr),
+    Network(IpNetwork),
+}
+
+impl From<IpAddr> for IpQuery {
+    fn from(address: IpAddr) -> Self {
+        Self::Address(address)
+    }
+}
+
+impl From<std::net::Ipv4Addr> for IpQuery {
+    fn from(address: std::net::Ipv4Addr) -> Self {
+        Self::Address(address.into())
+    }
+}
+
+impl From<std::net::Ipv6Addr> for IpQuery {
+    fn from(address: std::net::Ipv6Addr) -> Self {
+        Self::Address(address.into())
+    }
+}
+
+impl From<IpNetwork> for IpQuery {
+    fn from(network: IpNetwork) -> Self {
+        Self::Network(network)
+    }
+}
+
+/// Host bits of network are cleared, so `192.0.2.1/24` is queried as `192.0.2.0/24`.
+#[cfg(feature = "ipnet")]
+impl From<ipnet::IpNet> for IpQuery {
+    fn from(network: ipnet::IpNet) -> Self {
+        // Prefix length of `IpNet` is always valid.
+        Self::Network(IpNetwork::new(network.network(), network.prefix_len()).unwrap())
+    }
+}
+
+/// Parse address like `192.0.2.1` or network like `192.0.2.0/24`.
+impl TryFrom<&str> for IpQuery {
+    type Error = ClientError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.contains('/') {
+            value
+                .parse()
+                .map(Self::Network)
+                .map_err(|e| ClientError::InvalidQuery(Box::new(e)))
+        } else {
+            value
+                .parse()
+                .map(Self::Address)
+                .map_err(|e| ClientError::InvalidQuery(Box::new(e)))
+        }
+    }
+}
+
+/// Object to find in bootstrap and query, used by `Client::lookup_many`.
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+pub enum Lookup {
+    Domain(String),
+    Ip(IpAddr),
+    IpNetwork(IpNetwork),
+    Asn(u32),
+    Entity(String),
+    Nameserver(String),
+}
+
+impl Lookup {
+    /// Returns base URLs of RDAP servers from bootstrap that are authoritative for this query.
+    fn servers<'a>(&self, bootstrap: &'a bootstrap::Bootstrap) -> &'a [String] {
+        let servers = match self {
+            Self::Domain(domain) => match bootstrap::reverse_network(domain) {
+                Some(network) => bootstrap.ip.find_network(network),
+                None => bootstrap.dns.find(domain),
+            },
+            Self::Ip(ip) => bootstrap.ip.find(*ip),
+            Self::IpNetwork(network) => bootstrap.ip.find_network(*network),
+            Self::Asn(asn) => bootstrap.asn.find(*asn),
+            Self::Entity(entity) => bootstrap.object_tags.find(entity),
+            // Nameserver is queried at registry of its domain.
+            Self::Nameserver(host) => bootstrap.dns.find(host),
+        };
+        servers.map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns true for lookups of IP addresses and AS numbers, that are delegated by RIRs.
+    fn is_number_resource(&self) -> bool {
+        match self {
+            Self::Domain(domain) => bootstrap::reverse_network(domain).is_some(),
+            Self::Ip(_) | Self::IpNetwork(_) | Self::Asn(_) => true,
+            Self::Entity(_) | Self::Nameserver(_) => false,
+        }
+    }
+
+    /// Returns path of query relative to server base URL. Domain names are converted to A-labels
+    /// as recommended by RFC 9082 and arguments are percent encoded.
+    fn path(&self) -> String {
+        let (mut path, argument) = match self {
+            Self::Domain(domain) => ("domain/".to_owned(), Self::ascii_name(domain)),
+            Self::Nameserver(host) => ("nameserver/".to_owned(), Self::ascii_name(host)),
+            Self::Ip(ip) => return format!("ip/{}", ip),
+            Self::IpNetwork(network) => {
+                return format!("ip/{}/{}", network.network_address(), network.netmask())
+            }
+            Self::Asn(asn) => return format!("autnum/{}", asn),
+            Self::Entity(entity) => ("entity/".to_owned(), entity.clone()),
+        };
+        encode_segment(&argument, &mut path);
+        path
+    }
+
+    /// Convert domain name to A-labels, invalid name is kept as it is.
+    fn ascii_name(name: &str) -> String {
+        let name = name.trim_end_matches('.');
+        idna::domain_to_ascii(name).unwrap_or_else(|_| name.to_owned())
+    }
+}
+
+/// Detect kind of query: IP address (`192.0.2.1`), network (`2001:db8::/32`), AS number (`AS1234`
+/// or just `1234`), entity handle with object tag (`ABC123-ARIN`) or domain name.
+impl TryFrom<&str> for Lookup {
+    type Error = ClientError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(ClientError::InvalidQuery("empty query".into()));
+        }
+        if let Ok(query) = IpQuery::try_from(value) {
+            return Ok(match query {
+                IpQuery::Address(address) => Self::Ip(address),
+                IpQuery::Network(network) => Self::IpNetwork(network),
+            });
+        }
+        if value.contains('/') {
+            return Err(ClientError::InvalidQuery(
+                format!("invalid IP network {}", value).into(),
+            ));
+        }
+        if let Some(asn) = parser::parse_asn(value) {
+            return Ok(Self::Asn(asn));
+        }
+        // Handle ends with tag after hyphen and unlike domain names doesn't contain dots.
+        let is_handle = !value.contains('.')
+            && !value
+                .get(..4)
+                .map_or(false, |prefix| prefix.eq_ignore_ascii_case("xn--"))
+            && value
+                .rsplit_once('-')
+                .map_or(false, |(name, tag)| !name.is_empty() && !tag.is_empty());
+        if is_handle {
+            Ok(Self::Entity(value.to_owned()))
+        } else {
+            Ok(Self::Domain(value.to_owned()))
+        }
+    }
+}
+
+/// Percent encode everything except unreserved characters, so value can be used as one URL path
+/// segment or file name.
+pub(crate) fn encode_segment(value: &str, out: &mut String) {
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
 }
 
-/// Error enum returned by Client requests.
+/// Error enum returned by Client requests. All variants are declared for every target and set of
+/// features, but some of them are returned only on some targets or with some features, as noted
+/// in their docs. New variants can be added, so matches must have a wildcard arm.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum ClientError {
     /// Error caused by reqwest client (for example timeout, not exists dns record etc.)
     Reqwest(reqwest::Error),
@@ -94,7 +650,51 @@ pub enum ClientError {
     /// Error during converting JSON to RDAP structures.
     JsonDecode(Box<reqwest::Response>, serde_json::error::Error),
     /// Server error response as RDAP error message.
-    Rdap(Box<reqwest::Url>, parser::Error),
+    Rdap(Box<reqwest::Url>, Box<parser::Error>),
+    /// Error during fetching or parsing bootstrap from IANA.
+    Bootstrap(Box<dyn std::error::Error + Send + Sync>),
+    /// Bootstrap doesn't contain any RDAP server for given query.
+    NoServer,
+    /// Object doesn't exist, server returned 404 status code. Returned by `Client::entity`,
+    /// other methods return `Rdap` or `Server` error.
+    NotFound(Box<reqwest::Url>),
+    /// Query argument is not valid, for example IP network string given to `Client::ip`.
+    InvalidQuery(Box<dyn std::error::Error + Send + Sync>),
+    /// Whole operation took longer than deadline set by `ClientBuilder::deadline`.
+    DeadlineExceeded,
+    /// Operation was aborted by cancellation token set by `Client::with_cancellation`.
+    Cancelled,
+    /// Request to plaintext `http` URL was refused because of `SchemePolicy::HttpsOnly`.
+    InsecureUrl(Box<reqwest::Url>),
+    /// Server returned 429 status code, `retry_after` is parsed from `Retry-After` header.
+    RateLimited { retry_after: Option<Duration> },
+    /// Server redirected more times than allowed by `ClientBuilder::max_redirects`. Contains
+    /// already followed URLs.
+    TooManyRedirects(Vec<reqwest::Url>),
+    /// Server returned successful response, but its content type is not RDAP JSON (for example
+    /// HTML or plain text page). Body of response is not read.
+    UnexpectedContentType(Box<reqwest::Response>),
+    /// Bootstrap lists more RDAP servers for query and all of them failed with timeout,
+    /// connection error or 5xx status code. Contains base URLs of servers with their errors, in
+    /// order in which they were tried.
+    AllServersFailed(Vec<(String, ClientError)>),
+    /// Response body is larger than limit set by `ClientBuilder::max_response_size`. Download of
+    /// body was aborted.
+    ResponseTooLarge { url: Box<reqwest::Url>, limit: u64 },
+    /// Cassette set by `ClientBuilder::cassette` doesn't contain response for request, or
+    /// recorded response couldn't be written to cassette file. Not returned on wasm32.
+    Cassette(Box<dyn std::error::Error + Send + Sync>),
+    /// DNS query of `DnsChecker` failed. Contains `hickory_resolver::error::ResolveError`, returned
+    /// only with `dns` feature.
+    Dns(Box<dyn std::error::Error + Send + Sync>),
+    /// Response body is empty (`error` is `None`) or it is not valid JSON. Used instead of
+    /// `Server` and `JsonDecode` on wasm32, where response is consumed by reading its body. Returned
+    /// only on wasm32.
+    InvalidBody {
+        url: Box<reqwest::Url>,
+        status: reqwest::StatusCode,
+        error: Option<serde_json::Error>,
+    },
 }
 
 impl From<reqwest::Error> for ClientError {
@@ -105,6 +705,17 @@ impl From<reqwest::Error> for ClientError {
 
 const RDAP_CONTENT_TYPES: [&str; 2] = ["application/rdap+json", "application/json"];
 
+// RFC 7480 requires `application/rdap+json`, but plain JSON is accepted with lower preference.
+const ACCEPT_HEADER: &str = "application/rdap+json, application/json;q=0.9";
+
+/// Returns true if media type (without parameters like charset) is one of `RDAP_CONTENT_TYPES`.
+fn is_rdap_content_type(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    RDAP_CONTENT_TYPES
+        .iter()
+        .any(|rdap_type| media_type.eq_ignore_ascii_case(rdap_type))
+}
+
 fn is_rdap_response(response: &reqwest::Response) -> bool {
     if let Some(content_length) = response.content_length() {
         if content_length == 0 {
@@ -114,320 +725,4047 @@ fn is_rdap_response(response: &reqwest::Response) -> bool {
 
     if let Some(content_type) = response.headers().get(header::CONTENT_TYPE) {
         if let Ok(content_type_str) = content_type.to_str() {
-            return RDAP_CONTENT_TYPES
-                .iter()
-                .any(|rdap_type| content_type_str.contains(rdap_type));
+            return is_rdap_content_type(content_type_str);
         }
     }
 
     false
 }
 
-/// RDAP client.
-#[derive(Default)]
-pub struct Client {
-    client: reqwest::Client,
+/// Returns true if response declares content type that is not RDAP. Responses without content
+/// type are accepted, because some servers don't send it.
+fn is_unexpected_content_type(response: &reqwest::Response) -> bool {
+    match response.headers().get(header::CONTENT_TYPE) {
+        Some(content_type) => !content_type
+            .to_str()
+            .map(is_rdap_content_type)
+            .unwrap_or(false),
+        None => false,
+    }
 }
 
-impl Client {
-    /// Creates new `Client` with with default configuration.
-    pub fn new() -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(10))
-            .build()
-            .unwrap();
-
-        Self::with_reqwest_client(client)
+impl ClientError {
+    /// Convert error for 404 response to `NotFound`.
+    fn into_not_found(self) -> Self {
+        match self {
+            Self::Server(response) if response.status() == reqwest::StatusCode::NOT_FOUND => {
+                Self::NotFound(Box::new(response.url().clone()))
+            }
+            Self::Rdap(url, error) if error.error_code() == 404 => Self::NotFound(url),
+            error => error,
+        }
     }
+}
 
-    /// Creates new `Client` with given [reqwest](https://docs.rs/reqwest/) client.
-    pub fn with_reqwest_client(client: reqwest::Client) -> Self {
-        Self { client }
+impl From<Infallible> for ClientError {
+    fn from(e: Infallible) -> Self {
+        match e {}
     }
+}
 
-    async fn get_bootstrap<T: DeserializeOwned>(
-        &self,
-        url: &str,
-    ) -> Result<parser::Bootstrap<T>, reqwest::Error> {
-        self.client.get(url).send().await?.json().await
-    }
+/// Parsed RDAP response together with information how it was obtained.
+#[derive(Debug)]
+pub struct Response<T> {
+    /// Parsed RDAP object.
+    pub object: T,
+    /// Authoritative URL that returned the response, after following all redirects.
+    pub url: reqwest::Url,
+    /// URLs that returned redirect, in order in which they were requested.
+    pub redirects: Vec<reqwest::Url>,
+}
 
-    pub async fn fetch_bootstrap_asn(&self) -> Result<bootstrap::Asn, Box<dyn std::error::Error>> {
-        let bootstrap = self
-            .get_bootstrap("https://data.iana.org/rdap/asn.json")
-            .await?;
-        Ok(bootstrap::Asn::try_from(&bootstrap)?)
+impl<T> Response<T> {
+    /// Returns just parsed RDAP object.
+    pub fn into_object(self) -> T {
+        self.object
     }
+}
 
-    pub async fn fetch_bootstrap_dns(&self) -> Result<bootstrap::Dns, Box<dyn std::error::Error>> {
-        let bootstrap = self
-            .get_bootstrap("https://data.iana.org/rdap/dns.json")
-            .await?;
-        Ok(bootstrap::Dns::from(&bootstrap))
+impl<T> Deref for Response<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.object
     }
+}
 
-    pub async fn fetch_bootstrap_ip(&self) -> Result<bootstrap::Ip, Box<dyn std::error::Error>> {
-        let (parsed_ipv4, parsed_ipv6) = futures::join!(
-            self.get_bootstrap("https://data.iana.org/rdap/ipv4.json"),
-            self.get_bootstrap("https://data.iana.org/rdap/ipv6.json"),
-        );
-        Ok(bootstrap::Ip::try_from((&parsed_ipv4?, &parsed_ipv6?))?)
+/// Domain response of registry together with response of registrar, returned by
+/// `Client::lookup_domain_with_registrar`.
+#[derive(Debug)]
+pub struct DomainWithRegistrar {
+    /// Response of registry found in bootstrap.
+    pub registry: Response<parser::Domain>,
+    /// Response of registrar from `related` link of registry response, `None` when registry
+    /// response doesn't contain such link. Failed registrar query doesn't fail whole lookup, so
+    /// registry data are returned also when registrar server is unavailable.
+    pub registrar: Option<Result<Response<parser::Domain>, ClientError>>,
+}
+
+/// Returns link to RDAP response of the same domain at registrar, that is `related` link to
+/// RDAP `domain` path, which registries of thin gTLDs include in domain response.
+fn registrar_link(domain: &parser::Domain) -> Option<&parser::Link> {
+    domain.links.iter().flatten().find(|link| {
+        link.rel.as_deref() == Some("related")
+            && link.r#type.as_deref().map_or(true, is_rdap_content_type)
+            && link.href.contains("/domain/")
+    })
+}
+
+/// One page of domain search results, used by `Client::search_domains_stream`.
+#[derive(serde::Deserialize)]
+struct DomainSearchPage {
+    #[serde(rename = "domainSearchResults", default)]
+    results: Vec<parser::Domain>,
+    paging_metadata: Option<parser::PagingMetadata>,
+}
+
+/// Request of the next page of search results.
+enum NextPage {
+    /// Same search with cursor from `next` link.
+    Cursor(SearchParams),
+    /// `next` link without cursor, that is fetched as is.
+    Url(String),
+}
+
+/// Time until which servers should not be queried, shared between lookups in one batch.
+#[derive(Default)]
+struct RateLimits(futures::lock::Mutex<HashMap<String, Instant>>);
+
+impl RateLimits {
+    async fn wait(&self, server: &str) {
+        let until = self.0.lock().await.get(server).copied();
+        if let Some(until) = until {
+            rt::sleep(until.saturating_duration_since(Instant::now())).await;
+        }
     }
 
-    pub async fn fetch_bootstrap_object_tags(
-        &self,
-    ) -> Result<bootstrap::ObjectTags, Box<dyn std::error::Error>> {
-        let bootstrap = self
-            .get_bootstrap("https://data.iana.org/rdap/object-tags.json")
-            .await?;
-        Ok(bootstrap::ObjectTags::from(&bootstrap))
+    async fn block(&self, server: &str, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut limits = self.0.lock().await;
+        let entry = limits.entry(server.to_string()).or_insert(until);
+        *entry = (*entry).max(until);
     }
+}
 
-    /// Fetch bootstrap from IANA for ASN, IPv4 and IPV6, domains (DNS) and object tags.
-    pub async fn fetch_bootstrap(
-        &self,
-    ) -> Result<bootstrap::Bootstrap, Box<dyn std::error::Error>> {
-        let (asn, dns, ip, object_tags) = futures::join!(
-            self.fetch_bootstrap_asn(),
-            self.fetch_bootstrap_dns(),
-            self.fetch_bootstrap_ip(),
-            self.fetch_bootstrap_object_tags(),
-        );
+/// How many times is one lookup in batch sent when server returns 429 status code.
+const MAX_RATE_LIMITED_ATTEMPTS: u32 = 3;
 
-        Ok(bootstrap::Bootstrap {
-            ip: ip?,
-            dns: dns?,
-            asn: asn?,
-            object_tags: object_tags?,
-        })
-    }
+/// Bootstrap cached by client, with time when it should be fetched again.
+struct CachedBootstrap {
+    bootstrap: Arc<bootstrap::Bootstrap>,
+    refresh_at: Option<Instant>,
+}
 
-    async fn parse_response<T: DeserializeOwned>(
-        mut response: reqwest::Response,
-    ) -> Result<T, ClientError> {
-        // Preallocate buffer if content length is known, but 8 KB maximum.
-        let mut buf = if let Some(content_length) = response.content_length() {
-            BytesMut::with_capacity(8192.min(content_length) as usize)
-        } else {
-            BytesMut::new()
-        };
+/// How long to wait before fetching expired bootstrap again, when fetching failed.
+const BOOTSTRAP_RETRY_DELAY: Duration = Duration::from_secs(60);
 
-        while let Some(chunk) = response.chunk().await? {
-            buf.extend(chunk);
-        }
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+const DEFAULT_USER_AGENT: &str = concat!("rdap_client/", env!("CARGO_PKG_VERSION"));
 
-        // Server returns empty response, doesnt make sense to try parse as JSON.
-        if buf.is_empty() {
-            return Err(ClientError::Server(Box::new(response)));
-        }
+/// Builder for `Client` with custom configuration.
+#[derive(Debug)]
+pub struct ClientBuilder {
+    builder: reqwest::ClientBuilder,
+    error: Option<reqwest::Error>,
+    deadline: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    max_redirects: usize,
+    max_response_size: Option<u64>,
+    headers: header::HeaderMap,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    cache: Option<ResponseCache>,
+    fair_queue: Option<FairQueue>,
+    bootstrap_url: String,
+    bootstrap_ttl: Option<Duration>,
+    bootstrap_overrides: Option<bootstrap::Overrides>,
+    scheme_policy: SchemePolicy,
+    parse_options: parser::ParseOptions,
+    delegated_stats_url: Option<String>,
+    #[cfg(not(target_arch = "wasm32"))]
+    bootstrap_cache: Option<bootstrap_cache::BootstrapCache>,
+    credentials: CredentialStore,
+    wire_debug: Option<WireDebug>,
+    #[cfg(not(target_arch = "wasm32"))]
+    replay: Option<std::path::PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    cassette: Option<Cassette>,
+}
 
-        serde_json::from_slice(&buf.freeze())
-            .map_err(|e| ClientError::JsonDecode(Box::new(response), e))
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        Self::new()
     }
+}
 
-    async fn handle_response<T: DeserializeOwned>(
-        response: reqwest::Response,
-    ) -> Result<T, ClientError> {
-        if response.status() == reqwest::StatusCode::OK {
-            Self::parse_response(response).await
-        } else if is_rdap_response(&response) {
-            Err(ClientError::Rdap(
-                Box::new(response.url().clone()),
-                Self::parse_response(response).await?,
-            ))
-        } else {
-            Err(ClientError::Server(Box::new(response)))
+impl ClientBuilder {
+    /// Creates new `ClientBuilder` with default configuration (the same as `Client::new()` uses).
+    ///
+    /// Proxies from `HTTP_PROXY`, `HTTPS_PROXY`, `ALL_PROXY` and `NO_PROXY` environment
+    /// variables are used by default, unless `proxy`, `custom_proxy` or `no_proxy` is called.
+    pub fn new() -> Self {
+        Self {
+            builder: Self::default_reqwest_builder(),
+            error: None,
+            deadline: None,
+            retry_policy: None,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            max_response_size: None,
+            headers: header::HeaderMap::new(),
+            middlewares: Vec::new(),
+            metrics_sink: None,
+            cache: None,
+            fair_queue: None,
+            bootstrap_url: bootstrap::IANA_BOOTSTRAP_URL.to_string(),
+            bootstrap_ttl: None,
+            bootstrap_overrides: None,
+            scheme_policy: SchemePolicy::default(),
+            parse_options: parser::ParseOptions::default(),
+            delegated_stats_url: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            bootstrap_cache: None,
+            credentials: CredentialStore::default(),
+            wire_debug: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            replay: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            cassette: None,
         }
     }
 
-    fn construct_headers() -> header::HeaderMap {
-        let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::ACCEPT,
-            header::HeaderValue::from_str(&RDAP_CONTENT_TYPES.join(", "))
-                .expect("error during converting header value"), // Should never fail
-        );
-        headers
+    /// Value of `User-Agent` header sent with every request. Default value is
+    /// `rdap_client/<version>`.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.builder = self.builder.user_agent(user_agent);
+        self
     }
 
-    async fn get<T: DeserializeOwned, I: IntoUrl>(&self, url: I) -> Result<T, ClientError> {
-        self.client
-            .get(url)
-            .headers(Self::construct_headers())
-            .send()
-            .await
-            .map(Self::handle_response)?
-            .await
+    /// Add header that will be sent with every request. Can be called multiple times.
+    pub fn header(mut self, name: header::HeaderName, value: header::HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
     }
 
-    async fn get_with_query<T: DeserializeOwned, I: IntoUrl, Q: Serialize>(
-        &self,
-        url: I,
-        query: &Q,
-    ) -> Result<T, ClientError> {
-        self.client
-            .get(url)
-            .query(query)
-            .headers(Self::construct_headers())
-            .send()
-            .await
-            .map(Self::handle_response)?
-            .await
+    /// Add headers that will be sent with every request.
+    pub fn headers(mut self, headers: header::HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
     }
 
-    /// Query given RDAP server for IPv4 or IPv6 address.
-    pub async fn query_ip<I: Into<IpAddr>>(
-        &self,
-        server: &str,
-        ip: I,
-    ) -> Result<parser::IpNetwork, ClientError> {
-        let url = format!("{}ip/{}", server, ip.into());
-        self.get(&url).await
+    /// Add middleware that can inspect or modify requests and responses. Middlewares are called
+    /// in order in which they were added.
+    pub fn middleware<M: Middleware>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
     }
 
-    /// Query given RDAP server for IP network.
-    pub async fn query_ip_network<I: Into<IpNetwork>>(
-        &self,
-        server: &str,
-        ip_network: I,
-    ) -> Result<parser::IpNetwork, ClientError> {
-        let ip_network = ip_network.into();
-        let url = format!(
-            "{}ip/{}/{}",
-            server,
-            ip_network.network_address(),
-            ip_network.netmask()
-        );
-        self.get(&url).await
+    /// Send given credentials in `Authorization` header with every request to `host`, including
+    /// requests redirected from other hosts. Credentials are never sent to other hosts.
+    pub fn credentials(mut self, host: &str, credentials: Credentials) -> Self {
+        self.credentials.insert(host, credentials);
+        self
     }
 
-    /// Query given RDAP server for AS number.
-    pub async fn query_asn(&self, server: &str, asn: u32) -> Result<parser::AutNum, ClientError> {
-        let url = format!("{}autnum/{}", server, asn);
-        self.get(&url).await
+    /// Write every request and response to standard error, similar to `curl -v`: request line,
+    /// status line, headers and first `max_body_bytes` of response body. Values of
+    /// `Authorization`, `Cookie` and other sensitive headers are redacted. Intended for
+    /// diagnosing why server returns unparsable output, streamed search results are not written.
+    pub fn wire_debug(mut self, max_body_bytes: usize) -> Self {
+        self.wire_debug = Some(WireDebug { max_body_bytes });
+        self
     }
 
-    /// Query given RDAP server for nameserver handle.
-    pub async fn query_nameserver(
-        &self,
-        server: &str,
-        nameserver: &str,
-    ) -> Result<parser::Nameserver, ClientError> {
-        let url = format!("{}nameserver/{}", server, nameserver);
-        self.get(&url).await
+    /// Offline mode, every response is read from previously saved file in `dir` instead of sending
+    /// request to server. File name is given by query type and argument, independent of server,
+    /// for example `domain/example.com.json`, `ip/192.0.2.0%2F24.json`, `autnum/15169.json` or
+    /// `domains/name=exam%2A.cz.json` for search. When file doesn't exist, `ClientError::Server`
+    /// with 404 status code is returned.
+    ///
+    /// Bootstrap is not replayed, so set it by `Client::set_bootstrap` before calling `lookup_*`
+    /// methods. Other middlewares are called before replay.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn replay<P: Into<std::path::PathBuf>>(mut self, dir: P) -> Self {
+        self.replay = Some(dir.into());
+        self
     }
 
-    /// Query given RDAP server for domain by name.
-    pub async fn query_domain(
-        &self,
-        server: &str,
-        domain: &str,
-    ) -> Result<parser::Domain, ClientError> {
-        let url = format!("{}domain/{}", server, domain);
-        self.get(&url).await
+    /// Record responses to cassette file or replay them from it, depending on `CassetteMode`.
+    /// Unlike `replay`, cassette keeps status code and headers of responses and matches requests
+    /// also by server host, so redirects are replayed too.
+    ///
+    /// Bootstrap is not recorded, so set it by `Client::set_bootstrap` before calling `lookup_*`
+    /// methods. Other middlewares are called before cassette.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn cassette(mut self, cassette: Cassette) -> Self {
+        self.cassette = Some(cassette);
+        self
     }
 
-    pub async fn query_reverse_domain<I: Into<IpAddr>>(
-        &self,
-        server: &str,
-        ip: I,
-    ) -> Result<parser::Domain, ClientError> {
-        let domain = match ip.into() {
-            IpAddr::V4(ipv4) => {
-                let octets = ipv4.octets();
-                format!(
-                    "{}.{}.{}.{}.in-addr.arpa",
-                    octets[3], octets[2], octets[1], octets[0]
-                )
+    /// Base URL from which bootstrap registries (`dns.json`, `ipv4.json` etc.) are fetched, for
+    /// example internal mirror. Default is `bootstrap::IANA_BOOTSTRAP_URL`.
+    pub fn bootstrap_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.bootstrap_url = url.into();
+        self
+    }
+
+    /// How long is fetched bootstrap used, before it is fetched again by the next `lookup_*`
+    /// call. When fetching fails, expired bootstrap is still used and fetching is tried again
+    /// later. By default, bootstrap is fetched just once. See also
+    /// `Client::bootstrap_refresh_task` for refreshing outside of lookups.
+    pub fn bootstrap_ttl(mut self, ttl: Duration) -> Self {
+        self.bootstrap_ttl = Some(ttl);
+        self
+    }
+
+    /// How are `http` and `https` servers from bootstrap used. Default is
+    /// `SchemePolicy::PreferHttps`.
+    pub fn scheme_policy(mut self, scheme_policy: SchemePolicy) -> Self {
+        self.scheme_policy = scheme_policy;
+        self
+    }
+
+    /// Tolerances for responses that are not compliant with RFC 9083. Default is
+    /// `ParseOptions::lenient()`, use `ParseOptions::strict()` for testing of RDAP servers.
+    ///
+    /// Options apply to responses parsed by client. Bodies parsed later by plain `serde_json`,
+    /// for example original JSON of [`Raw`], are parsed with lenient options.
+    pub fn parse_options(mut self, parse_options: parser::ParseOptions) -> Self {
+        self.parse_options = parse_options;
+        self
+    }
+
+    /// Custom RDAP servers used by `lookup_*` methods instead of servers from bootstrap. Overrides
+    /// are kept when bootstrap is refreshed.
+    pub fn bootstrap_overrides(mut self, overrides: bootstrap::Overrides) -> Self {
+        self.bootstrap_overrides = Some(overrides);
+        self
+    }
+
+    /// When IP address or AS number is not covered by IANA bootstrap (for example legacy or ERX
+    /// space), find RIR that delegated it in extended delegated stats downloaded from `url`,
+    /// usually `bootstrap::NRO_DELEGATED_STATS_URL`, and query RDAP server of that RIR. Stats
+    /// are downloaded once, at the first lookup that needs them.
+    pub fn delegated_stats_url<S: Into<String>>(mut self, url: S) -> Self {
+        self.delegated_stats_url = Some(url.into());
+        self
+    }
+
+    /// Store downloaded bootstrap registries in `dir`, so they can be used by next processes.
+    /// Cached registry younger than `bootstrap_ttl` is used without any request, older one is
+    /// revalidated using `ETag` and `Last-Modified` headers. Corrupted cache is ignored and
+    /// overwritten by downloaded registry.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn bootstrap_cache_dir<P: Into<std::path::PathBuf>>(mut self, dir: P) -> Self {
+        self.bootstrap_cache = Some(bootstrap_cache::BootstrapCache { dir: dir.into() });
+        self
+    }
+
+    /// Use cache for responses that can be revalidated using `ETag` or `Last-Modified` headers.
+    pub fn cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Limit number of concurrent requests by given queue, that interleaves waiting requests to
+    /// different servers. Useful for bulk lookups, where one slow server would otherwise occupy
+    /// all slots. Pass clone of one queue to more clients to share the limit.
+    pub fn fair_queue(mut self, fair_queue: FairQueue) -> Self {
+        self.fair_queue = Some(fair_queue);
+        self
+    }
+
+    /// Set sink that receives metrics about every request made by client.
+    pub fn metrics_sink<M: MetricsSink>(mut self, metrics_sink: M) -> Self {
+        self.metrics_sink = Some(Arc::new(metrics_sink));
+        self
+    }
+
+    /// Add middleware that calls given closure to modify every outgoing request.
+    pub fn map_request<F>(self, f: F) -> Self
+    where
+        F: Fn(&mut reqwest::Request) + Send + Sync + 'static,
+    {
+        self.middleware(middleware::MapRequest(f))
+    }
+
+    /// Add middleware that calls given closure with every incoming response.
+    pub fn inspect_response<F>(self, f: F) -> Self
+    where
+        F: Fn(&reqwest::Response) + Send + Sync + 'static,
+    {
+        self.middleware(middleware::InspectResponse(f))
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn default_reqwest_builder() -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .user_agent(DEFAULT_USER_AGENT)
+            // Redirects are followed by `Client` itself, so it can keep redirect chain.
+            .redirect(reqwest::redirect::Policy::none())
+    }
+
+    /// Browser follows redirects itself and doesn't support timeouts, use `deadline` instead.
+    #[cfg(target_arch = "wasm32")]
+    fn default_reqwest_builder() -> reqwest::ClientBuilder {
+        reqwest::Client::builder().user_agent(DEFAULT_USER_AGENT)
+    }
+
+    /// Timeout for one HTTP request, from start connecting until the response body has finished.
+    /// Default value is 10 seconds. Not available on wasm32, use `deadline` instead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.builder = self.builder.timeout(timeout);
+        self
+    }
+
+    /// Deadline for whole operation, including bootstrap fetching, redirects and retries.
+    /// When exceeded, `ClientError::DeadlineExceeded` is returned. By default, there is no deadline.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Maximum number of followed redirects for one query. Default value is 10.
+    pub fn max_redirects(mut self, max_redirects: usize) -> Self {
+        self.max_redirects = max_redirects;
+        self
+    }
+
+    /// Maximum size of response body in bytes. When exceeded, download is aborted and
+    /// `ClientError::ResponseTooLarge` is returned. For streamed search results, limit applies
+    /// to the whole response. By default, size is not limited.
+    pub fn max_response_size(mut self, max_size: u64) -> Self {
+        self.max_response_size = Some(max_size);
+        self
+    }
+
+    /// Retry failed requests according to given policy. By default, requests are not retried.
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
+    /// Send all requests through proxy with given URL. Supported schemes are `http`, `https` and
+    /// `socks5` or `socks5h` (requires `socks` feature). Hosts listed in `NO_PROXY` environment
+    /// variable are still accessed directly.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn proxy<U: IntoUrl>(self, url: U) -> Self {
+        match reqwest::Proxy::all(url) {
+            Ok(proxy) => self.custom_proxy(proxy.no_proxy(reqwest::NoProxy::from_env())),
+            Err(error) => self.with_error(error),
+        }
+    }
+
+    /// Add custom proxy, for example proxy used just for given scheme
+    /// (see [`reqwest::Proxy::https`](https://docs.rs/reqwest/latest/reqwest/struct.Proxy.html)).
+    /// Can be called multiple times.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn custom_proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.builder = self.builder.proxy(proxy);
+        self
+    }
+
+    /// Disable all proxies, including proxies set by environment variables.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn no_proxy(mut self) -> Self {
+        self.builder = self.builder.no_proxy();
+        self
+    }
+
+    /// Timeout for idle connections kept in the pool, `None` keeps them open until the server
+    /// closes them. Default value is 90 seconds.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.builder = self.builder.pool_idle_timeout(timeout);
+        self
+    }
+
+    /// Maximum number of idle connections kept in the pool for one host. By default, there is no
+    /// limit.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.builder = self.builder.pool_max_idle_per_host(max);
+        self
+    }
+
+    /// Interval of TCP keepalive probes for open connections, `None` disables them. By default,
+    /// TCP keepalive is disabled.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tcp_keepalive(mut self, interval: Option<Duration>) -> Self {
+        self.builder = self.builder.tcp_keepalive(interval);
+        self
+    }
+
+    /// Disable compression, so `Accept-Encoding` header is not sent and server returns
+    /// uncompressed responses. By default, gzip and brotli compressed responses are accepted and
+    /// transparently decompressed. Not available on wasm32, where browser handles compression.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn no_compression(mut self) -> Self {
+        self.builder = self.builder.no_gzip().no_brotli();
+        self
+    }
+
+    /// Disable Nagle's algorithm, so small requests are sent immediately. By default, Nagle's
+    /// algorithm is enabled.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.builder = self.builder.tcp_nodelay(enabled);
+        self
+    }
+
+    /// Use HTTP/2 without negotiation, also for plain HTTP servers. Servers that don't support
+    /// HTTP/2 will fail. Without this option, HTTP/2 is used for HTTPS servers that support it,
+    /// so more requests can be multiplexed over one connection.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_prior_knowledge(mut self) -> Self {
+        self.builder = self.builder.http2_prior_knowledge();
+        self
+    }
+
+    /// Interval of HTTP/2 PING frames that keep connection alive, `None` disables them. By
+    /// default, PING frames are not sent.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_keep_alive_interval(mut self, interval: Option<Duration>) -> Self {
+        self.builder = self.builder.http2_keep_alive_interval(interval);
+        self
+    }
+
+    /// Timeout for receiving acknowledgement of HTTP/2 PING frame, connection is closed when
+    /// exceeded. Default value is 20 seconds.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_keep_alive_timeout(mut self, timeout: Duration) -> Self {
+        self.builder = self.builder.http2_keep_alive_timeout(timeout);
+        self
+    }
+
+    /// Send HTTP/2 PING frames also when there are no in-flight requests. By default, PING
+    /// frames are sent only with in-flight requests.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn http2_keep_alive_while_idle(mut self, enabled: bool) -> Self {
+        self.builder = self.builder.http2_keep_alive_while_idle(enabled);
+        self
+    }
+
+    /// Connect to given IP addresses instead of resolving `domain` by DNS, like `curl --resolve`.
+    /// Port from URL is used.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn resolve(mut self, domain: &str, addrs: &[IpAddr]) -> Self {
+        let addrs: Vec<_> = addrs
+            .iter()
+            .map(|addr| std::net::SocketAddr::new(*addr, 0))
+            .collect();
+        self.builder = self.builder.resolve_to_addrs(domain, &addrs);
+        self
+    }
+
+    /// Use custom DNS resolver. Addresses set by `resolve` take precedence.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn dns_resolver<R: reqwest::dns::Resolve + 'static>(mut self, resolver: Arc<R>) -> Self {
+        self.builder = self.builder.dns_resolver(resolver);
+        self
+    }
+
+    /// Connect to servers just over IPv4, IPv6 addresses of servers are ignored.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn ipv4_only(mut self) -> Self {
+        // Connector uses only addresses of the same family as bound local address.
+        self.builder = self
+            .builder
+            .local_address(IpAddr::from(std::net::Ipv4Addr::UNSPECIFIED));
+        self
+    }
+
+    /// Connect to servers just over IPv6, IPv4 addresses of servers are ignored.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn ipv6_only(mut self) -> Self {
+        self.builder = self
+            .builder
+            .local_address(IpAddr::from(std::net::Ipv6Addr::UNSPECIFIED));
+        self
+    }
+
+    /// Client certificate for mutual TLS authentication, required by some private RDAP servers.
+    #[cfg(all(
+        not(target_arch = "wasm32"),
+        any(feature = "rustls-tls", feature = "native-tls")
+    ))]
+    pub fn identity(mut self, identity: reqwest::Identity) -> Self {
+        self.builder = self.builder.identity(identity);
+        self
+    }
+
+    /// Client certificate for mutual TLS authentication from PEM that contains private key and
+    /// at least one certificate. Switches TLS backend to rustls.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "rustls-tls"))]
+    pub fn identity_pem(self, pem: &[u8]) -> Self {
+        match reqwest::Identity::from_pem(pem) {
+            Ok(identity) => {
+                let mut builder = self.identity(identity);
+                builder.builder = builder.builder.use_rustls_tls();
+                builder
             }
-            IpAddr::V6(ipv6) => {
-                let mut ret: [u8; 32] = Default::default();
-                for (i, byte) in ipv6.octets().iter().enumerate() {
-                    ret[i * 2] = byte >> 4;
-                    ret[i * 2 + 1] = byte & 0xf;
+            Err(e) => self.with_error(e),
+        }
+    }
+
+    /// Client certificate for mutual TLS authentication from DER encoded PKCS #12 archive
+    /// protected by `password`. Switches TLS backend to native TLS.
+    #[cfg(all(not(target_arch = "wasm32"), feature = "native-tls"))]
+    pub fn identity_pkcs12(self, der: &[u8], password: &str) -> Self {
+        match reqwest::Identity::from_pkcs12_der(der, password) {
+            Ok(identity) => {
+                let mut builder = self.identity(identity);
+                builder.builder = builder.builder.use_native_tls();
+                builder
+            }
+            Err(e) => self.with_error(e),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn with_error(mut self, error: reqwest::Error) -> Self {
+        // Keep just first error, that will be returned from `build` method.
+        self.error.get_or_insert(error);
+        self
+    }
+
+    /// Returns `Client` with given configuration.
+    pub fn build(self) -> Result<Client, reqwest::Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let mut client = Client::with_reqwest_client(self.builder.build()?);
+        client.deadline = self.deadline;
+        client.retry_policy = self.retry_policy;
+        client.max_redirects = self.max_redirects;
+        client.max_response_size = self.max_response_size;
+        client.headers = self.headers;
+        client.middlewares = self.middlewares;
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(dir) = self.replay {
+            // Replay has to be the last middleware, because it doesn't call next ones.
+            client.middlewares.push(Arc::new(replay::Replay { dir }));
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(cassette) = self.cassette {
+            // Cassette doesn't call next middlewares when replaying.
+            client.middlewares.push(Arc::new(cassette));
+        }
+        client.metrics_sink = self.metrics_sink;
+        client.cache = self.cache;
+        client.fair_queue = self.fair_queue;
+        client.bootstrap_url = self.bootstrap_url;
+        client.bootstrap_ttl = self.bootstrap_ttl;
+        client.bootstrap_overrides = self.bootstrap_overrides.map(Arc::new);
+        client.scheme_policy = self.scheme_policy;
+        client.parse_options = self.parse_options;
+        client.delegated_stats_url = self.delegated_stats_url;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            client.bootstrap_cache = self.bootstrap_cache;
+        }
+        client.credentials = Arc::new(self.credentials);
+        client.wire_debug = self.wire_debug;
+        Ok(client)
+    }
+}
+
+/// RDAP client.
+///
+/// Cloning `Client` is cheap, clones share connection pool and bootstrap.
+#[derive(Clone)]
+pub struct Client {
+    client: reqwest::Client,
+    bootstrap: Arc<futures::lock::Mutex<Option<CachedBootstrap>>>,
+    bootstrap_url: String,
+    bootstrap_ttl: Option<Duration>,
+    bootstrap_overrides: Option<Arc<bootstrap::Overrides>>,
+    scheme_policy: SchemePolicy,
+    parse_options: parser::ParseOptions,
+    delegated_stats_url: Option<String>,
+    delegated_stats: Arc<futures::lock::Mutex<Option<Arc<bootstrap::Bootstrap>>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    bootstrap_cache: Option<bootstrap_cache::BootstrapCache>,
+    deadline: Option<Duration>,
+    retry_policy: Option<RetryPolicy>,
+    max_redirects: usize,
+    max_response_size: Option<u64>,
+    headers: header::HeaderMap,
+    middlewares: Vec<Arc<dyn Middleware>>,
+    metrics_sink: Option<Arc<dyn MetricsSink>>,
+    cache: Option<ResponseCache>,
+    fair_queue: Option<FairQueue>,
+    cancellation: Option<CancellationToken>,
+    search_params: SearchParams,
+    session_cookie: Option<farv1::SessionCookie>,
+    credentials: Arc<CredentialStore>,
+    wire_debug: Option<WireDebug>,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    /// Creates new `Client` with with default configuration.
+    pub fn new() -> Self {
+        ClientBuilder::new().build().unwrap()
+    }
+
+    /// Creates `ClientBuilder` to configure a `Client`.
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Creates new `Client` with given [reqwest](https://docs.rs/reqwest/) client. Its user agent
+    /// and timeout are used instead of defaults of `ClientBuilder`.
+    ///
+    /// Redirects are followed by `Client`, so given client must be built with
+    /// `reqwest::redirect::Policy::none()`. Redirects followed by reqwest are reported just as
+    /// redirect from requested URL to final URL and headers are not removed when they lead to other
+    /// host.
+    pub fn with_reqwest_client(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            bootstrap: Default::default(),
+            bootstrap_url: bootstrap::IANA_BOOTSTRAP_URL.to_string(),
+            bootstrap_ttl: None,
+            bootstrap_overrides: None,
+            scheme_policy: SchemePolicy::default(),
+            parse_options: parser::ParseOptions::default(),
+            delegated_stats_url: None,
+            delegated_stats: Default::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            bootstrap_cache: None,
+            deadline: None,
+            retry_policy: None,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            max_response_size: None,
+            headers: header::HeaderMap::new(),
+            middlewares: Vec::new(),
+            metrics_sink: None,
+            cache: None,
+            fair_queue: None,
+            cancellation: None,
+            search_params: SearchParams::default(),
+            session_cookie: None,
+            credentials: Default::default(),
+            wire_debug: None,
+        }
+    }
+
+    /// Returns clone of this `Client` that sends also given headers, for example for one request:
+    /// `client.with_headers(headers).lookup_domain("example.com")`.
+    pub fn with_headers(&self, headers: header::HeaderMap) -> Self {
+        let mut client = self.clone();
+        client.headers.extend(headers);
+        client
+    }
+
+    /// Returns clone of this `Client` which operations have given deadline instead of one set by
+    /// `ClientBuilder::deadline`, for example for one call:
+    /// `client.with_deadline(Duration::from_secs(5)).domain("example.com")`. Deadline covers
+    /// bootstrap fetching, redirects and retries of each operation.
+    pub fn with_deadline(&self, deadline: Duration) -> Self {
+        let mut client = self.clone();
+        client.deadline = Some(deadline);
+        client
+    }
+
+    /// Returns clone of this `Client` which operations are aborted when given token is cancelled.
+    /// Operations return `ClientError::Cancelled` and in-flight requests are dropped, so their
+    /// connections are closed.
+    ///
+    /// All operations can be also cancelled just by dropping returned future, for example in
+    /// `select!` against shutdown signal.
+    pub fn with_cancellation(&self, token: CancellationToken) -> Self {
+        let mut client = self.clone();
+        client.cancellation = Some(token);
+        client
+    }
+
+    /// Returns clone of this `Client` that sends given sorting and paging parameters with every
+    /// search request, including reverse search.
+    pub fn with_search_params(&self, params: SearchParams) -> Self {
+        let mut client = self.clone();
+        client.search_params = params;
+        client
+    }
+
+    /// Set bootstrap that will be used by `lookup_*` methods instead of fetching it from IANA.
+    /// Set bootstrap doesn't expire, but it can be replaced by `refresh_bootstrap`.
+    pub async fn set_bootstrap(&self, bootstrap: bootstrap::Bootstrap) {
+        *self.bootstrap.lock().await = Some(CachedBootstrap {
+            bootstrap: Arc::new(bootstrap),
+            refresh_at: None,
+        });
+    }
+
+    /// Returns bootstrap used by `lookup_*` methods. When bootstrap was not set or fetched yet
+    /// or it is older than `ClientBuilder::bootstrap_ttl`, it is fetched from IANA and cached for
+    /// next calls.
+    ///
+    /// With `embedded-bootstrap` feature, embedded snapshot is used when bootstrap cannot be
+    /// fetched, until it is fetched successfully later.
+    #[cfg_attr(
+        all(feature = "embedded-bootstrap", not(feature = "tracing")),
+        allow(unused_variables)
+    )]
+    pub async fn bootstrap(&self) -> Result<Arc<bootstrap::Bootstrap>, ClientError> {
+        let mut cached = self.bootstrap.lock().await;
+        match cached.as_ref() {
+            Some(current) if current.refresh_at.map_or(true, |at| Instant::now() < at) => {
+                return Ok(Arc::clone(&current.bootstrap));
+            }
+            Some(_) => {
+                debug!("bootstrap expired, fetching from IANA");
+            }
+            None => {
+                debug!("bootstrap not cached, fetching from IANA");
+            }
+        }
+
+        match self.fetch_bootstrap().await {
+            Ok(fetched) => Ok(self.store_bootstrap(&mut cached, fetched)),
+            Err(e) => {
+                warn!(error = %e, "fetching bootstrap failed");
+                match cached.as_mut() {
+                    Some(stale) => {
+                        let delay = self
+                            .bootstrap_ttl
+                            .map_or(BOOTSTRAP_RETRY_DELAY, |ttl| ttl.min(BOOTSTRAP_RETRY_DELAY));
+                        stale.refresh_at = Some(Instant::now() + delay);
+                        Ok(Arc::clone(&stale.bootstrap))
+                    }
+                    #[cfg(feature = "embedded-bootstrap")]
+                    None => {
+                        debug!("using embedded bootstrap");
+                        let embedded = Arc::new(bootstrap::Bootstrap::embedded());
+                        *cached = Some(CachedBootstrap {
+                            bootstrap: Arc::clone(&embedded),
+                            refresh_at: Some(Instant::now() + BOOTSTRAP_RETRY_DELAY),
+                        });
+                        Ok(embedded)
+                    }
+                    #[cfg(not(feature = "embedded-bootstrap"))]
+                    None => Err(ClientError::Bootstrap(e)),
                 }
-                ret.reverse();
+            }
+        }
+    }
 
-                format!(
-                    "{}.ip6.arpa",
-                    ret.iter()
-                        .map(|n| n.to_string())
-                        .collect::<Vec<_>>()
-                        .join(".")
-                )
+    /// Fetch bootstrap from IANA and use it for next `lookup_*` calls. Lookups are not blocked
+    /// while bootstrap is fetched, new bootstrap replaces the current one at once. Fetched
+    /// bootstrap is ignored when it has older publication time than the current one. Returns
+    /// bootstrap that is used after refresh.
+    pub async fn refresh_bootstrap(&self) -> Result<Arc<bootstrap::Bootstrap>, ClientError> {
+        let fetched = self.fetch_bootstrap().await.map_err(|e| {
+            warn!(error = %e, "refreshing bootstrap failed");
+            ClientError::Bootstrap(e)
+        })?;
+        let mut cached = self.bootstrap.lock().await;
+        Ok(self.store_bootstrap(&mut cached, fetched))
+    }
+
+    /// Returns future that refreshes bootstrap every `interval` forever, for long-running
+    /// services. Spawn it on used runtime, for example `tokio::spawn(client.bootstrap_refresh_task(
+    /// Duration::from_secs(86400)))`. Failed refresh is logged and the current bootstrap is kept.
+    pub fn bootstrap_refresh_task(&self, interval: Duration) -> impl Future<Output = ()> {
+        let client = self.clone();
+        async move {
+            loop {
+                rt::sleep(interval).await;
+                // Error is already logged.
+                let _ = client.refresh_bootstrap().await;
             }
-        };
-        self.query_domain(server, &domain).await
+        }
     }
 
-    /// Query given RDAP server for entity by its name.
-    pub async fn query_entity(
+    /// Returns bootstrap for routing given lookup. That is bootstrap from IANA, or bootstrap built
+    /// from delegated stats set by `ClientBuilder::delegated_stats_url`, when lookup is for IP
+    /// address, AS number or reverse domain that is not covered by IANA bootstrap.
+    async fn lookup_bootstrap(
         &self,
-        server: &str,
-        entity: &str,
-    ) -> Result<parser::Entity, ClientError> {
-        let url = format!("{}entity/{}", server, entity);
-        self.get(&url).await
+        lookup: &Lookup,
+    ) -> Result<Arc<bootstrap::Bootstrap>, ClientError> {
+        let bootstrap = self.bootstrap().await?;
+        let url = match &self.delegated_stats_url {
+            Some(url) if lookup.is_number_resource() => url,
+            _ => return Ok(bootstrap),
+        };
+        let overridden = self
+            .bootstrap_overrides
+            .as_ref()
+            .map_or(false, |overrides| !lookup.servers(&overrides.0).is_empty());
+        if overridden || !lookup.servers(&bootstrap).is_empty() {
+            return Ok(bootstrap);
+        }
+
+        debug!("no server in IANA bootstrap, using delegated stats");
+        let mut delegated = self.delegated_stats.lock().await;
+        if let Some(delegated) = delegated.as_ref() {
+            return Ok(Arc::clone(delegated));
+        }
+        let fetched = self.fetch_delegated_stats(url).await.map_err(|e| {
+            warn!(error = %e, "fetching delegated stats failed");
+            ClientError::Bootstrap(e)
+        })?;
+        let fetched = Arc::new(fetched);
+        *delegated = Some(Arc::clone(&fetched));
+        Ok(fetched)
     }
 
-    /// Search given RDAP server for nameserver by name or IP address.
-    pub async fn search_nameserver(
+    async fn fetch_delegated_stats(
         &self,
-        server: &str,
-        search_nameserver: SearchNameserver,
-    ) -> Result<parser::NameserverSearchResults, ClientError> {
-        let url = &format!("{}nameservers", server);
-        self.get_with_query(url, &[(search_nameserver.key(), search_nameserver.value())])
-            .await
+        url: &str,
+    ) -> Result<bootstrap::Bootstrap, Box<dyn std::error::Error + Send + Sync>> {
+        let stats = self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        bootstrap::Bootstrap::from_delegated_stats(&stats)
     }
 
-    /// Search given RDAP server for domain by name, NS LDH name or NS IP address.
-    pub async fn search_domain(
+    fn store_bootstrap(
         &self,
-        server: &str,
-        search_domain: SearchDomain,
-    ) -> Result<parser::DomainSearchResults, ClientError> {
-        let url = format!("{}domains", server);
-        self.get_with_query(&url, &[(search_domain.key(), search_domain.value())])
-            .await
+        cached: &mut Option<CachedBootstrap>,
+        fetched: bootstrap::Bootstrap,
+    ) -> Arc<bootstrap::Bootstrap> {
+        let refresh_at = self.bootstrap_ttl.map(|ttl| Instant::now() + ttl);
+        match cached {
+            Some(current) if current.bootstrap.publication > fetched.publication => {
+                debug!("fetched bootstrap is older than current one, ignoring it");
+                current.refresh_at = refresh_at;
+                Arc::clone(&current.bootstrap)
+            }
+            _ => {
+                let fetched = Arc::new(fetched);
+                *cached = Some(CachedBootstrap {
+                    bootstrap: Arc::clone(&fetched),
+                    refresh_at,
+                });
+                fetched
+            }
+        }
     }
 
-    /// Search given RDAP server for domain by FN or handle.
-    pub async fn search_entity(
+    /// Download and parse one bootstrap registry.
+    async fn get_bootstrap<T: DeserializeOwned>(
         &self,
-        server: &str,
-        search_entity: SearchEntity,
-    ) -> Result<parser::EntitySearchResults, ClientError> {
-        let url = format!("{}entities", server);
-        self.get_with_query(&url, &[(search_entity.key(), search_entity.value())])
-            .await
+        file: &str,
+    ) -> Result<parser::Bootstrap<T>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}{}", self.bootstrap_url, file);
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(cache) = &self.bootstrap_cache {
+            return cache
+                .get(&self.client, &url, file, self.bootstrap_ttl)
+                .await;
+        }
+        Ok(self
+            .client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
     }
 
-    /// Method from [`arin_originas0` extension.](https://bitbucket.org/arin-specs/arin-rdap-originas/src/master/arin-rdap-originas.txt).
-    /// Given server must support this method. Returns result of `IpNetwork`s.
-    pub async fn search_networks_by_origin_as(
+    pub async fn fetch_bootstrap_asn(
         &self,
-        server: &str,
-        asn: u32,
-    ) -> Result<parser::ArinOriginas0OriginautnumsResults, ClientError> {
-        let url = format!("{}arin_originas0_networksbyoriginas/{}", server, asn);
-        self.get(&url).await
+    ) -> Result<bootstrap::Asn, Box<dyn std::error::Error + Send + Sync>> {
+        let bootstrap = self.get_bootstrap("asn.json").await?;
+        Ok(bootstrap::Asn::try_from(&bootstrap)?)
     }
 
-    /// Help method.
-    pub async fn help(&self, server: &str) -> Result<parser::Help, ClientError> {
-        let url = format!("{}help/", server);
-        self.get(&url).await
+    pub async fn fetch_bootstrap_dns(
+        &self,
+    ) -> Result<bootstrap::Dns, Box<dyn std::error::Error + Send + Sync>> {
+        let bootstrap = self.get_bootstrap("dns.json").await?;
+        Ok(bootstrap::Dns::from(&bootstrap))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::Client;
+    pub async fn fetch_bootstrap_ip(
+        &self,
+    ) -> Result<bootstrap::Ip, Box<dyn std::error::Error + Send + Sync>> {
+        let (parsed_ipv4, parsed_ipv6) = futures::join!(
+            self.get_bootstrap("ipv4.json"),
+            self.get_bootstrap("ipv6.json"),
+        );
+        Ok(bootstrap::Ip::try_from((&parsed_ipv4?, &parsed_ipv6?))?)
+    }
 
-    #[test]
-    fn test_send_sync() {
+    pub async fn fetch_bootstrap_object_tags(
+        &self,
+    ) -> Result<bootstrap::ObjectTags, Box<dyn std::error::Error + Send + Sync>> {
+        let bootstrap = self.get_bootstrap("object-tags.json").await?;
+        Ok(bootstrap::ObjectTags::from(&bootstrap))
+    }
+
+    /// Fetch bootstrap from IANA for ASN, IPv4 and IPV6, domains (DNS) and object tags.
+    pub async fn fetch_bootstrap(
+        &self,
+    ) -> Result<bootstrap::Bootstrap, Box<dyn std::error::Error + Send + Sync>> {
+        #[cfg(feature = "tracing")]
+        let start = Instant::now();
+        let (asn, dns, ipv4, ipv6, object_tags) = instrument!(
+            async {
+                futures::join!(
+                    self.get_bootstrap("asn.json"),
+                    self.get_bootstrap("dns.json"),
+                    self.get_bootstrap("ipv4.json"),
+                    self.get_bootstrap("ipv6.json"),
+                    self.get_bootstrap("object-tags.json"),
+                )
+            },
+            "rdap_fetch_bootstrap"
+        )
+        .await;
+        debug!(elapsed = ?start.elapsed(), "bootstrap fetched");
+        #[cfg(feature = "tracing")]
+        {
+            use bootstrap::{validate, Registry};
+            let mut diagnostics = Vec::new();
+            if let Ok(asn) = &asn {
+                diagnostics.extend(validate(Registry::Asn, asn));
+            }
+            if let Ok(dns) = &dns {
+                diagnostics.extend(validate(Registry::Dns, dns));
+            }
+            if let Ok(ipv4) = &ipv4 {
+                diagnostics.extend(validate(Registry::Ipv4, ipv4));
+            }
+            if let Ok(ipv6) = &ipv6 {
+                diagnostics.extend(validate(Registry::Ipv6, ipv6));
+            }
+            if let Ok(object_tags) = &object_tags {
+                diagnostics.extend(validate(Registry::ObjectTags, object_tags));
+            }
+            for diagnostic in diagnostics {
+                warn!(%diagnostic, "invalid bootstrap registry");
+            }
+        }
+
+        bootstrap::Bootstrap::from_registries(&asn?, &dns?, &ipv4?, &ipv6?, &object_tags?)
+    }
+
+    async fn parse_response<T: DeserializeOwned>(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<T, ClientError> {
+        self.read_body(response).await?.decode(self.parse_options)
+    }
+
+    /// Read whole body of response, but at most `max_response_size` bytes.
+    async fn read_body(&self, response: reqwest::Response) -> Result<Body, ClientError> {
+        let body = Body::read(response, self.max_response_size).await?;
+        if let Some(wire_debug) = &self.wire_debug {
+            wire_debug.body(&body.bytes);
+        }
+        Ok(body)
+    }
+
+    /// Returns response if it is successful RDAP response, otherwise converts it to error.
+    async fn check_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<reqwest::Response, ClientError> {
+        if response.status() == reqwest::StatusCode::OK {
+            if is_unexpected_content_type(&response) {
+                return Err(ClientError::UnexpectedContentType(Box::new(response)));
+            }
+            Ok(response)
+        } else if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            Err(ClientError::RateLimited {
+                retry_after: retry::retry_after(response.headers()),
+            })
+        } else if is_rdap_response(&response) {
+            Err(ClientError::Rdap(
+                Box::new(response.url().clone()),
+                Box::new(self.parse_response(response).await?),
+            ))
+        } else {
+            Err(ClientError::Server(Box::new(response)))
+        }
+    }
+
+    fn construct_headers() -> header::HeaderMap {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::ACCEPT,
+            header::HeaderValue::from_static(ACCEPT_HEADER),
+        );
+        headers
+    }
+
+    /// Run operation with deadline and abort it when cancellation token is cancelled.
+    async fn within_deadline<T, F: Future<Output = Result<T, ClientError>>>(
+        &self,
+        future: F,
+    ) -> Result<T, ClientError> {
+        let future = async {
+            match self.deadline {
+                Some(deadline) => rt::timeout(deadline, future)
+                    .await
+                    .map_err(|_| ClientError::DeadlineExceeded)?,
+                None => future.await,
+            }
+        };
+
+        match &self.cancellation {
+            Some(token) => {
+                let cancelled = token.cancelled();
+                futures::pin_mut!(future);
+                match futures::future::select(future, cancelled).await {
+                    futures::future::Either::Left((result, _)) => result,
+                    futures::future::Either::Right(_) => {
+                        debug!("operation cancelled");
+                        Err(ClientError::Cancelled)
+                    }
+                }
+            }
+            None => future.await,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<T, ClientError> {
+        Ok(self.send_response(request).await?.object)
+    }
+
+    async fn send_response<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<Response<T>, ClientError> {
+        // Boxed, because deserialization of flattened `extra` fields makes the future too big
+        // for stack of test threads when it is nested in other futures.
+        Box::pin(self.within_deadline(async {
+            let mut request = self.build_request(request)?;
+            let cache = self
+                .cache
+                .as_ref()
+                .map(|cache| (cache, request.url().clone()));
+            let cached = cache.as_ref().and_then(|(cache, url)| cache.get(url));
+            if let Some(cached) = &cached {
+                cached.add_validators(request.headers_mut());
+            }
+
+            let response = self.send_raw(request).await?;
+            let (url, redirects) = (response.url, response.redirects);
+            if let Some(cached) = cached {
+                if response.object.status() == reqwest::StatusCode::NOT_MODIFIED {
+                    debug!(%url, "not modified, using cached response");
+                    let object = Body::with_bytes(response.object, cached.body)
+                        .decode(self.parse_options)?;
+                    return Ok(Response {
+                        object,
+                        url,
+                        redirects,
+                    });
+                }
+            }
+
+            let response = self.check_response(response.object).await?;
+            let body = self.read_body(response).await?;
+            let cached = cache.and_then(|(cache, key)| {
+                let cached = CachedResponse::new(body.head.headers(), body.bytes.clone())?;
+                Some((cache, key, cached))
+            });
+            let object = body.decode(self.parse_options)?;
+            if let Some((cache, key, cached)) = cached {
+                cache.insert(key, cached);
+            }
+            Ok(Response {
+                object,
+                url,
+                redirects,
+            })
+        }))
+        .await
+    }
+
+    fn build_request(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Request, reqwest::Error> {
+        let mut request = request
+            .headers(Self::construct_headers())
+            .headers(self.headers.clone())
+            .build()?;
+        if let Some(cookie) = &self.session_cookie {
+            cookie.add_to(&mut request);
+        }
+        Ok(request)
+    }
+
+    /// Send request and follow redirects. Returned response is not checked.
+    async fn send_raw(
+        &self,
+        mut request: reqwest::Request,
+    ) -> Result<Response<reqwest::Response>, ClientError> {
+        let mut redirects = Vec::new();
+        loop {
+            if self.scheme_policy == SchemePolicy::HttpsOnly && request.url().scheme() != "https" {
+                warn!(url = %request.url(), "refusing to send request to plaintext URL");
+                return Err(ClientError::InsecureUrl(Box::new(request.url().clone())));
+            }
+            self.credentials.add_to(&mut request);
+            let next_request = request.try_clone();
+            let url = request.url().clone();
+            let response = self.execute(request).await?;
+            if response.url() != &url {
+                // Redirect was followed by reqwest client or browser.
+                #[cfg(not(target_arch = "wasm32"))]
+                warn!(%url, final_url = %response.url(), "redirect followed by reqwest client");
+                redirects.push(url);
+            }
+
+            if let (Some(location), Some(mut next_request)) =
+                (Self::redirect_location(&response), next_request)
+            {
+                redirects.push(response.url().clone());
+                if redirects.len() > self.max_redirects {
+                    return Err(ClientError::TooManyRedirects(redirects));
+                }
+                if next_request.url().host_str() != location.host_str() {
+                    // Do not send credentials to different host.
+                    let headers = next_request.headers_mut();
+                    headers.remove(header::AUTHORIZATION);
+                    headers.remove(header::COOKIE);
+                    headers.remove(header::PROXY_AUTHORIZATION);
+                    // Custom headers can contain API keys for original server.
+                    for name in self.headers.keys() {
+                        headers.remove(name);
+                    }
+                }
+                debug!(%location, "following redirect");
+                *next_request.url_mut() = location;
+                request = next_request;
+                continue;
+            }
+
+            return Ok(Response {
+                url: response.url().clone(),
+                object: response,
+                redirects,
+            });
+        }
+    }
+
+    /// Send search request and return stream of objects from `key` array. Deadline applies just
+    /// to receiving response headers, not to reading the body.
+    async fn search_stream<T: DeserializeOwned>(
+        &self,
+        url: String,
+        key: &'static str,
+    ) -> Result<impl Stream<Item = Result<T, ClientError>>, ClientError> {
+        let response = self
+            .within_deadline(async {
+                let request = self.client.get(url);
+                let request = self.build_request(self.add_search_params(request))?;
+                let response = self.send_raw(request).await?;
+                self.check_response(response.object).await
+            })
+            .await?;
+        Ok(stream::search_results(
+            response,
+            key,
+            self.max_response_size,
+            self.parse_options,
+        ))
+    }
+
+    fn redirect_location(response: &reqwest::Response) -> Option<reqwest::Url> {
+        use reqwest::StatusCode;
+
+        match response.status() {
+            StatusCode::MOVED_PERMANENTLY
+            | StatusCode::FOUND
+            | StatusCode::SEE_OTHER
+            | StatusCode::TEMPORARY_REDIRECT
+            | StatusCode::PERMANENT_REDIRECT => {
+                let location = response.headers().get(header::LOCATION)?.to_str().ok()?;
+                response.url().join(location).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Execute request and retry it according to retry policy.
+    async fn execute(
+        &self,
+        mut request: reqwest::Request,
+    ) -> Result<reqwest::Response, ClientError> {
+        let max_attempts = self
+            .retry_policy
+            .as_ref()
+            .map_or(1, RetryPolicy::get_max_attempts);
+        let mut attempt = 1;
+        loop {
+            // Requests without body can be always cloned.
+            let next_request = if attempt < max_attempts {
+                request.try_clone()
+            } else {
+                None
+            };
+            let permit = match &self.fair_queue {
+                Some(queue) => Some(
+                    queue
+                        .acquire(request.url().host_str().unwrap_or_default())
+                        .await,
+                ),
+                None => None,
+            };
+            debug!(method = %request.method(), url = %request.url(), attempt, "sending request");
+            let url = self.metrics_sink.as_ref().map(|_| request.url().clone());
+            if let Some(wire_debug) = &self.wire_debug {
+                wire_debug.request(&request);
+            }
+            let start = Instant::now();
+            let mut result = Next::new(&self.client, &self.middlewares)
+                .run(request)
+                .await;
+            drop(permit);
+            if let (Some(wire_debug), Ok(response)) = (&self.wire_debug, &result) {
+                wire_debug.response(response);
+            }
+            if let (Some(metrics_sink), Some(url)) = (&self.metrics_sink, url) {
+                match &mut result {
+                    // Recorded when body is read, so it contains number of read bytes.
+                    Ok(response) => {
+                        PendingMetrics::attach(metrics_sink, url, start.elapsed(), response);
+                    }
+                    Err(e) => metrics_sink.record(&RequestMetrics {
+                        server: url.host_str().unwrap_or_default(),
+                        url: &url,
+                        duration: start.elapsed(),
+                        status: None,
+                        bytes: None,
+                        error: Some(e),
+                    }),
+                }
+            }
+            #[cfg(feature = "tracing")]
+            match &result {
+                Ok(response) => {
+                    debug!(
+                        status = response.status().as_u16(),
+                        elapsed = ?start.elapsed(),
+                        "response received"
+                    );
+                }
+                Err(e) => {
+                    warn!(error = ?e, elapsed = ?start.elapsed(), "request failed");
+                }
+            }
+
+            if let (Some(policy), Some(next_request)) = (&self.retry_policy, next_request) {
+                if let Some(delay) = policy.retry_delay(&result, attempt) {
+                    debug!(?delay, attempt, "retrying request");
+                    rt::sleep(delay).await;
+                    request = next_request;
+                    attempt += 1;
+                    continue;
+                }
+            }
+
+            return result;
+        }
+    }
+
+    async fn get<T: DeserializeOwned, I: IntoUrl>(&self, url: I) -> Result<T, ClientError> {
+        self.send(self.client.get(url)).await
+    }
+
+    /// Send search request with query value encoded by `encode_search_value` and parameters set
+    /// by `with_search_params`.
+    async fn get_search<T: DeserializeOwned>(
+        &self,
+        server: &str,
+        path: &str,
+        key: &str,
+        value: &str,
+    ) -> Result<T, ClientError> {
+        let url = search_url(server, path, key, value);
+        self.send(self.add_search_params(self.client.get(url)))
+            .await
+    }
+
+    fn add_search_params(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        request.query(&self.search_params.query())
+    }
+
+    /// Query given RDAP server for IPv4 or IPv6 address.
+    pub async fn query_ip<I: Into<IpAddr>>(
+        &self,
+        server: &str,
+        ip: I,
+    ) -> Result<parser::IpNetwork, ClientError> {
+        let url = format!("{}ip/{}", server, ip.into());
+        self.get(&url).await
+    }
+
+    /// Query given RDAP server for IP network.
+    pub async fn query_ip_network<I: Into<IpNetwork>>(
+        &self,
+        server: &str,
+        ip_network: I,
+    ) -> Result<parser::IpNetwork, ClientError> {
+        let ip_network = ip_network.into();
+        let url = format!(
+            "{}ip/{}/{}",
+            server,
+            ip_network.network_address(),
+            ip_network.netmask()
+        );
+        self.get(&url).await
+    }
+
+    /// Query given RDAP server for AS number.
+    pub async fn query_asn(&self, server: &str, asn: u32) -> Result<parser::AutNum, ClientError> {
+        let url = format!("{}autnum/{}", server, asn);
+        self.get(&url).await
+    }
+
+    /// Query given RDAP server for nameserver handle.
+    pub async fn query_nameserver(
+        &self,
+        server: &str,
+        nameserver: &str,
+    ) -> Result<parser::Nameserver, ClientError> {
+        let url = format!("{}nameserver/{}", server, nameserver);
+        self.get(&url).await
+    }
+
+    /// Query given RDAP server for domain by name.
+    pub async fn query_domain(
+        &self,
+        server: &str,
+        domain: &str,
+    ) -> Result<parser::Domain, ClientError> {
+        let url = format!("{}domain/{}", server, domain);
+        self.get(&url).await
+    }
+
+    pub async fn query_reverse_domain<I: Into<IpAddr>>(
+        &self,
+        server: &str,
+        ip: I,
+    ) -> Result<parser::Domain, ClientError> {
+        let domain = match ip.into() {
+            IpAddr::V4(ipv4) => {
+                let octets = ipv4.octets();
+                format!(
+                    "{}.{}.{}.{}.in-addr.arpa",
+                    octets[3], octets[2], octets[1], octets[0]
+                )
+            }
+            IpAddr::V6(ipv6) => {
+                let mut ret: [u8; 32] = Default::default();
+                for (i, byte) in ipv6.octets().iter().enumerate() {
+                    ret[i * 2] = byte >> 4;
+                    ret[i * 2 + 1] = byte & 0xf;
+                }
+                ret.reverse();
+
+                format!(
+                    "{}.ip6.arpa",
+                    ret.iter()
+                        .map(|n| n.to_string())
+                        .collect::<Vec<_>>()
+                        .join(".")
+                )
+            }
+        };
+        self.query_domain(server, &domain).await
+    }
+
+    /// Query given RDAP server for entity by its name.
+    pub async fn query_entity(
+        &self,
+        server: &str,
+        entity: &str,
+    ) -> Result<parser::Entity, ClientError> {
+        let url = format!("{}entity/{}", server, entity);
+        self.get(&url).await
+    }
+
+    /// Search given RDAP server for nameserver by name or IP address.
+    pub async fn search_nameserver(
+        &self,
+        server: &str,
+        search_nameserver: SearchNameserver,
+    ) -> Result<parser::NameserverSearchResults, ClientError> {
+        let (key, value) = (search_nameserver.key(), search_nameserver.value());
+        self.get_search(server, "nameservers", key, &value).await
+    }
+
+    /// Search given RDAP server for domain by name, NS LDH name or NS IP address.
+    pub async fn search_domain(
+        &self,
+        server: &str,
+        search_domain: SearchDomain,
+    ) -> Result<parser::DomainSearchResults, ClientError> {
+        let (key, value) = (search_domain.key(), search_domain.value());
+        self.get_search(server, "domains", key, &value).await
+    }
+
+    /// Search for domains by name, that can contain wildcard `*` matching trailing characters of
+    /// label, like `exa*.com`. Server is found in DNS bootstrap by labels after the last one with
+    /// wildcard, so at least TLD must be given literally.
+    pub async fn search_domains(
+        &self,
+        search: SearchDomain,
+    ) -> Result<parser::DomainSearchResults, ClientError> {
+        let registry = search.registry_domain()?.to_owned();
+        self.search_in(&registry, "domains", [(search.key(), search.value())])
+            .await
+    }
+
+    /// Search for domains like `search_domains` and return stream of all found domains. Paged
+    /// results (RFC 8977) are followed by `next` links until the last page, so next page is
+    /// requested when all domains from previous page are consumed. Stream ends after the first
+    /// error.
+    pub fn search_domains_stream(
+        &self,
+        search: SearchDomain,
+    ) -> impl Stream<Item = Result<parser::Domain, ClientError>> + '_ {
+        let first = Some(NextPage::Cursor(self.search_params.clone()));
+        futures::stream::unfold(first, move |next| {
+            let search = search.clone();
+            async move {
+                // Boxed, because future of whole search is too big for stack of stream.
+                let page = match Box::pin(self.search_domains_page(search, next?)).await {
+                    Ok(page) => page,
+                    Err(e) => return Some((futures::stream::iter(vec![Err(e)]), None)),
+                };
+                let next = page.paging_metadata.as_ref().and_then(|paging| {
+                    match self.search_params.next_page(paging) {
+                        Some(params) => Some(NextPage::Cursor(params)),
+                        None => Some(NextPage::Url(paging.next_page()?.href.clone())),
+                    }
+                });
+                // Empty page with link to next page would never end.
+                let next = next.filter(|_| !page.results.is_empty());
+                let results: Vec<_> = page.results.into_iter().map(Ok).collect();
+                Some((futures::stream::iter(results), next))
+            }
+        })
+        .flatten()
+    }
+
+    async fn search_domains_page(
+        &self,
+        search: SearchDomain,
+        next: NextPage,
+    ) -> Result<DomainSearchPage, ClientError> {
+        match next {
+            NextPage::Cursor(params) => {
+                let registry = search.registry_domain()?.to_owned();
+                self.with_search_params(params)
+                    .search_in(&registry, "domains", [(search.key(), search.value())])
+                    .await
+            }
+            NextPage::Url(url) => self.get(url).await,
+        }
+    }
+
+    /// Search for domains at registry of given domain or TLD (like `com`), found in DNS
+    /// bootstrap. Use it to find domains delegated to nameserver, for example
+    /// `SearchDomain::NsLdhName("ns1.example.net")` or `SearchDomain::NsIp(ip)`. Nameserver name
+    /// can contain wildcard `*` like domain name in `search_domains`.
+    pub async fn search_domains_in(
+        &self,
+        registry: &str,
+        search: SearchDomain,
+    ) -> Result<parser::DomainSearchResults, ClientError> {
+        search.validate()?;
+        self.search_in(registry, "domains", [(search.key(), search.value())])
+            .await
+    }
+
+    /// Search for nameservers by name, that can contain wildcard `*` like `ns*.example.com`.
+    /// Server is found in DNS bootstrap like for `search_domains`.
+    pub async fn search_nameservers(
+        &self,
+        search: SearchNameserver,
+    ) -> Result<parser::NameserverSearchResults, ClientError> {
+        let registry = search.registry_domain()?.to_owned();
+        self.search_in(&registry, "nameservers", [(search.key(), search.value())])
+            .await
+    }
+
+    /// Search for nameservers at registry of given domain or TLD (like `com`), found in DNS
+    /// bootstrap. Use it to find nameservers by IP address with `SearchNameserver::Ip(ip)`.
+    pub async fn search_nameservers_in(
+        &self,
+        registry: &str,
+        search: SearchNameserver,
+    ) -> Result<parser::NameserverSearchResults, ClientError> {
+        search.validate()?;
+        self.search_in(registry, "nameservers", [(search.key(), search.value())])
+            .await
+    }
+
+    /// Search for entities by full name (like `Bobby Joe*`) or handle (like `ABC*-ARIN`), that can
+    /// end with wildcard `*`. Server is found by object tag of handle in object tags bootstrap
+    /// (RFC 8521), unless `server` is given, which is required for search by full name.
+    pub async fn search_entities(
+        &self,
+        search: SearchEntity,
+        server: Option<&str>,
+    ) -> Result<parser::EntitySearchResults, ClientError> {
+        search.validate()?;
+        let mut path = format!("entities?{}=", search.key());
+        let lookup = match search {
+            SearchEntity::Fn(value) => {
+                encode_search_value(&value, &mut path);
+                None
+            }
+            SearchEntity::Handle(value) => {
+                encode_search_value(&value, &mut path);
+                Some(Lookup::Entity(value))
+            }
+        };
+        let future = self.within_deadline(async {
+            let lookup = match (server, lookup) {
+                (Some(server), _) => {
+                    record!("server", server);
+                    let url = format!("{}{}", bootstrap::base_url(server), path);
+                    return self
+                        .send(self.add_search_params(self.client.get(url)))
+                        .await;
+                }
+                (None, Some(lookup)) => lookup,
+                (None, None) => {
+                    return Err(ClientError::InvalidQuery(
+                        "server must be given for search by full name".into(),
+                    ))
+                }
+            };
+            let bootstrap = self.lookup_bootstrap(&lookup).await?;
+            let servers = self.lookup_servers(&lookup, &bootstrap)?;
+            Self::with_failover(servers, |server| {
+                let url = format!("{}{}", server, path);
+                self.send(self.add_search_params(self.client.get(url)))
+            })
+            .await
+        });
+        instrument!(
+            future,
+            "rdap_search",
+            query = %path,
+            server = tracing::field::Empty
+        )
+        .await
+    }
+
+    /// Send search query to servers of registry of given domain from DNS bootstrap.
+    async fn search_in<T: DeserializeOwned>(
+        &self,
+        registry: &str,
+        path: &str,
+        query: [(&str, String); 1],
+    ) -> Result<T, ClientError> {
+        let lookup = Lookup::Domain(registry.to_owned());
+        let [(key, value)] = &query;
+        let future = self.within_deadline(async {
+            let bootstrap = self.lookup_bootstrap(&lookup).await?;
+            let servers = self.lookup_servers(&lookup, &bootstrap)?;
+            Self::with_failover(servers, |server| self.get_search(server, path, key, value)).await
+        });
+        Box::pin(instrument!(
+            future,
+            "rdap_search",
+            query = ?query,
+            server = tracing::field::Empty
+        ))
+        .await
+    }
+
+    /// Search given RDAP server for domain by FN or handle.
+    pub async fn search_entity(
+        &self,
+        server: &str,
+        search_entity: SearchEntity,
+    ) -> Result<parser::EntitySearchResults, ClientError> {
+        let (key, value) = (search_entity.key(), search_entity.value());
+        self.get_search(server, "entities", key, &value).await
+    }
+
+    /// Search given RDAP server for nameserver by name or IP address. Found nameservers are
+    /// parsed one by one as response body arrives, so whole response is never buffered.
+    pub async fn search_nameserver_stream(
+        &self,
+        server: &str,
+        search_nameserver: SearchNameserver,
+    ) -> Result<impl Stream<Item = Result<parser::Nameserver, ClientError>>, ClientError> {
+        let url = search_url(
+            server,
+            "nameservers",
+            search_nameserver.key(),
+            &search_nameserver.value(),
+        );
+        self.search_stream(url, "nameserverSearchResults").await
+    }
+
+    /// Search given RDAP server for domain by name, NS LDH name or NS IP address. Found domains
+    /// are parsed one by one as response body arrives, so whole response is never buffered.
+    pub async fn search_domain_stream(
+        &self,
+        server: &str,
+        search_domain: SearchDomain,
+    ) -> Result<impl Stream<Item = Result<parser::Domain, ClientError>>, ClientError> {
+        let url = search_url(
+            server,
+            "domains",
+            search_domain.key(),
+            &search_domain.value(),
+        );
+        self.search_stream(url, "domainSearchResults").await
+    }
+
+    /// Search given RDAP server for entity by FN or handle. Found entities are parsed one by one
+    /// as response body arrives, so whole response is never buffered.
+    pub async fn search_entity_stream(
+        &self,
+        server: &str,
+        search_entity: SearchEntity,
+    ) -> Result<impl Stream<Item = Result<parser::Entity, ClientError>>, ClientError> {
+        let url = search_url(
+            server,
+            "entities",
+            search_entity.key(),
+            &search_entity.value(),
+        );
+        self.search_stream(url, "entitySearchResults").await
+    }
+
+    /// Method from [`arin_originas0` extension.](https://bitbucket.org/arin-specs/arin-rdap-originas/src/master/arin-rdap-originas.txt).
+    /// Given server must support this method. Returns result of `IpNetwork`s.
+    pub async fn search_networks_by_origin_as(
+        &self,
+        server: &str,
+        asn: u32,
+    ) -> Result<parser::ArinOriginas0OriginautnumsResults, ClientError> {
+        let url = format!("{}arin_originas0_networksbyoriginas/{}", server, asn);
+        self.get(&url).await
+    }
+
+    /// Query help of given RDAP server, that contains notices like terms of service and
+    /// supported extensions in `rdap_conformance`.
+    pub async fn help(&self, server: &str) -> Result<parser::Help, ClientError> {
+        let url = format!("{}help", bootstrap::base_url(server));
+        self.get(&url).await
+    }
+
+    /// Send reverse search (RFC 9536) to given RDAP server, for example for domains by properties
+    /// of their registrant.
+    pub async fn reverse_search<T: DeserializeOwned>(
+        &self,
+        server: &str,
+        search: ReverseSearch<T>,
+    ) -> Result<T, ClientError> {
+        let url = format!("{}{}", bootstrap::base_url(server), search.path()?);
+        let request = self.add_search_params(self.client.get(url));
+        self.send(request).await
+    }
+
+    /// Fetch RDAP response from given URL, for example URL stored earlier or returned by other
+    /// tool, and parse it as object, search results or help. Use `lookup` with `Lookup` to query
+    /// object which server is not known.
+    pub async fn fetch_url<U: IntoUrl>(
+        &self,
+        url: U,
+    ) -> Result<Response<parser::TopLevelResponse>, ClientError> {
+        self.send_response(self.client.get(url)).await
+    }
+
+    /// Fetch target of link returned in other response, like `related` link to registrar or `up`
+    /// link to parent network. Relative `href` is resolved against context URI in `value`.
+    pub async fn follow(
+        &self,
+        link: &parser::Link,
+    ) -> Result<Response<parser::TopLevelResponse>, ClientError> {
+        self.fetch_url(Self::link_url(link)?).await
+    }
+
+    /// Returns target URL of link, relative `href` is resolved against `value`.
+    fn link_url(link: &parser::Link) -> Result<reqwest::Url, ClientError> {
+        let base = link
+            .value
+            .as_deref()
+            .and_then(|value| reqwest::Url::parse(value).ok());
+        match base {
+            Some(base) => base.join(&link.href),
+            None => reqwest::Url::parse(&link.href),
+        }
+        .map_err(|e| ClientError::InvalidQuery(Box::new(e)))
+    }
+
+    /// Find RDAP server for given domain in bootstrap and query it. IDN like `münchen.de` is
+    /// converted to A-labels and error returned by server is `ClientError::Rdap`. Use
+    /// `lookup_domain` to get also authoritative URL and followed redirects.
+    pub async fn domain(&self, domain: &str) -> Result<parser::Domain, ClientError> {
+        Ok(self.lookup_domain(domain).await?.into_object())
+    }
+
+    /// Find RDAP server for given domain in bootstrap and query it for domain by name. Reverse
+    /// domains like `6.0.193.in-addr.arpa` are routed by IP bootstrap.
+    pub async fn lookup_domain(
+        &self,
+        domain: &str,
+    ) -> Result<Response<parser::Domain>, ClientError> {
+        self.lookup(Lookup::Domain(domain.to_string())).await
+    }
+
+    /// Like `lookup_domain`, but when registry response contains `related` link to registrar, like
+    /// responses of thin gTLD registries, domain is fetched also from registrar that has contact
+    /// details of registrant.
+    pub async fn lookup_domain_with_registrar(
+        &self,
+        domain: &str,
+    ) -> Result<DomainWithRegistrar, ClientError> {
+        let registry = self.lookup_domain(domain).await?;
+        let registrar = match registrar_link(&registry) {
+            Some(link) => {
+                debug!(href = %link.href, "following link to registrar");
+                let result = match Self::link_url(link) {
+                    Ok(url) => self.send_response(self.client.get(url)).await,
+                    Err(e) => Err(e),
+                };
+                Some(result)
+            }
+            None => None,
+        };
+        Ok(DomainWithRegistrar {
+            registry,
+            registrar,
+        })
+    }
+
+    /// Check if domain is registered without downloading and parsing its data. Server found in
+    /// bootstrap is queried by HEAD request (RFC 7480), when server doesn't support it, GET is
+    /// sent instead. Returns `false` when server responds with 404 status code.
+    pub async fn exists_domain(&self, domain: &str) -> Result<bool, ClientError> {
+        let lookup = Lookup::Domain(domain.to_string());
+        let future = self.within_deadline(async {
+            let bootstrap = self.lookup_bootstrap(&lookup).await?;
+            let servers = self.lookup_servers(&lookup, &bootstrap)?;
+            Self::with_failover(servers, |server| {
+                self.exists(format!("{}{}", server, lookup.path()))
+            })
+            .await
+        });
+        instrument!(
+            future,
+            "rdap_exists",
+            query = ?lookup,
+            server = tracing::field::Empty
+        )
+        .await
+    }
+
+    async fn exists(&self, url: String) -> Result<bool, ClientError> {
+        use reqwest::StatusCode;
+
+        let request = self.build_request(self.client.head(&url))?;
+        let response = self.send_raw(request).await?.object;
+        match response.status() {
+            StatusCode::OK => return Ok(true),
+            StatusCode::NOT_FOUND => return Ok(false),
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED => {
+                debug!(%url, "HEAD is not supported, sending GET");
+            }
+            StatusCode::TOO_MANY_REQUESTS => {
+                return Err(ClientError::RateLimited {
+                    retry_after: retry::retry_after(response.headers()),
+                })
+            }
+            _ => return Err(ClientError::Server(Box::new(response))),
+        }
+
+        let request = self.build_request(self.client.get(url))?;
+        let response = self.send_raw(request).await?.object;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false);
+        }
+        self.check_response(response).await?;
+        Ok(true)
+    }
+
+    /// Find RDAP server for given IP address or network in bootstrap and query it. Accepts
+    /// `IpAddr`, `IpNetwork`, `ipnet::IpNet` (with `ipnet` feature) or string like `192.0.2.1` or
+    /// `192.0.2.0/24`, network is queried as `ip/192.0.2.0/24`.
+    pub async fn ip<Q>(&self, query: Q) -> Result<parser::IpNetwork, ClientError>
+    where
+        Q: TryInto<IpQuery>,
+        ClientError: From<Q::Error>,
+    {
+        let lookup = match query.try_into()? {
+            IpQuery::Address(address) => Lookup::Ip(address),
+            IpQuery::Network(network) => Lookup::IpNetwork(network),
+        };
+        Ok(self
+            .lookup::<parser::IpNetwork>(lookup)
+            .await?
+            .into_object())
+    }
+
+    /// Find RDAP server for given IPv4 or IPv6 address in bootstrap and query it for IP network.
+    pub async fn lookup_ip<I: Into<IpAddr>>(
+        &self,
+        ip: I,
+    ) -> Result<Response<parser::IpNetwork>, ClientError> {
+        self.lookup(Lookup::Ip(ip.into())).await
+    }
+
+    /// Find RDAP server for given AS number in ASN bootstrap and query it. Response contains also
+    /// followed redirects. Use `AutNum::range` to get AS numbers of response, because some servers
+    /// return just number as handle.
+    pub async fn autnum(&self, asn: u32) -> Result<Response<parser::AutNum>, ClientError> {
+        self.lookup_asn(asn).await
+    }
+
+    /// Like `autnum`, but AS number is parsed from string like `AS1234`, `1234` or `AS1.10`.
+    pub async fn autnum_str(&self, asn: &str) -> Result<Response<parser::AutNum>, ClientError> {
+        let asn = parser::parse_asn(asn).ok_or_else(|| {
+            ClientError::InvalidQuery(format!("invalid AS number {}", asn).into())
+        })?;
+        self.autnum(asn).await
+    }
+
+    /// Find RDAP server for given AS number in bootstrap and query it.
+    pub async fn lookup_asn(&self, asn: u32) -> Result<Response<parser::AutNum>, ClientError> {
+        self.lookup(Lookup::Asn(asn)).await
+    }
+
+    /// Query registry of domain of given nameserver host for that nameserver. Unicode host is
+    /// converted to A-labels. When registry doesn't support nameserver lookup (returns 501 status
+    /// code), nameserver is searched by name instead.
+    pub async fn nameserver(&self, host: &str) -> Result<parser::Nameserver, ClientError> {
+        let lookup = Lookup::Nameserver(host.to_string());
+        let future = self.within_deadline(async {
+            let bootstrap = self.lookup_bootstrap(&lookup).await?;
+            let servers = self.lookup_servers(&lookup, &bootstrap)?;
+            Self::with_failover(servers, |server| {
+                let url = format!("{}{}", server, lookup.path());
+                async move {
+                    match self.send(self.client.get(url)).await {
+                        Err(e) if Self::is_not_implemented(&e) => {
+                            debug!(server, "nameserver lookup not supported, searching");
+                            self.search_nameserver_by_name(server, host).await?.ok_or(e)
+                        }
+                        result => result,
+                    }
+                }
+            })
+            .await
+        });
+        instrument!(
+            future,
+            "rdap_lookup",
+            query = ?lookup,
+            server = tracing::field::Empty
+        )
+        .await
+    }
+
+    /// Search server for nameserver with exactly given name.
+    async fn search_nameserver_by_name(
+        &self,
+        server: &str,
+        host: &str,
+    ) -> Result<Option<parser::Nameserver>, ClientError> {
+        let host = Lookup::ascii_name(host);
+        let results = self
+            .search_nameserver_stream(server, SearchNameserver::Name(host.clone()))
+            .await?;
+        futures::pin_mut!(results);
+        while let Some(nameserver) = results.next().await {
+            let nameserver = nameserver?;
+            if nameserver.ldh_name == host.as_str() {
+                return Ok(Some(nameserver));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns true if server doesn't support query, as signalled by 501 status code.
+    fn is_not_implemented(error: &ClientError) -> bool {
+        match error {
+            ClientError::Server(response) => {
+                response.status() == reqwest::StatusCode::NOT_IMPLEMENTED
+            }
+            ClientError::Rdap(_, error) => error.error_code() == 501,
+            _ => false,
+        }
+    }
+
+    /// Query entity by handle. Server is found by object tag of handle (like `ARIN` in
+    /// `ABC123-ARIN`) in object tags bootstrap (RFC 8521), unless `server` is given, for example
+    /// for handle without tag. Returns `ClientError::NotFound` when entity doesn't exist.
+    pub async fn entity(
+        &self,
+        handle: &str,
+        server: Option<&str>,
+    ) -> Result<parser::Entity, ClientError> {
+        let lookup = Lookup::Entity(handle.to_string());
+        let result = match server {
+            Some(server) => {
+                let url = format!("{}{}", bootstrap::base_url(server), lookup.path());
+                self.send(self.client.get(url)).await
+            }
+            None => self
+                .lookup::<parser::Entity>(lookup)
+                .await
+                .map(Response::into_object),
+        };
+        result.map_err(ClientError::into_not_found)
+    }
+
+    /// Find RDAP server for given entity handle in object tags bootstrap (RFC 8521) and query it.
+    pub async fn lookup_entity(
+        &self,
+        entity: &str,
+    ) -> Result<Response<parser::Entity>, ClientError> {
+        self.lookup(Lookup::Entity(entity.to_string())).await
+    }
+
+    /// Lookup object given as string, kind of object is detected from its format: IP address or
+    /// network, AS number like `AS15169`, entity handle with object tag like `ABC123-ARIN` or
+    /// domain name.
+    pub async fn lookup_str(&self, query: &str) -> Result<Response<parser::Object>, ClientError> {
+        self.lookup(Lookup::try_from(query)?).await
+    }
+
+    /// Find RDAP server in bootstrap and query it for given object, which is parsed as `T`. Use
+    /// `Raw<T>` to get also original JSON returned by server.
+    pub async fn lookup<T: DeserializeOwned>(
+        &self,
+        lookup: Lookup,
+    ) -> Result<Response<T>, ClientError> {
+        let future = self.within_deadline(async {
+            let bootstrap = self.lookup_bootstrap(&lookup).await?;
+            let servers = self.lookup_servers(&lookup, &bootstrap)?;
+            Self::with_failover(servers, |server| {
+                let url = format!("{}{}", server, lookup.path());
+                self.send_response(self.client.get(url))
+            })
+            .await
+        });
+        instrument!(
+            future,
+            "rdap_lookup",
+            query = ?lookup,
+            server = tracing::field::Empty
+        )
+        .await
+    }
+
+    /// Returns servers for lookup from bootstrap ordered by scheme policy or
+    /// `ClientError::NoServer`, when there is none.
+    fn lookup_servers<'a>(
+        &'a self,
+        lookup: &Lookup,
+        bootstrap: &'a bootstrap::Bootstrap,
+    ) -> Result<Vec<&'a str>, ClientError> {
+        let overridden = self
+            .bootstrap_overrides
+            .as_ref()
+            .map_or(&[][..], |overrides| lookup.servers(&overrides.0));
+        let servers = if overridden.is_empty() {
+            lookup.servers(bootstrap)
+        } else {
+            debug!("using servers from bootstrap overrides");
+            overridden
+        };
+        let is_https = |server: &&str| server.starts_with("https://");
+        let mut servers: Vec<_> = servers.iter().map(String::as_str).collect();
+        match self.scheme_policy {
+            SchemePolicy::PreferHttps => servers.sort_by_key(|server| !is_https(server)),
+            SchemePolicy::HttpsOnly => servers.retain(is_https),
+            SchemePolicy::AsListed => {}
+        }
+        if servers.is_empty() {
+            warn!("no RDAP server found in bootstrap");
+            return Err(ClientError::NoServer);
+        }
+        Ok(servers)
+    }
+
+    /// Send query to servers in order until one of them returns response or error that is not
+    /// caused by server unavailability.
+    async fn with_failover<'a, T, F, Fut>(
+        servers: Vec<&'a str>,
+        mut send: F,
+    ) -> Result<T, ClientError>
+    where
+        F: FnMut(&'a str) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut errors = Vec::new();
+        for server in servers {
+            record!("server", server);
+            debug!(server, "RDAP server selected");
+            match send(server).await {
+                Err(e) if Self::is_unavailable(&e) => {
+                    warn!(server, error = ?e, "RDAP server unavailable");
+                    errors.push((server.to_string(), e));
+                }
+                result => return result,
+            }
+        }
+        match errors.len() {
+            1 => Err(errors.remove(0).1),
+            _ => Err(ClientError::AllServersFailed(errors)),
+        }
+    }
+
+    /// Returns true if error means that server is unavailable and other server should be tried.
+    fn is_unavailable(error: &ClientError) -> bool {
+        match error {
+            ClientError::Reqwest(e) => e.is_timeout() || retry::is_connect_error(e),
+            ClientError::Server(response) => response.status().is_server_error(),
+            _ => false,
+        }
+    }
+
+    /// Lookup many objects concurrently, at most `max_concurrency` requests are in flight at the
+    /// same time. Results are returned in the same order as given lookups.
+    ///
+    /// When server returns 429 status code with `Retry-After` header, no other request from this
+    /// batch is sent to that server until given time passes and rate limited lookup is retried.
+    pub fn lookup_many<I: IntoIterator<Item = Lookup>>(
+        &self,
+        lookups: I,
+        max_concurrency: usize,
+    ) -> impl Stream<Item = Result<Response<parser::Object>, ClientError>> + '_ {
+        let lookups: Vec<_> = lookups.into_iter().collect();
+        let rate_limits = Arc::new(RateLimits::default());
+        futures::stream::iter(lookups)
+            .map(move |lookup| {
+                let rate_limits = rate_limits.clone();
+                instrument!(
+                    async move { self.lookup_rate_limited(lookup, &rate_limits).await },
+                    "rdap_lookup",
+                    query = ?lookup,
+                    server = tracing::field::Empty
+                )
+            })
+            .buffered(max_concurrency.max(1))
+    }
+
+    async fn lookup_rate_limited(
+        &self,
+        lookup: Lookup,
+        rate_limits: &RateLimits,
+    ) -> Result<Response<parser::Object>, ClientError> {
+        let bootstrap = self.lookup_bootstrap(&lookup).await?;
+        let servers = self.lookup_servers(&lookup, &bootstrap)?;
+        Self::with_failover(servers, |server| {
+            let url = format!("{}{}", server, lookup.path());
+            async move {
+                let mut attempt = 1;
+                loop {
+                    rate_limits.wait(server).await;
+                    match self.send_response(self.client.get(&url)).await {
+                        Err(ClientError::RateLimited {
+                            retry_after: Some(retry_after),
+                        }) if attempt < MAX_RATE_LIMITED_ATTEMPTS => {
+                            debug!(server, ?retry_after, "server rate limited batch lookup");
+                            rate_limits.block(server, retry_after).await;
+                            attempt += 1;
+                        }
+                        result => return result,
+                    }
+                }
+            }
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        bootstrap, parser, search_pattern_suffix, Cassette, CassetteMode, Client, ClientBuilder,
+        ClientError, FairQueue, Lookup, RdapQuery, RetryPolicy, ReverseSearch, SchemePolicy,
+        SearchDomain, SearchEntity, SearchNameserver, SearchParams, SortOrder,
+    };
+    use chrono::Datelike;
+    use reqwest::header;
+    use serde::de::DeserializeOwned;
+    use std::convert::TryFrom;
+    use std::fs::File;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{mpsc, Arc};
+    use std::time::Duration;
+
+    const DOMAIN_JSON: &str =
+        r#"{"objectClassName":"domain","ldhName":"example.com","entities":[],"events":[]}"#;
+
+    fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let mut response = format!("HTTP/1.1 {}\r\nConnection: close\r\n", status);
+        for header in headers {
+            response.push_str(header);
+            response.push_str("\r\n");
+        }
+        response.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+        response
+    }
+
+    fn rdap_response(body: &str) -> String {
+        response("200 OK", &["Content-Type: application/rdap+json"], body)
+    }
+
+    /// Starts HTTP server that sends given responses, one per connection. Returns server base URL
+    /// and receiver of raw requests heads.
+    fn serve(responses: Vec<String>) -> (String, mpsc::Receiver<String>) {
+        let count = responses.len();
+        let mut responses = responses.into_iter();
+        serve_with(count, move |_| responses.next().unwrap())
+    }
+
+    /// Like `serve`, but response for `count` connections is created by handler from request
+    /// head.
+    fn serve_with<F: FnMut(&str) -> String + Send + 'static>(
+        count: usize,
+        mut handler: F,
+    ) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            for _ in 0..count {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                }
+                let request = String::from_utf8_lossy(&request).into_owned();
+                let response = handler(&request);
+                let _ = sender.send(request);
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    fn test_client() -> ClientBuilder {
+        ClientBuilder::new().no_proxy()
+    }
+
+    fn parse<T: DeserializeOwned>(path: &str) -> T {
+        let file = File::open(format!("test_data/bootstrap/{}", path)).unwrap();
+        serde_json::from_reader(file).unwrap()
+    }
+
+    fn load_bootstrap() -> bootstrap::Bootstrap {
+        bootstrap::Bootstrap {
+            dns: bootstrap::Dns::from(&parse("dns.json")),
+            ip: bootstrap::Ip::try_from((&parse("ipv4.json"), &parse("ipv6.json"))).unwrap(),
+            asn: bootstrap::Asn::try_from(&parse("asn.json")).unwrap(),
+            object_tags: bootstrap::ObjectTags::from(&parse("object-tags.json")),
+            publication: None,
+        }
+    }
+
+    #[test]
+    fn test_send_sync() {
         fn is_send_sync<T: Send + Sync>() {}
         is_send_sync::<Client>(); // compiles only if true
     }
+
+    #[test]
+    fn test_builder_proxy() {
+        assert!(ClientBuilder::new()
+            .proxy("http://localhost:8080")
+            .build()
+            .is_ok());
+        assert!(ClientBuilder::new()
+            .custom_proxy(reqwest::Proxy::https("http://localhost:8080").unwrap())
+            .build()
+            .is_ok());
+        assert!(ClientBuilder::new().no_proxy().build().is_ok());
+        assert!(ClientBuilder::new().proxy("not url").build().is_err());
+    }
+
+    #[cfg(feature = "rustls-tls")]
+    #[test]
+    fn test_builder_identity_pem() {
+        let pem = std::fs::read("test_data/identity/identity.pem").unwrap();
+        assert!(ClientBuilder::new().identity_pem(&pem).build().is_ok());
+        assert!(ClientBuilder::new()
+            .identity_pem(b"not pem")
+            .build()
+            .is_err());
+    }
+
+    #[cfg(feature = "native-tls")]
+    #[test]
+    fn test_builder_identity_pkcs12() {
+        let der = std::fs::read("test_data/identity/identity.p12").unwrap();
+        assert!(ClientBuilder::new()
+            .identity_pkcs12(&der, "test")
+            .build()
+            .is_ok());
+        assert!(ClientBuilder::new()
+            .identity_pkcs12(&der, "wrong password")
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_builder_http2() {
+        assert!(ClientBuilder::new()
+            .tcp_nodelay(true)
+            .http2_keep_alive_interval(Some(Duration::from_secs(30)))
+            .http2_keep_alive_timeout(Duration::from_secs(10))
+            .http2_keep_alive_while_idle(true)
+            .build()
+            .is_ok());
+    }
+
+    #[test]
+    fn test_builder_pool() {
+        assert!(ClientBuilder::new()
+            .pool_idle_timeout(Some(Duration::from_secs(30)))
+            .pool_max_idle_per_host(4)
+            .tcp_keepalive(Some(Duration::from_secs(60)))
+            .build()
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_deadline() {
+        let client = ClientBuilder::new()
+            .deadline(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        let result: Result<(), _> = client.within_deadline(futures::future::pending()).await;
+        assert!(matches!(result, Err(ClientError::DeadlineExceeded)));
+
+        let result = client.within_deadline(async { Ok(1) }).await;
+        assert!(matches!(result, Ok(1)));
+
+        // Server that accepts connection, but never responds.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let _stream = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(5));
+        });
+        let client = test_client().build().unwrap();
+        let result = client
+            .with_deadline(Duration::from_millis(20))
+            .entity("ABC-EXAMPLE", Some(&server))
+            .await;
+        assert!(matches!(result, Err(ClientError::DeadlineExceeded)));
+        assert!(client.deadline.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cancellation() {
+        // Server that accepts connection, but never responds.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = format!("http://{}/", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let _stream = listener.accept().unwrap();
+            std::thread::sleep(Duration::from_secs(5));
+        });
+
+        let token = crate::CancellationToken::new();
+        let client = test_client()
+            .build()
+            .unwrap()
+            .with_cancellation(token.clone());
+        let (result, _) = futures::join!(client.query_domain(&server, "example.com"), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            token.cancel();
+        });
+        assert!(matches!(result, Err(ClientError::Cancelled)));
+
+        // Already cancelled token aborts operation immediately.
+        let result = client
+            .within_deadline(futures::future::pending::<Result<(), _>>())
+            .await;
+        assert!(matches!(result, Err(ClientError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        let (server, requests) = serve(vec![
+            response("503 Service Unavailable", &[], ""),
+            rdap_response(DOMAIN_JSON),
+        ]);
+        let client = test_client()
+            .retry_policy(RetryPolicy::new().backoff_base(Duration::from_millis(1)))
+            .build()
+            .unwrap();
+        let domain = client.query_domain(&server, "example.com").await.unwrap();
+        assert_eq!(Some("example.com"), domain.ldh_name.as_deref());
+        assert_eq!(2, requests.try_iter().count());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited() {
+        let rate_limited = || response("429 Too Many Requests", &["Retry-After: 0"], "");
+        let (server, _) = serve(vec![rate_limited()]);
+        let client = test_client().build().unwrap();
+        assert!(matches!(
+            client.query_domain(&server, "example.com").await,
+            Err(ClientError::RateLimited {
+                retry_after: Some(retry_after)
+            }) if retry_after == Duration::from_secs(0)
+        ));
+
+        let (server, requests) = serve(vec![rate_limited(), rdap_response(DOMAIN_JSON)]);
+        let client = test_client()
+            .retry_policy(RetryPolicy::new())
+            .build()
+            .unwrap();
+        assert!(client.query_domain(&server, "example.com").await.is_ok());
+        assert_eq!(2, requests.try_iter().count());
+    }
+
+    #[tokio::test]
+    async fn test_redirects() {
+        let (target, _) = serve(vec![rdap_response(DOMAIN_JSON)]);
+        let target_url = format!("{}domain/example.com", target);
+        let (server, _) = serve(vec![
+            response("302 Found", &["Location: /other/domain/example.com"], ""),
+            response(
+                "301 Moved Permanently",
+                &[&format!("Location: {}", target_url)],
+                "",
+            ),
+        ]);
+        let client = test_client().build().unwrap();
+        let response = client
+            .send_response::<parser::Domain>(
+                client.client.get(format!("{}domain/example.com", server)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(Some("example.com"), response.ldh_name.as_deref());
+        assert_eq!(target_url, response.url.as_str());
+        assert_eq!(
+            vec![
+                format!("{}domain/example.com", server),
+                format!("{}other/domain/example.com", server)
+            ],
+            response
+                .redirects
+                .iter()
+                .map(|u| u.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_redirects_followed_by_reqwest() {
+        // Redirect followed by reqwest client is reported from requested URL.
+        let (target, _) = serve(vec![rdap_response(DOMAIN_JSON)]);
+        let target_url = format!("{}domain/example.com", target);
+        let (server, _) = serve(vec![response(
+            "302 Found",
+            &[&format!("Location: {}", target_url)],
+            "",
+        )]);
+        let client = Client::with_reqwest_client(reqwest::Client::new());
+        let followed = client
+            .send_response::<parser::Domain>(
+                client.client.get(format!("{}domain/example.com", server)),
+            )
+            .await
+            .unwrap();
+        assert_eq!(target_url, followed.url.as_str());
+        assert_eq!(
+            format!("{}domain/example.com", server),
+            followed.redirects[0].as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_too_many_redirects() {
+        let redirect = || response("302 Found", &["Location: /domain/example.com"], "");
+        let (server, _) = serve(vec![redirect(), redirect()]);
+        let client = test_client().max_redirects(1).build().unwrap();
+        assert!(matches!(
+            client.query_domain(&server, "example.com").await,
+            Err(ClientError::TooManyRedirects(redirects)) if redirects.len() == 2
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_headers() {
+        let (server, requests) =
+            serve(vec![rdap_response(DOMAIN_JSON), rdap_response(DOMAIN_JSON)]);
+        let client = test_client()
+            .user_agent("test-agent")
+            .header(
+                header::HeaderName::from_static("x-api-key"),
+                header::HeaderValue::from_static("secret"),
+            )
+            .build()
+            .unwrap();
+        client.query_domain(&server, "example.com").await.unwrap();
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.contains("user-agent: test-agent\r\n"));
+        assert!(request.contains("x-api-key: secret\r\n"));
+        assert!(request.contains("accept: application/rdap+json, application/json;q=0.9\r\n"));
+
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            header::HeaderName::from_static("x-request"),
+            header::HeaderValue::from_static("one"),
+        );
+        client
+            .with_headers(headers)
+            .query_domain(&server, "example.com")
+            .await
+            .unwrap();
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.contains("x-api-key: secret\r\n"));
+        assert!(request.contains("x-request: one\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_farv1_device() {
+        let device = r#"{"rdapConformance": ["farv1"], "farv1_deviceInfo": {
+            "verification_url": "https://example.com/device", "user_code": "WDJB-MJHT",
+            "expires_in": 60, "interval": 0}}"#;
+        let pending = r#"{"errorCode": 401, "title": "Authorization pending"}"#;
+        let session = r#"{"rdapConformance": ["farv1"], "farv1_userClaims": {"sub": "103692"},
+            "farv1_sessionInfo": {"tokenExpiration": 3599, "tokenRefresh": true}}"#;
+        let (server, requests) = serve(vec![
+            response(
+                "200 OK",
+                &[
+                    "Content-Type: application/rdap+json",
+                    "Set-Cookie: id=abc; Path=/; HttpOnly",
+                ],
+                device,
+            ),
+            response(
+                "401 Unauthorized",
+                &["Content-Type: application/rdap+json"],
+                pending,
+            ),
+            rdap_response(session),
+            rdap_response(DOMAIN_JSON),
+        ]);
+        let client = test_client().build().unwrap();
+
+        let mut session = client
+            .farv1_device(&server, Some("https://idp.example.com"))
+            .await
+            .unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with(
+            "GET /farv1_session/device?farv1_iss=https%3A%2F%2Fidp.example.com HTTP/1.1"
+        ));
+        let device_info = session.info.farv1_device_info.as_ref().unwrap();
+        assert_eq!("WDJB-MJHT", device_info.user_code);
+
+        client.farv1_device_poll(&mut session).await.unwrap();
+        for _ in 0..2 {
+            let request = requests.recv().unwrap().to_lowercase();
+            assert!(request.starts_with("get /farv1_session/devicepoll "));
+            assert!(request.contains("cookie: id=abc\r\n"));
+        }
+        let claims = session.info.farv1_user_claims.as_ref().unwrap();
+        assert_eq!("103692", claims["sub"]);
+
+        client
+            .with_farv1_session(&session)
+            .query_domain(&server, "example.com")
+            .await
+            .unwrap();
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.contains("cookie: id=abc\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_credentials() {
+        // Both servers listen on 127.0.0.1, so other server is accessed using `localhost` host.
+        let (other, other_requests) = serve(vec![rdap_response(DOMAIN_JSON)]);
+        let other = other.replace("127.0.0.1", "localhost");
+        let (server, requests) = serve(vec![
+            rdap_response(DOMAIN_JSON),
+            response(
+                "302 Found",
+                &[&format!("Location: {}domain/example.com", other)],
+                "",
+            ),
+        ]);
+        let client = test_client()
+            .credentials("127.0.0.1", crate::Credentials::bearer("secret").unwrap())
+            .header(
+                header::HeaderName::from_static("x-api-key"),
+                header::HeaderValue::from_static("key"),
+            )
+            .build()
+            .unwrap();
+        for _ in 0..2 {
+            client.query_domain(&server, "example.com").await.unwrap();
+            let request = requests.recv().unwrap().to_lowercase();
+            assert!(request.contains("authorization: bearer secret\r\n"));
+            assert!(request.contains("x-api-key: key\r\n"));
+        }
+        let request = other_requests.recv().unwrap().to_lowercase();
+        assert!(!request.contains("authorization"));
+        assert!(!request.contains("x-api-key"));
+    }
+
+    #[tokio::test]
+    async fn test_replay() {
+        let dir = std::env::temp_dir().join(format!("rdap_client_replay_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("domain")).unwrap();
+        std::fs::write(dir.join("domain/example.com.json"), DOMAIN_JSON).unwrap();
+
+        // Server is never contacted.
+        let client = test_client().replay(&dir).build().unwrap();
+        let domain = client
+            .query_domain("http://127.0.0.1:1/", "Example.com")
+            .await
+            .unwrap();
+        assert_eq!("example.com", domain.ldh_name.unwrap());
+        match client
+            .query_domain("http://127.0.0.1:1/", "other.com")
+            .await
+        {
+            Err(ClientError::Server(response)) => assert_eq!(404, response.status()),
+            other => panic!("unexpected result {:?}", other),
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cassette() {
+        let path =
+            std::env::temp_dir().join(format!("rdap_client_cassette_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        assert!(Cassette::load(&path, CassetteMode::ReplayOnly).is_err());
+
+        let (server, requests) =
+            serve(vec![rdap_response(DOMAIN_JSON), rdap_response(DOMAIN_JSON)]);
+        let cassette = Cassette::load(&path, CassetteMode::RecordOnce).unwrap();
+        assert!(cassette.is_recording());
+        let client = test_client().cassette(cassette).build().unwrap();
+        client.query_domain(&server, "example.com").await.unwrap();
+        requests.recv().unwrap();
+
+        // Cassette exists now, so it is replayed and server is not contacted.
+        for mode in [CassetteMode::RecordOnce, CassetteMode::ReplayOnly] {
+            let cassette = Cassette::load(&path, mode).unwrap();
+            assert!(!cassette.is_recording());
+            let client = test_client().cassette(cassette).build().unwrap();
+            let domain = client.query_domain(&server, "EXAMPLE.com").await.unwrap();
+            assert_eq!("example.com", domain.ldh_name.unwrap());
+            match client.query_domain(&server, "other.com").await {
+                Err(ClientError::Cassette(_)) => {}
+                other => panic!("unexpected result {:?}", other),
+            }
+        }
+        assert!(requests.try_recv().is_err());
+
+        let cassette = Cassette::load(&path, CassetteMode::Passthrough).unwrap();
+        let client = test_client().cassette(cassette).build().unwrap();
+        client.query_domain(&server, "example.com").await.unwrap();
+        requests.recv().unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fair_queue() {
+        let (server, _) = serve(vec![rdap_response(DOMAIN_JSON), rdap_response(DOMAIN_JSON)]);
+        let queue = FairQueue::new(1);
+        let client = test_client().fair_queue(queue.clone()).build().unwrap();
+        let (first, second) = futures::join!(
+            client.query_domain(&server, "example.com"),
+            client.query_domain(&server, "example.com")
+        );
+        first.unwrap();
+        second.unwrap();
+        assert_eq!(0, queue.in_flight());
+    }
+
+    #[tokio::test]
+    async fn test_compression() {
+        let (server, requests) =
+            serve(vec![rdap_response(DOMAIN_JSON), rdap_response(DOMAIN_JSON)]);
+        let client = test_client().build().unwrap();
+        client.query_domain(&server, "example.com").await.unwrap();
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.contains("accept-encoding: gzip, br\r\n"));
+
+        let client = test_client().no_compression().build().unwrap();
+        client.query_domain(&server, "example.com").await.unwrap();
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(!request.contains("accept-encoding"));
+    }
+
+    #[tokio::test]
+    async fn test_resolve() {
+        let (server, _) = serve(vec![rdap_response(DOMAIN_JSON)]);
+        let server = server.replace("127.0.0.1", "rdap.test");
+        let localhost = "127.0.0.1".parse().unwrap();
+        let client = test_client()
+            .resolve("rdap.test", &[localhost])
+            .build()
+            .unwrap();
+        assert!(client.query_domain(&server, "example.com").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_ip_only() {
+        let (server, _) = serve(vec![rdap_response(DOMAIN_JSON)]);
+        let client = test_client().ipv6_only().build().unwrap();
+        assert!(matches!(
+            client.query_domain(&server, "example.com").await,
+            Err(ClientError::Reqwest(_))
+        ));
+        let client = test_client().ipv4_only().build().unwrap();
+        assert!(client.query_domain(&server, "example.com").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_lookup_raw() {
+        let (server, _) = serve(vec![rdap_response(DOMAIN_JSON)]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+        let response = client
+            .lookup::<crate::Raw<parser::Domain>>(crate::Lookup::Domain("example.test".into()))
+            .await
+            .unwrap();
+        assert_eq!(
+            "example.com",
+            response.object.object.ldh_name.as_ref().unwrap()
+        );
+        assert_eq!(DOMAIN_JSON, response.object.json());
+    }
+
+    #[tokio::test]
+    async fn test_content_type() {
+        let (server, _) = serve(vec![
+            response(
+                "200 OK",
+                &["Content-Type: application/json; charset=utf-8"],
+                DOMAIN_JSON,
+            ),
+            response("200 OK", &[], DOMAIN_JSON),
+            response(
+                "200 OK",
+                &["Content-Type: text/html"],
+                "<html>Not RDAP</html>",
+            ),
+            response("200 OK", &["Content-Type: application/rdap+json"], "{"),
+        ]);
+        let client = test_client().build().unwrap();
+        for _ in 0..2 {
+            let domain = client.query_domain(&server, "example.com").await.unwrap();
+            assert_eq!("example.com", domain.ldh_name.unwrap());
+        }
+        match client.query_domain(&server, "example.com").await {
+            Err(ClientError::UnexpectedContentType(response)) => {
+                assert_eq!("text/html", response.headers()[header::CONTENT_TYPE])
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(matches!(
+            client.query_domain(&server, "example.com").await,
+            Err(ClientError::JsonDecode(_, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_parse_options() {
+        let json = r#"{"objectClassName":"domain","ldhName":"example.com","entities":[],"events":[{"eventAction":"registration","eventDate":"2020-01-01 10:00:00"}]}"#;
+        let (server, _) = serve(vec![rdap_response(json), rdap_response(json)]);
+        let client = test_client().build().unwrap();
+        assert!(client.query_domain(&server, "example.com").await.is_ok());
+
+        let client = test_client()
+            .parse_options(parser::ParseOptions::strict())
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client.query_domain(&server, "example.com").await,
+            Err(ClientError::JsonDecode(_, _))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_max_response_size() {
+        let chunked = format!(
+            "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/rdap+json\r\n\
+             Transfer-Encoding: chunked\r\n\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+            DOMAIN_JSON.len(),
+            DOMAIN_JSON
+        );
+        let (server, _) = serve(vec![
+            rdap_response(DOMAIN_JSON),
+            rdap_response(DOMAIN_JSON),
+            chunked,
+        ]);
+        let limit = DOMAIN_JSON.len() as u64;
+        let client = test_client().max_response_size(limit).build().unwrap();
+        assert!(client.query_domain(&server, "example.com").await.is_ok());
+
+        // Body for both responses is larger than limit, first one has `Content-Length` header.
+        let client = test_client().max_response_size(limit - 1).build().unwrap();
+        for _ in 0..2 {
+            match client.query_domain(&server, "example.com").await {
+                Err(ClientError::ResponseTooLarge { limit: l, .. }) => assert_eq!(limit - 1, l),
+                other => panic!("unexpected result {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_rdap_content_type() {
+        assert!(super::is_rdap_content_type("application/rdap+json"));
+        assert!(super::is_rdap_content_type(
+            "Application/JSON;charset=UTF-8"
+        ));
+        assert!(!super::is_rdap_content_type("text/html; charset=utf-8"));
+        assert!(!super::is_rdap_content_type("text/plain"));
+    }
+
+    #[tokio::test]
+    async fn test_search_stream() {
+        use futures::StreamExt;
+
+        let body = format!(
+            r#"{{"rdapConformance":["rdap_level_0"],"domainSearchResults":[{},{}]}}"#,
+            DOMAIN_JSON,
+            DOMAIN_JSON.replace("example.com", "example.org")
+        );
+        let (server, requests) = serve(vec![
+            rdap_response(&body),
+            rdap_response(r#"{"domainSearchResults":[{"ldhName":"#),
+        ]);
+        let client = test_client().build().unwrap();
+
+        let search = SearchDomain::Name("example.*".into());
+        let domains: Vec<_> = client
+            .search_domain_stream(&server, search)
+            .await
+            .unwrap()
+            .map(|domain| domain.unwrap().ldh_name.unwrap())
+            .collect()
+            .await;
+        assert_eq!(vec!["example.com", "example.org"], domains);
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains?name=example.* "));
+
+        let search = SearchDomain::Name("example.*".into());
+        let results: Vec<_> = client
+            .search_domain_stream(&server, search)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert!(matches!(results[..], [Err(ClientError::JsonDecode(..))]));
+    }
+
+    fn local_bootstrap(server: &str) -> bootstrap::Bootstrap {
+        let dns = format!(
+            r#"{{"version":"1.0","publication":"2020-01-01T00:00:00Z","services":[[["test"],["{}"]]]}}"#,
+            server
+        );
+        let dns: parser::BootstrapRfc7484 = serde_json::from_str(&dns).unwrap();
+        let empty = r#"{"version":"1.0","publication":"2020-01-01T00:00:00Z","services":[]}"#;
+        let empty: parser::BootstrapRfc7484 = serde_json::from_str(empty).unwrap();
+        let object_tags: parser::BootstrapRfc8521 = serde_json::from_str(
+            r#"{"version":"1.0","publication":"2020-01-01T00:00:00Z","services":[]}"#,
+        )
+        .unwrap();
+        bootstrap::Bootstrap {
+            dns: bootstrap::Dns::from(&dns),
+            ip: bootstrap::Ip::try_from((&empty, &empty)).unwrap(),
+            asn: bootstrap::Asn::try_from(&empty).unwrap(),
+            object_tags: bootstrap::ObjectTags::from(&object_tags),
+            publication: None,
+        }
+    }
+
+    /// Serves bootstrap registries from test data for `count` requests, with `ETag` header used
+    /// for conditional requests.
+    fn serve_bootstrap(count: usize) -> (String, mpsc::Receiver<String>) {
+        serve_with(count, |request| {
+            if request.to_lowercase().contains("if-none-match: \"v1\"") {
+                return response("304 Not Modified", &[], "");
+            }
+            let path = request.split(' ').nth(1).unwrap();
+            let mut body = String::new();
+            File::open(format!("test_data/bootstrap{}", path))
+                .unwrap()
+                .read_to_string(&mut body)
+                .unwrap();
+            response(
+                "200 OK",
+                &["Content-Type: application/json", "ETag: \"v1\""],
+                &body,
+            )
+        })
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_ttl() {
+        let (server, requests) = serve_bootstrap(10);
+        let client = test_client()
+            .bootstrap_url(&server)
+            .bootstrap_ttl(Duration::from_secs(0))
+            .build()
+            .unwrap();
+        let first = client.bootstrap().await.unwrap();
+        assert_eq!(
+            "2019-12-24T09:00:01+00:00",
+            first.publication.unwrap().to_rfc3339()
+        );
+        assert_eq!(5, requests.iter().take(5).count());
+
+        // Expired bootstrap is fetched again.
+        let second = client.bootstrap().await.unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(5, requests.iter().take(5).count());
+
+        // Server is not running anymore, so expired bootstrap is used.
+        let third = client.bootstrap().await.unwrap();
+        assert!(Arc::ptr_eq(&second, &third));
+        assert!(client.refresh_bootstrap().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_cache_dir() {
+        let dir = std::env::temp_dir().join(format!(
+            "rdap_client_bootstrap_cache_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        let (server, requests) = serve_bootstrap(11);
+        let builder = || {
+            test_client()
+                .bootstrap_url(&server)
+                .bootstrap_cache_dir(&dir)
+        };
+
+        builder().build().unwrap().bootstrap().await.unwrap();
+        assert_eq!(5, requests.iter().take(5).count());
+        assert!(dir.join("dns.json").exists());
+
+        // Corrupted registry is downloaded again, fresh ones are used without request.
+        std::fs::write(dir.join("dns.json"), "{").unwrap();
+        let client = builder()
+            .bootstrap_ttl(Duration::from_secs(3600))
+            .build()
+            .unwrap();
+        client.bootstrap().await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /dns.json "));
+        let client = builder()
+            .bootstrap_ttl(Duration::from_secs(3600))
+            .build()
+            .unwrap();
+        let bootstrap = client.bootstrap().await.unwrap();
+        assert!(bootstrap.dns.find("nic.cz").is_some());
+        assert!(requests.try_recv().is_err());
+
+        // Without TTL, cached registries are revalidated.
+        let client = builder().build().unwrap();
+        let bootstrap = client.bootstrap().await.unwrap();
+        assert!(bootstrap.dns.find("nic.cz").is_some());
+        for request in requests.iter().take(5) {
+            assert!(request.to_lowercase().contains("if-none-match: \"v1\""));
+        }
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[cfg(feature = "embedded-bootstrap")]
+    #[tokio::test]
+    async fn test_embedded_bootstrap() {
+        let client = test_client()
+            .bootstrap_url("http://127.0.0.1:1/")
+            .build()
+            .unwrap();
+        let embedded = client.bootstrap().await.unwrap();
+        assert!(embedded.dns.find("nic.cz").is_some());
+        assert!(Arc::ptr_eq(&embedded, &client.bootstrap().await.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_bootstrap() {
+        let (server, _) = serve_bootstrap(10);
+        let client = test_client().bootstrap_url(&server).build().unwrap();
+        let mut newer = load_bootstrap();
+        newer.publication = Some("2100-01-01T00:00:00Z".parse().unwrap());
+        client.set_bootstrap(newer).await;
+
+        // Fetched bootstrap is older than the current one.
+        let bootstrap = client.refresh_bootstrap().await.unwrap();
+        assert_eq!(2100, bootstrap.publication.unwrap().year());
+
+        client.set_bootstrap(load_bootstrap()).await;
+        let bootstrap = client.refresh_bootstrap().await.unwrap();
+        assert_eq!(2019, bootstrap.publication.unwrap().year());
+        assert!(Arc::ptr_eq(&bootstrap, &client.bootstrap().await.unwrap()));
+    }
+
+    #[tokio::test]
+    async fn test_bootstrap_overrides() {
+        let (server, requests) = serve(vec![rdap_response(DOMAIN_JSON)]);
+        let overrides = bootstrap::Overrides::new().domain("cz", &server);
+        let client = test_client()
+            .bootstrap_overrides(overrides)
+            .build()
+            .unwrap();
+        client.set_bootstrap(load_bootstrap()).await;
+        client.lookup_domain("nic.cz").await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /domain/nic.cz "));
+    }
+
+    #[test]
+    fn test_reverse_domain() {
+        let bootstrap = load_bootstrap();
+        let client = test_client().build().unwrap();
+        let servers = |domain: &str| {
+            let lookup = Lookup::Domain(domain.into());
+            client
+                .lookup_servers(&lookup, &bootstrap)
+                .ok()
+                .map(|s| s.join(" "))
+        };
+        assert_eq!(
+            "https://rdap.db.ripe.net/",
+            servers("6.0.193.in-addr.arpa").unwrap()
+        );
+        assert_eq!(
+            "https://rdap.db.ripe.net/",
+            servers("0.0.6.0.1.0.0.2.ip6.arpa").unwrap()
+        );
+        assert!(servers("0/25.6.0.193.in-addr.arpa").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_domain() {
+        let (server, requests) = serve(vec![
+            rdap_response(DOMAIN_JSON),
+            response(
+                "404 Not Found",
+                &["Content-Type: application/rdap+json"],
+                r#"{"errorCode":404,"title":"Not Found"}"#,
+            ),
+        ]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        client.domain("Příklad.test.").await.unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domain/xn--pklad-zsa96e.test "));
+
+        match client.domain("a b.test").await {
+            Err(ClientError::Rdap(_, error)) => assert_eq!(404, error.error_code()),
+            other => panic!("unexpected result {:?}", other),
+        }
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domain/a%20b.test "));
+    }
+
+    #[tokio::test]
+    async fn test_ip() {
+        let ip_json = r#"{"objectClassName":"ip network","handle":"NET","startAddress":"192.0.2.0","endAddress":"192.0.2.255","ipVersion":"v4"}"#;
+        let (server, requests) = serve(vec![rdap_response(ip_json), rdap_response(ip_json)]);
+        let mut bootstrap = local_bootstrap(&server);
+        bootstrap
+            .ip
+            .insert(vec![server], vec!["192.0.0.0/8".parse().unwrap()]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(bootstrap).await;
+
+        assert_eq!("NET", client.ip("192.0.2.0/24").await.unwrap().handle);
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /ip/192.0.2.0/24 "));
+        client
+            .ip(std::net::Ipv4Addr::new(192, 0, 2, 1))
+            .await
+            .unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /ip/192.0.2.1 "));
+
+        assert!(matches!(
+            client.ip("192.0.2.1/24").await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+        assert!(matches!(
+            client.ip("10.0.0.0/8").await,
+            Err(ClientError::NoServer)
+        ));
+
+        #[cfg(feature = "ipnet")]
+        assert_eq!(
+            crate::IpQuery::Network("192.0.2.0/24".parse().unwrap()),
+            "192.0.2.1/24".parse::<ipnet::IpNet>().unwrap().into()
+        );
+    }
+
+    #[test]
+    fn test_lookup_from_str() {
+        let lookup = |value: &str| Lookup::try_from(value).unwrap();
+        assert!(matches!(lookup("1.1.1.1"), Lookup::Ip(_)));
+        assert!(matches!(lookup("2001:db8::/32"), Lookup::IpNetwork(_)));
+        assert!(matches!(lookup("AS15169"), Lookup::Asn(15169)));
+        assert!(matches!(lookup("as1.10"), Lookup::Asn(65546)));
+        assert!(
+            matches!(lookup("ABC123-ARIN"), Lookup::Entity(ref handle) if handle == "ABC123-ARIN")
+        );
+        assert!(
+            matches!(lookup("example.com"), Lookup::Domain(ref domain) if domain == "example.com")
+        );
+        assert!(matches!(lookup("my-domain.com"), Lookup::Domain(_)));
+        assert!(matches!(lookup("xn--p1ai"), Lookup::Domain(_)));
+        assert!(matches!(lookup("com"), Lookup::Domain(_)));
+        assert!(matches!(lookup("2.0.192.in-addr.arpa"), Lookup::Domain(_)));
+        assert!(Lookup::try_from("192.0.2.1/24").is_err());
+        assert!(Lookup::try_from(" ").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_autnum() {
+        let autnum_json = r#"{"objectClassName":"autnum","handle":"65546","entities":[]}"#;
+        let redirect = response(
+            "301 Moved Permanently",
+            &["Location: /rir/autnum/65546"],
+            "",
+        );
+        let (server, requests) = serve(vec![redirect, rdap_response(autnum_json)]);
+        let mut bootstrap = local_bootstrap(&server);
+        bootstrap.asn.insert(vec![server], vec![65536..=65551]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(bootstrap).await;
+
+        let response = client.autnum_str("AS1.10").await.unwrap();
+        assert_eq!(Some(65546..=65546), response.range());
+        assert_eq!("/autnum/65546", response.redirects[0].path());
+        assert_eq!("/rir/autnum/65546", response.url.path());
+        assert!(requests.recv().unwrap().starts_with("GET /autnum/65546 "));
+
+        assert!(matches!(
+            client.autnum_str("ASX").await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_nameserver() {
+        let nameserver_json = |name| {
+            format!(
+                r#"{{"objectClassName":"nameserver","ldhName":"{}","entities":[],"events":[]}}"#,
+                name
+            )
+        };
+        let search_json = format!(
+            r#"{{"nameserverSearchResults":[{},{}]}}"#,
+            nameserver_json("ns1.xn--pklad-zsa96e.test.extra"),
+            nameserver_json("NS1.xn--pklad-zsa96e.test")
+        );
+        let (server, requests) = serve(vec![
+            rdap_response(&nameserver_json("ns1.example.test")),
+            response(
+                "501 Not Implemented",
+                &["Content-Type: application/rdap+json"],
+                r#"{"errorCode":501,"title":"Not Implemented"}"#,
+            ),
+            rdap_response(&search_json),
+        ]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        let nameserver = client.nameserver("ns1.example.test").await.unwrap();
+        assert_eq!("ns1.example.test", nameserver.ldh_name);
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /nameserver/ns1.example.test "));
+
+        let nameserver = client.nameserver("ns1.příklad.test").await.unwrap();
+        assert_eq!("NS1.xn--pklad-zsa96e.test", nameserver.ldh_name);
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /nameserver/ns1.xn--pklad-zsa96e.test "));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /nameservers?name=ns1.xn--pklad-zsa96e.test "));
+    }
+
+    #[tokio::test]
+    async fn test_search_domains() {
+        let search_json = format!(r#"{{"domainSearchResults":[{}]}}"#, DOMAIN_JSON);
+        let (server, requests) = serve(vec![
+            rdap_response(&search_json),
+            rdap_response(&search_json),
+            rdap_response(&search_json),
+        ]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        client
+            .search_domains(SearchDomain::Name("exa*.test".into()))
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains?name=exa*.test "));
+
+        client
+            .search_domains_in("test", SearchDomain::NsLdhName("ns*.example.net".into()))
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains?nsLdhName=ns*.example.net "));
+        client
+            .search_domains_in("test", SearchDomain::NsIp("2001:db8::1".parse().unwrap()))
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains?nsIp=2001%3Adb8%3A%3A1 "));
+        let search = SearchDomain::NsLdhName("ns1.example.net".into());
+        assert!(matches!(
+            client.search_domains(search).await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+        let search = SearchDomain::NsLdhName("n*s.example.net".into());
+        assert!(matches!(
+            client.search_domains_in("test", search).await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+
+        for pattern in ["", "*", "exa*", "e*a.test", "a..test", "example.te*"] {
+            assert!(matches!(
+                client
+                    .search_domains(SearchDomain::Name(pattern.into()))
+                    .await,
+                Err(ClientError::InvalidQuery(_))
+            ));
+        }
+        assert_eq!(Ok("test"), search_pattern_suffix("*.test").map_err(drop));
+        assert_eq!(
+            Ok("b.test"),
+            search_pattern_suffix("a*.b.test").map_err(drop)
+        );
+        assert_eq!(
+            Ok("example.test"),
+            search_pattern_suffix("example.test.").map_err(drop)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_search_nameservers() {
+        let search_json = r#"{"nameserverSearchResults":[{"objectClassName":"nameserver","ldhName":"ns1.example.test","entities":[],"events":[]}]}"#;
+        let (server, requests) =
+            serve(vec![rdap_response(search_json), rdap_response(search_json)]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        client
+            .search_nameservers(SearchNameserver::Name("ns*.example.test".into()))
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /nameservers?name=ns*.example.test "));
+        client
+            .search_nameservers_in("test", SearchNameserver::Ip("192.0.2.1".parse().unwrap()))
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /nameservers?ip=192.0.2.1 "));
+
+        let search = SearchNameserver::Ip("192.0.2.1".parse().unwrap());
+        assert!(matches!(
+            client.search_nameservers(search).await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+        let search = SearchNameserver::Name("ns1.*".into());
+        assert!(matches!(
+            client.search_nameservers(search).await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_entities() {
+        let search_json =
+            r#"{"entitySearchResults":[{"objectClassName":"entity","handle":"ABC-EXAMPLE"}]}"#;
+        let (server, requests) =
+            serve(vec![rdap_response(search_json), rdap_response(search_json)]);
+        let overrides = bootstrap::Overrides::new().object_tag("example", &server);
+        let client = test_client()
+            .bootstrap_overrides(overrides)
+            .build()
+            .unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        client
+            .search_entities(SearchEntity::Handle("AB*-EXAMPLE".into()), None)
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entities?handle=AB*-EXAMPLE "));
+        client
+            .search_entities(SearchEntity::Fn("Bobby Joe*".into()), Some(&server))
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entities?fn=Bobby%20Joe* "));
+
+        // Legacy search methods encode values in the same way.
+        let (server, requests) = serve(vec![rdap_response(search_json)]);
+        client
+            .search_entity(&server, SearchEntity::Fn("Bobby Joe*".into()))
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entities?fn=Bobby%20Joe* "));
+
+        for search in [
+            SearchEntity::Fn("Bobby Joe".into()),
+            SearchEntity::Fn(" *".into()),
+            SearchEntity::Handle("A*C-EXAMPLE".into()),
+        ] {
+            assert!(matches!(
+                client.search_entities(search, None).await,
+                Err(ClientError::InvalidQuery(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reverse_search() {
+        let search_json = format!(r#"{{"domainSearchResults":[{}]}}"#, DOMAIN_JSON);
+        let (server, requests) = serve(vec![rdap_response(&search_json)]);
+        let client = test_client().build().unwrap();
+
+        let search = ReverseSearch::domains()
+            .role("registrant")
+            .full_name("Bobby Joe*");
+        client.reverse_search(&server, search).await.unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains/reverse_search/entity?role=registrant&fn=Bobby%20Joe* "));
+
+        assert!(matches!(
+            client
+                .reverse_search(&server, ReverseSearch::entities())
+                .await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_search_params() {
+        let search_json = r#"{"entitySearchResults":[],"paging_metadata":{"pageNumber":1,"links":[{"rel":"next","href":"https://rdap.example/entities?fn=Bobby*&cursor=a%2Bb%3D"}]}}"#;
+        let (server, requests) = serve(vec![
+            rdap_response(search_json),
+            rdap_response(search_json),
+            rdap_response(search_json),
+        ]);
+        let client = test_client().build().unwrap();
+
+        let params = SearchParams::new()
+            .sort("name", SortOrder::Ascending)
+            .sort("registrationDate", SortOrder::Descending)
+            .count(true);
+        let results = client
+            .with_search_params(params.clone())
+            .search_entity(&server, SearchEntity::Fn("Bobby*".into()))
+            .await
+            .unwrap();
+        assert!(requests.recv().unwrap().starts_with(
+            "GET /entities?fn=Bobby*&sort=name%3Aa%2CregistrationDate%3Ad&count=true "
+        ));
+
+        let next = params
+            .next_page(results.paging_metadata.as_ref().unwrap())
+            .unwrap();
+        client
+            .with_search_params(next)
+            .search_entities(SearchEntity::Fn("Bobby*".into()), Some(&server))
+            .await
+            .unwrap();
+        assert!(requests.recv().unwrap().starts_with(
+            "GET /entities?fn=Bobby*&sort=name%3Aa%2CregistrationDate%3Ad&count=true&cursor=a%2Bb%3D "
+        ));
+
+        // Parameters are not sent by original client.
+        client
+            .search_entity(&server, SearchEntity::Fn("Bobby*".into()))
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entities?fn=Bobby* "));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_url_and_follow() {
+        let (server, requests) = serve(vec![
+            rdap_response(DOMAIN_JSON),
+            rdap_response(r#"{"entitySearchResults":[]}"#),
+        ]);
+        let client = test_client().build().unwrap();
+
+        let response = client
+            .fetch_url(format!("{}domain/example.com", server))
+            .await
+            .unwrap();
+        assert!(matches!(
+            response.object,
+            parser::TopLevelResponse::Object(object) if matches!(*object, parser::Object::Domain(_))
+        ));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domain/example.com "));
+
+        let link: parser::Link = serde_json::from_value(serde_json::json!({
+            "value": format!("{}domain/example.com", server),
+            "rel": "related",
+            "href": "../entities?fn=Bobby*",
+        }))
+        .unwrap();
+        let response = client.follow(&link).await.unwrap();
+        assert!(matches!(
+            response.object,
+            parser::TopLevelResponse::EntitySearchResults(_)
+        ));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entities?fn=Bobby* "));
+
+        let link: parser::Link =
+            serde_json::from_value(serde_json::json!({"href": "domain/example.com"})).unwrap();
+        assert!(matches!(
+            client.follow(&link).await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lookup_domain_with_registrar() {
+        let registrar_json = r#"{"objectClassName":"domain","ldhName":"example.test","entities":[{"objectClassName":"entity","roles":["registrant"]}],"events":[]}"#;
+        let (registrar, registrar_requests) = serve(vec![rdap_response(registrar_json)]);
+        let registry_json = format!(
+            r#"{{"objectClassName":"domain","ldhName":"example.test","entities":[],"events":[],"links":[{{"rel":"self","href":"https://rdap.example/domain/example.test"}},{{"rel":"related","type":"application/rdap+json","href":"{}domain/example.test"}}]}}"#,
+            registrar
+        );
+        let (server, _) = serve(vec![
+            rdap_response(&registry_json),
+            rdap_response(DOMAIN_JSON),
+        ]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        let response = client
+            .lookup_domain_with_registrar("example.test")
+            .await
+            .unwrap();
+        assert!(response.registry.entities.is_empty());
+        let registrar = response.registrar.unwrap().unwrap();
+        assert_eq!(1, registrar.entities.len());
+        assert!(registrar_requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domain/example.test "));
+
+        // Response without link to registrar.
+        let response = client
+            .lookup_domain_with_registrar("example.test")
+            .await
+            .unwrap();
+        assert!(response.registrar.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_search_entities_fan_out() {
+        let entity = |handle: &str, server: &str| {
+            format!(
+                r#"{{"objectClassName":"entity","handle":"{}","links":[{{"rel":"self","href":"https://{}/entity/{}"}}]}}"#,
+                handle, server, handle
+            )
+        };
+        let (first, _) = serve(vec![rdap_response(&format!(
+            r#"{{"entitySearchResults":[{},{}]}}"#,
+            entity("ABC-1", "rdap.first"),
+            entity("ABC-2", "rdap.first")
+        ))]);
+        // The same ABC-2 object, but different ABC-1 object of other registry.
+        let (second, requests) = serve(vec![rdap_response(&format!(
+            r#"{{"entitySearchResults":[{},{},{}]}}"#,
+            entity("ABC-1", "rdap.second"),
+            entity("ABC-2", "rdap.first"),
+            entity("ABC-3", "rdap.second")
+        ))]);
+        let (failing, _) = serve(vec![response("503 Service Unavailable", &[], "")]);
+        let client = test_client().build().unwrap();
+
+        let servers = [
+            first.as_str(),
+            failing.as_str(),
+            second.trim_end_matches('/'),
+        ];
+        let results = client
+            .search_entities_fan_out(SearchEntity::Handle("ABC*".into()), Some(&servers))
+            .await
+            .unwrap();
+        let hits: Vec<_> = results
+            .hits
+            .iter()
+            .map(|hit| (hit.server.as_str(), hit.object.handle.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            vec![
+                (first.as_str(), "ABC-1"),
+                (first.as_str(), "ABC-2"),
+                (second.as_str(), "ABC-1"),
+                (second.as_str(), "ABC-3")
+            ],
+            hits
+        );
+        assert_eq!(1, results.errors.len());
+        assert_eq!(failing, results.errors[0].0);
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entities?handle=ABC* "));
+
+        let (server, requests) = serve(vec![rdap_response(r#"{"entitySearchResults":[]}"#)]);
+        let results = client
+            .search_entities_fan_out(SearchEntity::Fn("John Do*".into()), Some(&[&server]))
+            .await
+            .unwrap();
+        assert!(results.hits.is_empty() && results.errors.is_empty());
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entities?fn=John%20Do* "));
+
+        for search in [SearchEntity::Fn("*".into()), SearchEntity::Fn("a*b".into())] {
+            assert!(matches!(
+                client
+                    .search_entities_fan_out(search, Some(&[&server]))
+                    .await,
+                Err(ClientError::InvalidQuery(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exists_domain() {
+        let (server, requests) = serve(vec![
+            response("200 OK", &["Content-Type: application/rdap+json"], ""),
+            response("404 Not Found", &[], ""),
+            response("405 Method Not Allowed", &[], ""),
+            rdap_response(DOMAIN_JSON),
+        ]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        assert!(client.exists_domain("example.test").await.unwrap());
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("HEAD /domain/example.test "));
+        assert!(!client.exists_domain("missing.test").await.unwrap());
+        requests.recv().unwrap();
+
+        // Fallback to GET when server doesn't support HEAD.
+        assert!(client.exists_domain("example.test").await.unwrap());
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("HEAD /domain/example.test "));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domain/example.test "));
+    }
+
+    #[tokio::test]
+    async fn test_search_domains_stream() {
+        use futures::StreamExt;
+
+        let (other, other_requests) = serve(vec![rdap_response(&format!(
+            r#"{{"domainSearchResults":[{}]}}"#,
+            DOMAIN_JSON.replace("example.com", "example4.test")
+        ))]);
+        let first_page = format!(
+            r#"{{"domainSearchResults":[{},{}],"paging_metadata":{{"links":[{{"rel":"next","href":"https://rdap.example/domains?name=exa*.test&cursor=c2"}}]}}}}"#,
+            DOMAIN_JSON.replace("example.com", "example1.test"),
+            DOMAIN_JSON.replace("example.com", "example2.test")
+        );
+        let second_page = format!(
+            r#"{{"domainSearchResults":[{}],"paging_metadata":{{"links":[{{"rel":"next","href":"{}domains?name=exa*.test&page=3"}}]}}}}"#,
+            DOMAIN_JSON.replace("example.com", "example3.test"),
+            other
+        );
+        let (server, requests) = serve(vec![
+            rdap_response(&first_page),
+            rdap_response(&second_page),
+        ]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        let domains: Vec<_> = client
+            .with_search_params(SearchParams::new().field_set("brief"))
+            .search_domains_stream(SearchDomain::Name("exa*.test".into()))
+            .map(|domain| domain.unwrap().ldh_name.unwrap())
+            .collect()
+            .await;
+        assert_eq!(
+            vec![
+                "example1.test",
+                "example2.test",
+                "example3.test",
+                "example4.test"
+            ],
+            domains
+        );
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains?name=exa*.test&fieldSet=brief "));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains?name=exa*.test&cursor=c2&fieldSet=brief "));
+        assert!(other_requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains?name=exa*.test&page=3 "));
+    }
+
+    #[tokio::test]
+    async fn test_send_query() {
+        let search_json = format!(r#"{{"domainSearchResults":[{}]}}"#, DOMAIN_JSON);
+        let (server, requests) = serve(vec![
+            rdap_response(DOMAIN_JSON),
+            rdap_response(&search_json),
+            rdap_response(&search_json),
+        ]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        let query = RdapQuery::lookup(Lookup::Domain("example.test".into()))
+            .field_set("brief")
+            .header("X-Request-Id", "1");
+        client.send_query::<parser::Domain>(&query).await.unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("GET /domain/example.test "));
+        assert!(request.contains("x-request-id: 1\r\n"));
+
+        let query =
+            RdapQuery::search_domains(SearchDomain::Name("exa*.test".into())).field_set("brief");
+        client
+            .send_query::<parser::DomainSearchResults>(&query)
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /domains?name=exa*.test&fieldSet=brief "));
+
+        let query = RdapQuery::search_entities(SearchEntity::Fn("Bobby*".into())).server(&server);
+        client
+            .send_query::<parser::TopLevelResponse>(&query)
+            .await
+            .unwrap();
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entities?fn=Bobby* "));
+
+        let query = RdapQuery::lookup(Lookup::Asn(1)).header("Invalid Name", "1");
+        assert!(matches!(
+            client.send_query::<parser::AutNum>(&query).await,
+            Err(ClientError::InvalidQuery(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_entity() {
+        let entity_json = r#"{"objectClassName":"entity","handle":"ABC-EXAMPLE"}"#;
+        let (server, requests) = serve(vec![
+            rdap_response(entity_json),
+            response("404 Not Found", &[], ""),
+            rdap_response(entity_json),
+        ]);
+        let overrides = bootstrap::Overrides::new().object_tag("example", &server);
+        let client = test_client()
+            .bootstrap_overrides(overrides)
+            .build()
+            .unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        let entity = client.entity("ABC-EXAMPLE", None).await.unwrap();
+        assert_eq!(Some("ABC-EXAMPLE".into()), entity.handle);
+        assert!(requests
+            .recv()
+            .unwrap()
+            .starts_with("GET /entity/ABC-EXAMPLE "));
+
+        assert!(matches!(
+            client.entity("XYZ-EXAMPLE", None).await,
+            Err(ClientError::NotFound(_))
+        ));
+        requests.recv().unwrap();
+
+        let server = server.trim_end_matches('/');
+        client.entity("ABC 1", Some(server)).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /entity/ABC%201 "));
+        assert!(matches!(
+            client.entity("ABC", None).await,
+            Err(ClientError::NoServer)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_help() {
+        let help_json = r#"{
+            "rdapConformance": ["rdap_level_0", "redacted"],
+            "notices": [{
+                "title": "Terms of Service",
+                "description": ["By using this service you agree to terms."],
+                "links": [{"rel": "terms-of-service", "href": "https://rdap.example/tos"}]
+            }]
+        }"#;
+        let (server, requests) = serve(vec![rdap_response(help_json)]);
+        let client = test_client().build().unwrap();
+        let help = client.help(server.trim_end_matches('/')).await.unwrap();
+        assert!(requests.recv().unwrap().starts_with("GET /help "));
+        let conformance = help.rdap_conformance.as_ref().unwrap();
+        assert!(conformance.has_extension("rdap_level_0"));
+        assert!(conformance.contains(&parser::Extension::Redacted));
+        assert_eq!(
+            "https://rdap.example/tos",
+            help.terms_of_service().unwrap().href
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delegated_stats() {
+        let stats = "ripencc|NL|ipv4|193.0.0.0|2048|19930901|allocated|abc\n";
+        let (url, requests) = serve(vec![response("200 OK", &[], stats)]);
+        let client = test_client().delegated_stats_url(url).build().unwrap();
+        client
+            .set_bootstrap(local_bootstrap("https://rdap.test/"))
+            .await;
+
+        // Lookups covered by IANA bootstrap don't download stats.
+        let lookup = Lookup::Domain("example.test".into());
+        assert!(client
+            .lookup_bootstrap(&lookup)
+            .await
+            .unwrap()
+            .ip
+            .iter()
+            .next()
+            .is_none());
+
+        for _ in 0..2 {
+            let lookup = Lookup::Ip("193.0.6.139".parse().unwrap());
+            let bootstrap = client.lookup_bootstrap(&lookup).await.unwrap();
+            assert_eq!(
+                vec!["https://rdap.db.ripe.net/"],
+                client.lookup_servers(&lookup, &bootstrap).unwrap()
+            );
+        }
+        assert_eq!(1, requests.try_iter().count());
+    }
+
+    #[tokio::test]
+    async fn test_scheme_policy() {
+        let mut bootstrap = local_bootstrap("https://only.example/");
+        bootstrap.dns.insert(
+            vec!["http://a.example/".into(), "https://b.example/".into()],
+            vec!["mixed".into()],
+        );
+        let servers = |policy| {
+            let client = test_client().scheme_policy(policy).build().unwrap();
+            let lookup = Lookup::Domain("example.mixed".into());
+            client
+                .lookup_servers(&lookup, &bootstrap)
+                .unwrap()
+                .join(" ")
+        };
+        assert_eq!(
+            "https://b.example/ http://a.example/",
+            servers(SchemePolicy::PreferHttps)
+        );
+        assert_eq!(
+            "http://a.example/ https://b.example/",
+            servers(SchemePolicy::AsListed)
+        );
+        assert_eq!("https://b.example/", servers(SchemePolicy::HttpsOnly));
+
+        let client = test_client()
+            .scheme_policy(SchemePolicy::HttpsOnly)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            client
+                .query_domain("http://127.0.0.1:1/", "example.com")
+                .await,
+            Err(ClientError::InsecureUrl(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_failover() {
+        let unavailable = || response("503 Service Unavailable", &[], "");
+        let (first, _) = serve(vec![unavailable(), unavailable()]);
+        let (second, _) = serve(vec![rdap_response(DOMAIN_JSON), unavailable()]);
+        let mut bootstrap = local_bootstrap(&first);
+        bootstrap
+            .dns
+            .insert(vec![first.clone(), second.clone()], vec!["failover".into()]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(bootstrap).await;
+
+        let response = client.lookup_domain("example.failover").await.unwrap();
+        assert_eq!(
+            second,
+            format!("{}://{}/", response.url.scheme(), response.url.authority())
+        );
+
+        match client.lookup_domain("example.failover").await {
+            Err(ClientError::AllServersFailed(errors)) => {
+                let servers: Vec<_> = errors.iter().map(|(server, _)| server.clone()).collect();
+                assert_eq!(vec![first, second], servers);
+                assert!(matches!(errors[0].1, ClientError::Server(_)));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lookup_many() {
+        use futures::StreamExt;
+
+        let domain = |name: &str| rdap_response(&DOMAIN_JSON.replace("example.com", name));
+        let (server, requests) = serve(vec![
+            domain("a.test"),
+            response("429 Too Many Requests", &["Retry-After: 0"], ""),
+            domain("b.test"),
+            domain("c.test"),
+        ]);
+        let client = test_client().build().unwrap();
+        client.set_bootstrap(local_bootstrap(&server)).await;
+
+        let lookups = vec![
+            crate::Lookup::Domain("a.test".into()),
+            crate::Lookup::Domain("b.test".into()),
+            crate::Lookup::Domain("c.test".into()),
+            crate::Lookup::Asn(1),
+        ];
+        let results: Vec<_> = client.lookup_many(lookups, 1).collect().await;
+        assert_eq!(4, results.len());
+        for (result, expected) in results.iter().zip(["a.test", "b.test", "c.test"]) {
+            match result.as_ref().map(|response| &response.object) {
+                Ok(parser::Object::Domain(domain)) => {
+                    assert_eq!(Some(expected), domain.ldh_name.as_deref())
+                }
+                other => panic!("unexpected result {:?}", other),
+            }
+        }
+        assert!(matches!(results[3], Err(ClientError::NoServer)));
+        assert!(requests.recv().unwrap().starts_with("GET /domain/a.test "));
+        assert!(requests.recv().unwrap().starts_with("GET /domain/b.test "));
+        assert!(requests.recv().unwrap().starts_with("GET /domain/b.test "));
+        assert!(requests.recv().unwrap().starts_with("GET /domain/c.test "));
+    }
+
+    #[tokio::test]
+    async fn test_middleware() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let (server, requests) = serve(vec![
+            response("503 Service Unavailable", &[], ""),
+            rdap_response(DOMAIN_JSON),
+        ]);
+        let responses = Arc::new(AtomicUsize::new(0));
+        let counter = responses.clone();
+        let client = test_client()
+            .retry_policy(RetryPolicy::new().backoff_base(Duration::from_millis(1)))
+            .map_request(|request| {
+                request.headers_mut().insert(
+                    header::AUTHORIZATION,
+                    header::HeaderValue::from_static("Bearer token"),
+                );
+            })
+            .inspect_response(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })
+            .build()
+            .unwrap();
+
+        client.query_domain(&server, "example.com").await.unwrap();
+        assert_eq!(2, responses.load(Ordering::SeqCst));
+        for _ in 0..2 {
+            let request = requests.recv().unwrap().to_lowercase();
+            assert!(request.contains("authorization: bearer token\r\n"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_sink() {
+        use crate::{MetricsSink, RequestMetrics};
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<(Option<u16>, Option<u64>)>>);
+
+        impl MetricsSink for Arc<Recorder> {
+            fn record(&self, metrics: &RequestMetrics<'_>) {
+                assert_eq!("127.0.0.1", metrics.server);
+                assert!(metrics.error.is_none());
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((metrics.status.map(|status| status.as_u16()), metrics.bytes));
+            }
+        }
+
+        let (server, _) = serve(vec![
+            response("503 Service Unavailable", &[], ""),
+            rdap_response(DOMAIN_JSON),
+            // Without `Content-Length`, body is read until connection is closed.
+            format!(
+                "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Type: application/rdap+json\r\n\r\n{}",
+                DOMAIN_JSON
+            ),
+        ]);
+        let recorder = Arc::new(Recorder::default());
+        let client = test_client()
+            .retry_policy(RetryPolicy::new().backoff_base(Duration::from_millis(1)))
+            .metrics_sink(recorder.clone())
+            .build()
+            .unwrap();
+        client.query_domain(&server, "example.com").await.unwrap();
+        client.query_domain(&server, "example.com").await.unwrap();
+
+        // Body of retried response is not read.
+        let bytes = DOMAIN_JSON.len() as u64;
+        assert_eq!(
+            vec![
+                (Some(503), None),
+                (Some(200), Some(bytes)),
+                (Some(200), Some(bytes))
+            ],
+            *recorder.0.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cache() {
+        let (server, requests) = serve(vec![
+            response(
+                "200 OK",
+                &["Content-Type: application/rdap+json", "ETag: \"v1\""],
+                DOMAIN_JSON,
+            ),
+            response("304 Not Modified", &["ETag: \"v1\""], ""),
+        ]);
+        let cache = crate::ResponseCache::new(10);
+        let client = test_client().cache(cache.clone()).build().unwrap();
+
+        for _ in 0..2 {
+            let domain = client.query_domain(&server, "example.com").await.unwrap();
+            assert_eq!("example.com", domain.ldh_name.unwrap());
+        }
+        assert_eq!(1, cache.len());
+        assert!(!requests
+            .recv()
+            .unwrap()
+            .to_lowercase()
+            .contains("if-none-match"));
+        assert!(requests
+            .recv()
+            .unwrap()
+            .to_lowercase()
+            .contains("if-none-match: \"v1\"\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_without_retry() {
+        let (server, _) = serve(vec![response("503 Service Unavailable", &[], "")]);
+        let client = test_client().build().unwrap();
+        assert!(matches!(
+            client.query_domain(&server, "example.com").await,
+            Err(ClientError::Server(..))
+        ));
+    }
+
+    #[test]
+    fn test_lookup_without_server() {
+        let client = Client::new();
+        futures::executor::block_on(async {
+            client.set_bootstrap(load_bootstrap()).await;
+            assert!(matches!(
+                client.lookup_domain("com.a.b.xxxxxxxxxxxxxx").await,
+                Err(ClientError::NoServer)
+            ));
+            assert!(matches!(
+                client.lookup_asn(0).await,
+                Err(ClientError::NoServer)
+            ));
+            assert!(matches!(
+                client.lookup_entity("TEST-TEST").await,
+                Err(ClientError::NoServer)
+            ));
+        });
+    }
 }
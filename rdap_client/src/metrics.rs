@@ -0,0 +1,122 @@
+use crate::ClientError;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Outcome of one HTTP request made by client. Every retry and followed redirect is reported as
+/// separate request.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct RequestMetrics<'a> {
+    /// Host of requested server.
+    pub server: &'a str,
+    /// Requested URL.
+    pub url: &'a reqwest::Url,
+    /// Time from sending request until response headers were received or request failed.
+    pub duration: Duration,
+    /// Response status code, `None` if request failed without response.
+    pub status: Option<reqwest::StatusCode>,
+    /// Number of bytes of response body read by client, `None` if body was not read, for example
+    /// for redirects and retried responses. On wasm32 size from `Content-Length` header, when
+    /// known.
+    pub bytes: Option<u64>,
+    /// Error if request failed without response.
+    pub error: Option<&'a ClientError>,
+}
+
+/// Receiver of metrics about requests made by client, for example to export them to Prometheus
+/// or StatsD.
+///
+/// ```
+/// use rdap_client::{MetricsSink, RequestMetrics};
+///
+/// struct Printer;
+///
+/// impl MetricsSink for Printer {
+///     fn record(&self, metrics: &RequestMetrics<'_>) {
+///         println!("{} {:?} {:?}", metrics.server, metrics.status, metrics.duration);
+///     }
+/// }
+///
+/// let client = rdap_client::Client::builder().metrics_sink(Printer).build().unwrap();
+/// ```
+pub trait MetricsSink: Send + Sync + 'static {
+    /// Called after every request, when body of response was read or response was dropped. Must
+    /// not block.
+    fn record(&self, metrics: &RequestMetrics<'_>);
+}
+
+impl fmt::Debug for dyn MetricsSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsSink")
+    }
+}
+
+/// Metrics of request with response, that are recorded when they are dropped, so they contain
+/// number of body bytes actually read.
+pub(crate) struct PendingMetrics {
+    sink: Arc<dyn MetricsSink>,
+    url: reqwest::Url,
+    duration: Duration,
+    status: reqwest::StatusCode,
+    bytes: Option<u64>,
+}
+
+impl PendingMetrics {
+    /// Attach metrics to response, so they are recorded by body reader or when response is
+    /// dropped. Response on wasm32 cannot carry them, so they are recorded immediately.
+    pub(crate) fn attach(
+        sink: &Arc<dyn MetricsSink>,
+        url: reqwest::Url,
+        duration: Duration,
+        response: &mut reqwest::Response,
+    ) {
+        #[cfg(not(target_arch = "wasm32"))]
+        let bytes = None;
+        #[cfg(target_arch = "wasm32")]
+        let bytes = response.content_length();
+        let metrics = Self {
+            sink: Arc::clone(sink),
+            url,
+            duration,
+            status: response.status(),
+            bytes,
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        response.extensions_mut().insert(metrics);
+        #[cfg(target_arch = "wasm32")]
+        drop(metrics);
+    }
+
+    /// Take metrics attached to response, when its body is going to be read.
+    pub(crate) fn take(response: &mut reqwest::Response) -> Option<Self> {
+        #[cfg(not(target_arch = "wasm32"))]
+        let metrics = response.extensions_mut().remove::<Self>();
+        #[cfg(target_arch = "wasm32")]
+        let metrics = {
+            let _ = response;
+            None
+        };
+        metrics.map(|mut metrics| {
+            metrics.bytes = Some(0);
+            metrics
+        })
+    }
+
+    pub(crate) fn add_bytes(&mut self, bytes: u64) {
+        *self.bytes.get_or_insert(0) += bytes;
+    }
+}
+
+impl Drop for PendingMetrics {
+    fn drop(&mut self) {
+        self.sink.record(&RequestMetrics {
+            server: self.url.host_str().unwrap_or_default(),
+            url: &self.url,
+            duration: self.duration,
+            status: Some(self.status),
+            bytes: self.bytes,
+            error: None,
+        });
+    }
+}
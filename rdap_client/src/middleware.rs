@@ -0,0 +1,104 @@
+use crate::ClientError;
+use std::fmt;
+use std::sync::Arc;
+
+/// Middleware that can inspect and modify outgoing requests and incoming responses, for example
+/// for custom authentication, logging, request signing or returning cached responses.
+///
+/// Middlewares are called in order in which they were added to `ClientBuilder`, for every attempt
+/// of every request (including retries and followed redirects).
+///
+/// ```
+/// use rdap_client::{Middleware, MiddlewareFuture, Next};
+///
+/// struct Logger;
+///
+/// impl Middleware for Logger {
+///     fn handle<'a>(
+///         &'a self,
+///         request: reqwest::Request,
+///         next: Next<'a>,
+///     ) -> MiddlewareFuture<'a> {
+///         Box::pin(async move {
+///             println!("{} {}", request.method(), request.url());
+///             let response = next.run(request).await?;
+///             println!("{}", response.status());
+///             Ok(response)
+///         })
+///     }
+/// }
+///
+/// let client = rdap_client::Client::builder().middleware(Logger).build().unwrap();
+/// ```
+pub trait Middleware: Send + Sync + 'static {
+    /// Handle request. Call `next.run(request)` to pass request to next middleware and finally
+    /// send it, or return response directly without sending request.
+    fn handle<'a>(&'a self, request: reqwest::Request, next: Next<'a>) -> MiddlewareFuture<'a>;
+}
+
+/// Future returned by middleware. It is not required to be `Send` on wasm32, because browser
+/// futures are not `Send`.
+#[cfg(not(target_arch = "wasm32"))]
+pub type MiddlewareFuture<'a> =
+    futures::future::BoxFuture<'a, Result<reqwest::Response, ClientError>>;
+
+/// Future returned by middleware. It is not required to be `Send` on wasm32, because browser
+/// futures are not `Send`.
+#[cfg(target_arch = "wasm32")]
+pub type MiddlewareFuture<'a> =
+    futures::future::LocalBoxFuture<'a, Result<reqwest::Response, ClientError>>;
+
+impl fmt::Debug for dyn Middleware {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Middleware")
+    }
+}
+
+/// Rest of middleware chain, that ends with sending request by reqwest client.
+pub struct Next<'a> {
+    client: &'a reqwest::Client,
+    middlewares: &'a [Arc<dyn Middleware>],
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(client: &'a reqwest::Client, middlewares: &'a [Arc<dyn Middleware>]) -> Self {
+        Self {
+            client,
+            middlewares,
+        }
+    }
+
+    /// Pass request to next middleware or send it, if this is the last one.
+    pub fn run(self, request: reqwest::Request) -> MiddlewareFuture<'a> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => middleware.handle(request, Next::new(self.client, rest)),
+            None => {
+                let client = self.client;
+                Box::pin(async move { Ok(client.execute(request).await?) })
+            }
+        }
+    }
+}
+
+/// Middleware created from closure that modifies every outgoing request.
+pub(crate) struct MapRequest<F>(pub(crate) F);
+
+impl<F: Fn(&mut reqwest::Request) + Send + Sync + 'static> Middleware for MapRequest<F> {
+    fn handle<'a>(&'a self, mut request: reqwest::Request, next: Next<'a>) -> MiddlewareFuture<'a> {
+        (self.0)(&mut request);
+        next.run(request)
+    }
+}
+
+/// Middleware created from closure that is called with every incoming response.
+pub(crate) struct InspectResponse<F>(pub(crate) F);
+
+impl<F: Fn(&reqwest::Response) + Send + Sync + 'static> Middleware for InspectResponse<F> {
+    fn handle<'a>(&'a self, request: reqwest::Request, next: Next<'a>) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            let response = next.run(request).await?;
+            (self.0)(&response);
+            Ok(response)
+        })
+    }
+}
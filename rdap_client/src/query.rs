@@ -0,0 +1,268 @@
+//! Query that is built independently of client and sent later by `Client::send_query`, for
+//! example from queue of pending work that is serialized between runs.
+
+use crate::{
+    bootstrap, encode_search_value, Client, ClientError, Lookup, Response, SearchDomain,
+    SearchEntity, SearchNameserver, SearchParams,
+};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Object or search queried by `RdapQuery`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum QueryTarget {
+    Lookup(Lookup),
+    SearchDomains(SearchDomain),
+    SearchNameservers(SearchNameserver),
+    SearchEntities(SearchEntity),
+}
+
+impl QueryTarget {
+    /// Returns path of query relative to server base URL.
+    fn path(&self) -> Result<String, ClientError> {
+        let (path, key, value) = match self {
+            Self::Lookup(lookup) => return Ok(lookup.path()),
+            Self::SearchDomains(search) => {
+                search.validate()?;
+                ("domains?", search.key(), search.clone().value())
+            }
+            Self::SearchNameservers(search) => {
+                search.validate()?;
+                ("nameservers?", search.key(), search.clone().value())
+            }
+            Self::SearchEntities(search) => {
+                search.validate()?;
+                ("entities?", search.key(), search.clone().value())
+            }
+        };
+        let mut path = path.to_owned();
+        path.push_str(key);
+        path.push('=');
+        encode_search_value(&value, &mut path);
+        Ok(path)
+    }
+
+    /// Returns lookup used to find server in bootstrap.
+    fn routing(&self) -> Result<Lookup, ClientError> {
+        match self {
+            Self::Lookup(lookup) => Ok(lookup.clone()),
+            Self::SearchDomains(search) => Ok(Lookup::Domain(search.registry_domain()?.into())),
+            Self::SearchNameservers(search) => Ok(Lookup::Domain(search.registry_domain()?.into())),
+            Self::SearchEntities(SearchEntity::Handle(handle)) => {
+                Ok(Lookup::Entity(handle.clone()))
+            }
+            Self::SearchEntities(SearchEntity::Fn(..)) => Err(ClientError::InvalidQuery(
+                "server must be given for search by full name".into(),
+            )),
+        }
+    }
+}
+
+/// Query with its options, that can be built without client, stored or serialized (for example
+/// as JSON) and sent later by `Client::send_query`.
+///
+/// ```no_run
+/// # async fn example(client: rdap_client::Client) -> Result<(), rdap_client::ClientError> {
+/// use rdap_client::{RdapQuery, SearchDomain};
+/// use std::time::Duration;
+///
+/// let query = RdapQuery::search_domains(SearchDomain::Name("exa*.com".into()))
+///     .field_set("brief")
+///     .deadline(Duration::from_secs(30));
+/// let json = serde_json::to_string(&query).unwrap();
+///
+/// let query: RdapQuery = serde_json::from_str(&json).unwrap();
+/// let response = client
+///     .send_query::<rdap_types::DomainSearchResults>(&query)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RdapQuery {
+    pub target: QueryTarget,
+    /// RDAP server base URL, when not set, server is found in bootstrap.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub server: Option<String>,
+    /// Sorting, paging and field set parameters, sent just with searches.
+    #[serde(default)]
+    pub search_params: SearchParams,
+    /// Headers sent in addition to headers of client.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    /// Deadline for whole query, overrides `ClientBuilder::deadline`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deadline: Option<Duration>,
+}
+
+impl RdapQuery {
+    /// Creates query for given target with default options.
+    pub fn new(target: QueryTarget) -> Self {
+        Self {
+            target,
+            server: None,
+            search_params: SearchParams::default(),
+            headers: Vec::new(),
+            deadline: None,
+        }
+    }
+
+    /// Query for object, like `Lookup::Domain("example.com".into())`.
+    pub fn lookup(lookup: Lookup) -> Self {
+        Self::new(QueryTarget::Lookup(lookup))
+    }
+
+    /// Search for domains, server is found in bootstrap like for `Client::search_domains`.
+    pub fn search_domains(search: SearchDomain) -> Self {
+        Self::new(QueryTarget::SearchDomains(search))
+    }
+
+    /// Search for nameservers, server is found in bootstrap like for
+    /// `Client::search_nameservers`.
+    pub fn search_nameservers(search: SearchNameserver) -> Self {
+        Self::new(QueryTarget::SearchNameservers(search))
+    }
+
+    /// Search for entities, server is found in bootstrap like for `Client::search_entities`.
+    pub fn search_entities(search: SearchEntity) -> Self {
+        Self::new(QueryTarget::SearchEntities(search))
+    }
+
+    /// Query given RDAP server instead of server from bootstrap.
+    pub fn server<S: Into<String>>(mut self, server: S) -> Self {
+        self.server = Some(server.into());
+        self
+    }
+
+    /// Sorting and paging parameters of search.
+    pub fn search_params(mut self, search_params: SearchParams) -> Self {
+        self.search_params = search_params;
+        self
+    }
+
+    /// Field set (RFC 8982) of search results, like `brief`.
+    pub fn field_set<S: Into<String>>(mut self, field_set: S) -> Self {
+        self.search_params = self.search_params.field_set(field_set);
+        self
+    }
+
+    /// Add header sent with this query. Can be called multiple times.
+    pub fn header<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Deadline for whole query, including bootstrap fetching, redirects and retries.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    fn header_map(&self) -> Result<HeaderMap, ClientError> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| ClientError::InvalidQuery(Box::new(e)))?;
+            let value =
+                HeaderValue::from_str(value).map_err(|e| ClientError::InvalidQuery(Box::new(e)))?;
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+impl From<Lookup> for RdapQuery {
+    fn from(lookup: Lookup) -> Self {
+        Self::lookup(lookup)
+    }
+}
+
+impl Client {
+    /// Send query built by `RdapQuery` and parse response as `T`, for example `parser::Domain`
+    /// or `parser::TopLevelResponse` when type of response is not known.
+    pub async fn send_query<T: DeserializeOwned>(
+        &self,
+        query: &RdapQuery,
+    ) -> Result<Response<T>, ClientError> {
+        let mut client = self.with_search_params(query.search_params.clone());
+        client.headers.extend(query.header_map()?);
+        if query.deadline.is_some() {
+            client.deadline = query.deadline;
+        }
+        let path = query.target.path()?;
+        let is_search = !matches!(query.target, QueryTarget::Lookup(_));
+        let send = |server: &str| {
+            let mut request = client.client.get(format!("{}{}", server, path));
+            if is_search {
+                request = client.add_search_params(request);
+            }
+            client.send_response(request)
+        };
+        let future = client.within_deadline(async {
+            if let Some(server) = &query.server {
+                record!("server", server.as_str());
+                return send(&bootstrap::base_url(server)).await;
+            }
+            let lookup = query.target.routing()?;
+            let bootstrap = client.lookup_bootstrap(&lookup).await?;
+            let servers = client.lookup_servers(&lookup, &bootstrap)?;
+            Self::with_failover(servers, send).await
+        });
+        instrument!(
+            future,
+            "rdap_query",
+            query = %path,
+            server = tracing::field::Empty
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryTarget, RdapQuery};
+    use crate::{Lookup, SearchDomain, SearchEntity, SearchParams, SortOrder};
+    use std::time::Duration;
+
+    #[test]
+    fn test_path() {
+        let query = RdapQuery::lookup(Lookup::Domain("example.com".into()));
+        assert_eq!("domain/example.com", query.target.path().unwrap());
+        let query = RdapQuery::search_entities(SearchEntity::Fn("Bobby Joe*".into()));
+        assert_eq!("entities?fn=Bobby%20Joe*", query.target.path().unwrap());
+        assert!(query.target.routing().is_err());
+        let query = RdapQuery::search_domains(SearchDomain::Name("e*a.test".into()));
+        assert!(query.target.path().is_err());
+    }
+
+    #[test]
+    fn test_serialize() {
+        let query = RdapQuery::search_domains(SearchDomain::Name("exa*.test".into()))
+            .server("https://rdap.example/")
+            .search_params(SearchParams::new().sort("name", SortOrder::Descending))
+            .field_set("brief")
+            .header("X-Request-Id", "1")
+            .deadline(Duration::from_secs(5));
+        let json = serde_json::to_string(&query).unwrap();
+        let query: RdapQuery = serde_json::from_str(&json).unwrap();
+        assert!(matches!(
+            query.target,
+            QueryTarget::SearchDomains(SearchDomain::Name(ref name)) if name == "exa*.test"
+        ));
+        assert_eq!(Some("https://rdap.example/"), query.server.as_deref());
+        assert_eq!(
+            vec![
+                ("sort", "name:d".to_owned()),
+                ("fieldSet", "brief".to_owned())
+            ],
+            query.search_params.query()
+        );
+        assert_eq!(1, query.header_map().unwrap().len());
+        assert_eq!(Some(Duration::from_secs(5)), query.deadline);
+
+        let query: RdapQuery = serde_json::from_str(r#"{"target":{"Lookup":{"Asn":1}}}"#).unwrap();
+        assert!(matches!(query.target, QueryTarget::Lookup(Lookup::Asn(1))));
+    }
+}
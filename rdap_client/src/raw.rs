@@ -0,0 +1,75 @@
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::value::RawValue;
+
+/// Parsed object together with original JSON returned by server, for example for archiving
+/// exact server output or reading members that are not part of parsed types.
+///
+/// ```no_run
+/// use rdap_client::{Client, Lookup, Raw};
+///
+/// # async fn example() -> Result<(), rdap_client::ClientError> {
+/// let client = Client::new();
+/// let response = client
+///     .lookup::<Raw<rdap_types::Object>>(Lookup::Domain("example.com".into()))
+///     .await?;
+/// println!("{}", response.object.json());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Raw<T> {
+    /// Parsed object.
+    pub object: T,
+    json: Box<RawValue>,
+}
+
+impl<T> Raw<T> {
+    /// Original JSON as returned by server.
+    pub fn json(&self) -> &str {
+        self.json.get()
+    }
+
+    /// Original JSON converted to generic JSON value.
+    pub fn to_value(&self) -> serde_json::Result<serde_json::Value> {
+        serde_json::from_str(self.json.get())
+    }
+
+    /// Returns just parsed object.
+    pub fn into_object(self) -> T {
+        self.object
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Raw<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = Box::<RawValue>::deserialize(deserializer)?;
+        let object = serde_json::from_str(json.get()).map_err(D::Error::custom)?;
+        Ok(Self { object, json })
+    }
+}
+
+/// Serialized as original JSON.
+impl<T> Serialize for Raw<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.json.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Raw;
+    use crate::parser;
+
+    #[test]
+    fn test_raw() {
+        let json = r#"{"objectClassName": "nameserver", "ldhName": "ns1.example.com", "x": 1}"#;
+        let raw: Raw<parser::Nameserver> = serde_json::from_str(json).unwrap();
+        assert_eq!("ns1.example.com", raw.object.ldh_name);
+        assert_eq!(json, raw.json());
+        assert_eq!(1, raw.to_value().unwrap()["x"]);
+        assert_eq!(json, serde_json::to_string(&raw).unwrap());
+
+        assert!(serde_json::from_str::<Raw<parser::Nameserver>>("[]").is_err());
+    }
+}
@@ -0,0 +1,158 @@
+//! Offline replay of previously saved responses, for deterministic tests without network.
+
+use crate::{encode_segment as encode, ClientError, Middleware, MiddlewareFuture, Next};
+use reqwest::header;
+use reqwest::ResponseBuilderExt;
+use std::path::PathBuf;
+
+/// First path segments of RDAP queries, everything before them is server base URL.
+const QUERY_TYPES: [&str; 10] = [
+    "domain",
+    "ip",
+    "autnum",
+    "entity",
+    "nameserver",
+    "domains",
+    "nameservers",
+    "entities",
+    "help",
+    "arin_originas0_networksbyoriginas",
+];
+
+/// Types which arguments are domain names, that are case insensitive.
+const CASE_INSENSITIVE_TYPES: [&str; 4] = ["domain", "nameserver", "domains", "nameservers"];
+
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|hex| {
+            std::str::from_utf8(hex)
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        });
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Returns key of RDAP query independent of server, like `domain/example.com`,
+/// `ip/192.0.2.0%2F24` or `domains/name=exam%2A.cz`. Returns `None` when URL is not RDAP query.
+pub(crate) fn query_key(url: &reqwest::Url) -> Option<String> {
+    let segments: Vec<_> = url.path_segments()?.collect();
+    let position = segments
+        .iter()
+        .position(|segment| QUERY_TYPES.contains(segment))?;
+    let query_type = segments[position];
+    let lowercase = CASE_INSENSITIVE_TYPES.contains(&query_type);
+    let normalize = |value: String| match lowercase {
+        true => value.to_lowercase(),
+        false => value,
+    };
+
+    let mut key = query_type.to_owned();
+    let argument: Vec<_> = segments[position + 1..]
+        .iter()
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| decode(segment))
+        .collect();
+    if !argument.is_empty() {
+        key.push('/');
+        encode(&normalize(argument.join("/")), &mut key);
+    }
+
+    let mut query: Vec<_> = url.query_pairs().collect();
+    query.sort();
+    for (i, (name, value)) in query.into_iter().enumerate() {
+        key.push(if i == 0 { '/' } else { '&' });
+        encode(&name, &mut key);
+        key.push('=');
+        encode(&normalize(value.into_owned()), &mut key);
+    }
+    Some(key)
+}
+
+/// Middleware that returns responses from files instead of sending requests. Response for query
+/// is read from `<dir>/<key>.json`, where key is returned by `query_key`, for example
+/// `<dir>/domain/example.com.json`. When file doesn't exist, 404 response is returned.
+pub(crate) struct Replay {
+    pub(crate) dir: PathBuf,
+}
+
+impl Replay {
+    pub(crate) fn path(&self, url: &reqwest::Url) -> Option<PathBuf> {
+        Some(self.dir.join(format!("{}.json", query_key(url)?)))
+    }
+}
+
+impl Middleware for Replay {
+    fn handle<'a>(&'a self, request: reqwest::Request, _next: Next<'a>) -> MiddlewareFuture<'a> {
+        Box::pin(async move {
+            let body = self.path(request.url()).and_then(|path| {
+                debug!(path = %path.display(), "replaying response");
+                std::fs::read(path).ok()
+            });
+            let response = http::Response::builder().url(request.url().clone());
+            let response = match body {
+                Some(body) => response
+                    .status(reqwest::StatusCode::OK)
+                    .header(header::CONTENT_TYPE, "application/rdap+json")
+                    .body(body),
+                None => response
+                    .status(reqwest::StatusCode::NOT_FOUND)
+                    .body(Vec::new()),
+            };
+            // Builder with valid status code and header cannot fail.
+            Ok::<_, ClientError>(response.unwrap().into())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::query_key;
+
+    fn key(url: &str) -> String {
+        query_key(&url.parse().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_query_key() {
+        assert_eq!(
+            "domain/example.com",
+            key("https://rdap.example/domain/Example.COM")
+        );
+        assert_eq!(
+            "domain/example.com",
+            key("https://rdap.example/registry/domain/example.com/")
+        );
+        assert_eq!(
+            "ip/192.0.2.0%2F24",
+            key("https://rdap.example/ip/192.0.2.0/24")
+        );
+        assert_eq!(
+            "entity/ABC-RIPE",
+            key("https://rdap.example/entity/ABC-RIPE")
+        );
+        assert_eq!(
+            "domains/name=exam%2A.cz",
+            key("https://rdap.example/domains?name=EXAM*.cz")
+        );
+        assert_eq!(
+            "entities/fn=A%20B&handle=C",
+            key("https://rdap.example/entities?handle=C&fn=A%20B")
+        );
+        assert_eq!("help", key("https://rdap.example/help/"));
+        assert!(query_key(&"https://rdap.example/other".parse().unwrap()).is_none());
+    }
+}
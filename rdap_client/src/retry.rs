@@ -0,0 +1,410 @@
+use crate::rt::{SystemTime, UNIX_EPOCH};
+use crate::ClientError;
+use chrono::{DateTime, Utc};
+use reqwest::{header, StatusCode};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Class of failed attempt returned by retry classifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryClass {
+    /// Request can succeed when it is sent again.
+    Retryable,
+    /// Request will fail again, so it is not retried.
+    Permanent,
+}
+
+type ClassifierFn =
+    dyn Fn(&Result<reqwest::Response, ClientError>) -> Option<RetryClass> + Send + Sync;
+
+#[derive(Clone)]
+struct Classifier(Arc<ClassifierFn>);
+
+impl std::fmt::Debug for Classifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Classifier(..)")
+    }
+}
+
+/// Policy that controls if and how failed requests are retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    backoff_base: Duration,
+    max_backoff: Duration,
+    jitter: bool,
+    retry_connect_errors: bool,
+    retry_timeouts: bool,
+    retry_server_errors: bool,
+    respect_retry_after: bool,
+    classifier: Option<Classifier>,
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts with exponential backoff starting at 500 ms with jitter. Connect errors,
+    /// reset connections, timeouts, 502, 503 and 504 responses and 429 responses with
+    /// `Retry-After` header are retried.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            backoff_base: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            jitter: true,
+            retry_connect_errors: true,
+            retry_timeouts: true,
+            retry_server_errors: true,
+            respect_retry_after: true,
+            classifier: None,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates new `RetryPolicy` with default configuration.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Policy that never retries (just one attempt is made).
+    pub fn never() -> Self {
+        Self::new().max_attempts(1)
+    }
+
+    /// Maximum number of attempts including the first one. Zero is handled like one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Delay before the first retry, every next retry doubles the delay.
+    pub fn backoff_base(mut self, backoff_base: Duration) -> Self {
+        self.backoff_base = backoff_base;
+        self
+    }
+
+    /// Maximum delay between two attempts.
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// When enabled, delay is randomly chosen between half and full computed backoff, so
+    /// clients don't retry at the same time.
+    pub fn jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Retry requests that failed during connecting to server or because connection was reset.
+    pub fn retry_connect_errors(mut self, retry: bool) -> Self {
+        self.retry_connect_errors = retry;
+        self
+    }
+
+    /// Retry requests that timed out.
+    pub fn retry_timeouts(mut self, retry: bool) -> Self {
+        self.retry_timeouts = retry;
+        self
+    }
+
+    /// Retry requests when server returns 502, 503 or 504 status code. Other 5xx status codes
+    /// usually mean permanent error, retry them with `classifier` if needed.
+    pub fn retry_server_errors(mut self, retry: bool) -> Self {
+        self.retry_server_errors = retry;
+        self
+    }
+
+    /// When server returns 429 status code with `Retry-After` header, wait for time from the
+    /// header and retry request. When the time is longer than `max_backoff` or header is missing,
+    /// request is not retried. Enabled by default.
+    pub fn respect_retry_after(mut self, respect: bool) -> Self {
+        self.respect_retry_after = respect;
+        self
+    }
+
+    /// Set callback that decides if failed attempt should be retried. It is called with response
+    /// with error status code (4xx or 5xx) or with error. When it returns `None`, attempt is
+    /// classified by other options of this policy.
+    ///
+    /// ```
+    /// use rdap_client::{ClientError, RetryClass, RetryPolicy};
+    ///
+    /// // Also retry 500 from flaky server, but never retry timeouts.
+    /// let policy = RetryPolicy::new().classifier(|result| match result {
+    ///     Ok(response) if response.status() == 500 => Some(RetryClass::Retryable),
+    ///     Err(ClientError::Reqwest(e)) if e.is_timeout() => Some(RetryClass::Permanent),
+    ///     _ => None,
+    /// });
+    /// ```
+    pub fn classifier<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&Result<reqwest::Response, ClientError>) -> Option<RetryClass>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.classifier = Some(Classifier(Arc::new(classifier)));
+        self
+    }
+
+    pub(crate) fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Delay before next attempt, `attempt` is number of already made attempts (starts from one).
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let multiplier = 1u32
+            .checked_shl(attempt.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let backoff = self
+            .backoff_base
+            .checked_mul(multiplier)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            backoff
+        }
+    }
+
+    /// Returns how long to wait before next attempt or `None` if request should not be retried.
+    pub(crate) fn retry_delay(
+        &self,
+        result: &Result<reqwest::Response, ClientError>,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if self.classify(result)? == RetryClass::Permanent {
+            return None;
+        }
+        match result {
+            Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                match retry_after(response.headers()) {
+                    Some(retry_after) if retry_after <= self.max_backoff => Some(retry_after),
+                    Some(_) => None,
+                    None => Some(self.backoff(attempt)),
+                }
+            }
+            _ => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Returns class of failed attempt or `None` for successful or redirect response.
+    fn classify(&self, result: &Result<reqwest::Response, ClientError>) -> Option<RetryClass> {
+        if let Ok(response) = result {
+            if !response.status().is_client_error() && !response.status().is_server_error() {
+                return None;
+            }
+        }
+        if let Some(class) = self
+            .classifier
+            .as_ref()
+            .and_then(|classifier| (classifier.0)(result))
+        {
+            return Some(class);
+        }
+        let retryable = match result {
+            Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+                self.respect_retry_after && retry_after(response.headers()).is_some()
+            }
+            Ok(response) => self.is_retryable_status(response.status()),
+            Err(ClientError::Reqwest(error)) => self.is_retryable_error(error),
+            Err(_) => false,
+        };
+        Some(match retryable {
+            true => RetryClass::Retryable,
+            false => RetryClass::Permanent,
+        })
+    }
+
+    pub(crate) fn is_retryable_error(&self, error: &reqwest::Error) -> bool {
+        (self.retry_connect_errors && (is_connect_error(error) || is_connection_reset(error)))
+            || (self.retry_timeouts && error.is_timeout())
+    }
+
+    pub(crate) fn is_retryable_status(&self, status: StatusCode) -> bool {
+        self.retry_server_errors
+            && matches!(
+                status,
+                StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            )
+    }
+}
+
+/// Returns true if connection was closed by server or network during request, so request can
+/// be sent again on new connection.
+fn is_connection_reset(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(error) = source {
+        if let Some(error) = error.downcast_ref::<std::io::Error>() {
+            return matches!(
+                error.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+            );
+        }
+        source = error.source();
+    }
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn is_connect_error(error: &reqwest::Error) -> bool {
+    error.is_connect()
+}
+
+/// Browser doesn't distinguish connect errors, so every failed fetch is handled like one.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn is_connect_error(error: &reqwest::Error) -> bool {
+    error.is_request()
+}
+
+/// Parse `Retry-After` header value, that can be number of seconds or HTTP date.
+pub(crate) fn retry_after(headers: &header::HeaderMap) -> Option<Duration> {
+    let value = headers.get(header::RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        date.with_timezone(&Utc)
+            .signed_duration_since(Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+/// Returns pseudo-random number from interval [0, 1). Good enough for jitter, without need of
+/// additional dependency.
+fn random_fraction() -> f64 {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(time) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u32(time.subsec_nanos());
+    }
+    (hasher.finish() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new()
+            .backoff_base(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1))
+            .jitter(false);
+        assert_eq!(Duration::from_millis(100), policy.backoff(1));
+        assert_eq!(Duration::from_millis(200), policy.backoff(2));
+        assert_eq!(Duration::from_millis(400), policy.backoff(3));
+        assert_eq!(Duration::from_secs(1), policy.backoff(5));
+        assert_eq!(Duration::from_secs(1), policy.backoff(100));
+    }
+
+    #[test]
+    fn test_backoff_jitter() {
+        let policy = RetryPolicy::new().backoff_base(Duration::from_millis(100));
+        for _ in 0..100 {
+            let backoff = policy.backoff(2);
+            assert!(backoff >= Duration::from_millis(100));
+            assert!(backoff <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_retryable_status() {
+        let policy = RetryPolicy::new();
+        assert!(policy.is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(policy.is_retryable_status(StatusCode::GATEWAY_TIMEOUT));
+        assert!(!policy.is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!policy.is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!policy
+            .retry_server_errors(false)
+            .is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    #[test]
+    fn test_classify() {
+        let response = |status: u16, retry_after: Option<&str>| {
+            let mut response = http::Response::builder().status(status);
+            if let Some(retry_after) = retry_after {
+                response = response.header(header::RETRY_AFTER, retry_after);
+            }
+            Ok(reqwest::Response::from(response.body(Vec::new()).unwrap()))
+        };
+        let policy = RetryPolicy::new().jitter(false);
+        assert_eq!(None, policy.classify(&response(200, None)));
+        assert_eq!(
+            Some(RetryClass::Retryable),
+            policy.classify(&response(502, None))
+        );
+        for status in [400, 404, 422, 500] {
+            assert_eq!(
+                Some(RetryClass::Permanent),
+                policy.classify(&response(status, None))
+            );
+        }
+        assert_eq!(
+            Some(Duration::from_secs(1)),
+            policy.retry_delay(&response(429, Some("1")), 1)
+        );
+        assert_eq!(
+            Some(RetryClass::Permanent),
+            policy.classify(&response(429, None))
+        );
+        assert_eq!(None, policy.retry_delay(&response(429, Some("60")), 1));
+        assert_eq!(
+            None,
+            policy
+                .clone()
+                .respect_retry_after(false)
+                .retry_delay(&response(429, Some("1")), 1)
+        );
+
+        let policy = policy.classifier(|result| match result {
+            Ok(response) if response.status() == 500 => Some(RetryClass::Retryable),
+            Ok(response) if response.status() == 503 => Some(RetryClass::Permanent),
+            _ => None,
+        });
+        assert!(policy.retry_delay(&response(500, None), 1).is_some());
+        assert!(policy.retry_delay(&response(503, None), 1).is_none());
+        assert!(policy.retry_delay(&response(504, None), 1).is_some());
+        assert!(policy.retry_delay(&response(404, None), 1).is_none());
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut headers = header::HeaderMap::new();
+        assert_eq!(None, retry_after(&headers));
+
+        headers.insert(header::RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(Some(Duration::from_secs(120)), retry_after(&headers));
+
+        headers.insert(
+            header::RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(Some(Duration::from_secs(0)), retry_after(&headers));
+
+        let future = (Utc::now() + chrono::Duration::seconds(100)).to_rfc2822();
+        headers.insert(header::RETRY_AFTER, future.parse().unwrap());
+        let retry_after = retry_after(&headers).unwrap();
+        assert!(retry_after > Duration::from_secs(90) && retry_after <= Duration::from_secs(100));
+
+        headers.insert(header::RETRY_AFTER, "invalid".parse().unwrap());
+        assert_eq!(None, super::retry_after(&headers));
+    }
+
+    #[test]
+    fn test_max_attempts() {
+        assert_eq!(1, RetryPolicy::never().get_max_attempts());
+        assert_eq!(1, RetryPolicy::new().max_attempts(0).get_max_attempts());
+    }
+}
@@ -0,0 +1,88 @@
+//! [`tower::Service`](https://docs.rs/tower/latest/tower/trait.Service.html) adapter, so client
+//! can be composed with standard tower layers (timeout, load-shed, retry, buffer etc.)
+
+use crate::{parser, Client, ClientError, Lookup, Response};
+use std::task::{Context, Poll};
+
+/// Request for [`Client`] used as tower service.
+#[derive(Debug, Clone)]
+pub struct RdapRequest {
+    /// Object to query.
+    pub lookup: Lookup,
+    /// RDAP server base URL, when not set, server is found in bootstrap.
+    pub server: Option<String>,
+}
+
+impl RdapRequest {
+    /// Creates request that finds server in bootstrap.
+    pub fn new(lookup: Lookup) -> Self {
+        Self {
+            lookup,
+            server: None,
+        }
+    }
+
+    /// Query given RDAP server instead of server from bootstrap.
+    pub fn server<S: Into<String>>(mut self, server: S) -> Self {
+        self.server = Some(server.into());
+        self
+    }
+}
+
+impl From<Lookup> for RdapRequest {
+    fn from(lookup: Lookup) -> Self {
+        Self::new(lookup)
+    }
+}
+
+/// Response of [`Client`] used as tower service.
+pub type RdapResponse = Response<parser::Object>;
+
+#[cfg(not(target_arch = "wasm32"))]
+type ServiceFuture = futures::future::BoxFuture<'static, Result<RdapResponse, ClientError>>;
+#[cfg(target_arch = "wasm32")]
+type ServiceFuture = futures::future::LocalBoxFuture<'static, Result<RdapResponse, ClientError>>;
+
+impl tower_service::Service<RdapRequest> for Client {
+    type Response = RdapResponse;
+    type Error = ClientError;
+    type Future = ServiceFuture;
+
+    /// Client is always ready, use tower layers for limiting concurrency or rate.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: RdapRequest) -> Self::Future {
+        let client = self.clone();
+        Box::pin(async move {
+            match request.server {
+                Some(server) => {
+                    let url = format!("{}{}", server, request.lookup.path());
+                    client.send_response(client.client.get(&url)).await
+                }
+                None => client.lookup(request.lookup).await,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RdapRequest, RdapResponse};
+    use crate::{Client, ClientError, Lookup};
+    use tower_service::Service;
+
+    #[test]
+    fn test_service() {
+        fn is_service<S: Service<RdapRequest, Response = RdapResponse, Error = ClientError>>() {}
+        is_service::<Client>(); // compiles only if true
+    }
+
+    #[test]
+    fn test_request() {
+        let request = RdapRequest::from(Lookup::Asn(1)).server("https://rdap.example/");
+        assert_eq!(Some("https://rdap.example/"), request.server.as_deref());
+        assert_eq!("autnum/1", request.lookup.path());
+    }
+}
@@ -0,0 +1,273 @@
+//! Incremental parsing of search results.
+//!
+//! Search responses can be really big, so instead of buffering whole body, objects from search
+//! results array are extracted one by one as chunks of body arrive and every object is parsed
+//! separately.
+
+use crate::body::Chunks;
+use crate::{parser, ClientError};
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+
+#[derive(Debug, PartialEq)]
+enum State {
+    /// Looking for search results key in top level object.
+    Searching,
+    /// Inside of search results array.
+    Array,
+    /// Search results array was closed, rest of body is ignored.
+    Done,
+    /// Array contains unbalanced closing bracket, rest of body is ignored.
+    Invalid,
+}
+
+/// Extracts raw JSON values of array stored under `key` in top level object.
+pub(crate) struct ArrayScanner {
+    key: &'static str,
+    state: State,
+    depth: usize,
+    in_string: bool,
+    escape: bool,
+    /// Current string in top level object, can be key.
+    string: Vec<u8>,
+    /// Last string in top level object was key that we are looking for.
+    key_matches: bool,
+    /// Colon after matching key was found, so next value is search results.
+    value_follows: bool,
+    element: Vec<u8>,
+}
+
+impl ArrayScanner {
+    pub(crate) fn new(key: &'static str) -> Self {
+        Self {
+            key,
+            state: State::Searching,
+            depth: 0,
+            in_string: false,
+            escape: false,
+            string: Vec::new(),
+            key_matches: false,
+            value_follows: false,
+            element: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.state == State::Done
+    }
+
+    pub(crate) fn is_invalid(&self) -> bool {
+        self.state == State::Invalid
+    }
+
+    pub(crate) fn is_in_array(&self) -> bool {
+        self.state == State::Array
+    }
+
+    /// Process next chunk of body and push all complete array elements to `elements`.
+    pub(crate) fn push(&mut self, chunk: &[u8], elements: &mut VecDeque<Vec<u8>>) {
+        for &byte in chunk {
+            match self.state {
+                State::Searching => self.search(byte),
+                State::Array => self.array(byte, elements),
+                State::Done | State::Invalid => return,
+            }
+        }
+    }
+
+    fn search(&mut self, byte: u8) {
+        if self.in_string {
+            if self.escape {
+                self.escape = false;
+            } else if byte == b'\\' {
+                self.escape = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+                if self.depth == 1 {
+                    self.key_matches = self.string == self.key.as_bytes();
+                }
+                return;
+            }
+            if self.depth == 1 {
+                self.string.push(byte);
+            }
+            return;
+        }
+
+        match byte {
+            b'"' => {
+                self.in_string = true;
+                self.string.clear();
+            }
+            b':' if self.depth == 1 => {
+                self.value_follows = self.key_matches;
+                self.key_matches = false;
+            }
+            b'[' if self.depth == 1 && self.value_follows => {
+                self.depth += 1;
+                self.value_follows = false;
+                self.state = State::Array;
+            }
+            b'{' | b'[' => {
+                self.depth += 1;
+                self.value_follows = false;
+            }
+            b'}' | b']' => self.depth = self.depth.saturating_sub(1),
+            byte if !byte.is_ascii_whitespace() => self.value_follows = false,
+            _ => {}
+        }
+    }
+
+    fn array(&mut self, byte: u8, elements: &mut VecDeque<Vec<u8>>) {
+        if self.in_string {
+            if self.escape {
+                self.escape = false;
+            } else if byte == b'\\' {
+                self.escape = true;
+            } else if byte == b'"' {
+                self.in_string = false;
+            }
+            self.element.push(byte);
+            return;
+        }
+
+        match byte {
+            b',' if self.depth == 2 => self.finish_element(elements),
+            b']' if self.depth == 2 => {
+                self.finish_element(elements);
+                self.depth -= 1;
+                self.state = State::Done;
+            }
+            byte if byte.is_ascii_whitespace() && self.depth == 2 => {}
+            b'}' | b']' if self.depth == 2 => self.state = State::Invalid,
+            byte => {
+                match byte {
+                    b'"' => self.in_string = true,
+                    b'{' | b'[' => self.depth += 1,
+                    b'}' | b']' => self.depth -= 1,
+                    _ => {}
+                }
+                self.element.push(byte);
+            }
+        }
+    }
+
+    fn finish_element(&mut self, elements: &mut VecDeque<Vec<u8>>) {
+        if !self.element.is_empty() {
+            elements.push_back(std::mem::take(&mut self.element));
+        }
+    }
+}
+
+/// Returns stream of objects from search results array stored under `key`.
+pub(crate) fn search_results<T: DeserializeOwned>(
+    response: reqwest::Response,
+    key: &'static str,
+    max_size: Option<u64>,
+    options: parser::ParseOptions,
+) -> impl Stream<Item = Result<T, ClientError>> {
+    let elements: VecDeque<Vec<u8>> = VecDeque::new();
+    let chunks = Chunks::new(response, max_size);
+    let state = Some((chunks, ArrayScanner::new(key), elements));
+
+    futures::stream::unfold(state, move |state| async move {
+        let (mut chunks, mut scanner, mut elements) = state?;
+        loop {
+            if let Some(element) = elements.pop_front() {
+                return Some(match options.parse_slice(&element) {
+                    Ok(object) => (Ok(object), Some((chunks, scanner, elements))),
+                    Err(e) => (Err(chunks.into_head().into_error(Some(e))), None),
+                });
+            }
+
+            if scanner.is_invalid() {
+                let error = serde::de::Error::custom("unbalanced brackets in search results");
+                return Some((Err(chunks.into_head().into_error(Some(error))), None));
+            }
+
+            if scanner.is_done() {
+                return None;
+            }
+
+            match chunks.next().await {
+                Ok(Some(chunk)) => scanner.push(&chunk, &mut elements),
+                Ok(None) if scanner.is_in_array() => {
+                    let error = serde::de::Error::custom("EOF while parsing search results");
+                    return Some((Err(chunks.into_head().into_error(Some(error))), None));
+                }
+                // Response doesn't contain search results, so there is nothing to return.
+                Ok(None) => return None,
+                Err(e) => return Some((Err(e), None)),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArrayScanner;
+    use std::collections::VecDeque;
+
+    fn scan(key: &'static str, json: &str, chunk_size: usize) -> Vec<String> {
+        let mut scanner = ArrayScanner::new(key);
+        let mut elements = VecDeque::new();
+        for chunk in json.as_bytes().chunks(chunk_size) {
+            scanner.push(chunk, &mut elements);
+        }
+        assert!(scanner.is_done());
+        elements
+            .into_iter()
+            .map(|element| String::from_utf8(element).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_scan() {
+        let json = r#"{
+            "rdapConformance": ["rdap_level_0"],
+            "notices": [{"title": "domainSearchResults", "description": ["]"]}],
+            "domainSearchResults": [
+                {"ldhName": "a.cz", "entities": [{"handle": "}\"{"}]},
+                {"ldhName": "b.cz"}
+            ],
+            "other": [{}]
+        }"#;
+        let expected = vec![
+            r#"{"ldhName":"a.cz","entities":[{"handle":"}\"{"}]}"#,
+            r#"{"ldhName":"b.cz"}"#,
+        ];
+        for chunk_size in [1, 3, 7, json.len()] {
+            let elements: Vec<_> = scan("domainSearchResults", json, chunk_size)
+                .into_iter()
+                .map(|element| element.replace(' ', ""))
+                .collect();
+            assert_eq!(expected, elements);
+        }
+    }
+
+    #[test]
+    fn test_scan_empty() {
+        assert!(scan("entitySearchResults", r#"{"entitySearchResults": []}"#, 2).is_empty());
+    }
+
+    #[test]
+    fn test_scan_unbalanced() {
+        for json in [
+            r#"{"domainSearchResults":[}}}"#,
+            r#"{"domainSearchResults":[{}}]}"#,
+        ] {
+            let mut scanner = ArrayScanner::new("domainSearchResults");
+            let mut elements = VecDeque::new();
+            scanner.push(json.as_bytes(), &mut elements);
+            assert!(scanner.is_invalid());
+            assert!(!scanner.is_done());
+        }
+    }
+
+    #[test]
+    fn test_scan_nested_key() {
+        let json = r#"{"a": {"entitySearchResults": [1]}, "entitySearchResults": [2, 3]}"#;
+        assert_eq!(vec!["2", "3"], scan("entitySearchResults", json, 4));
+    }
+}
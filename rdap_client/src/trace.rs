@@ -0,0 +1,38 @@
+//! Macros that emit `tracing` events and spans when `tracing` feature is enabled and compile to
+//! nothing otherwise.
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)*);
+    };
+}
+
+/// Record value of field declared as `tracing::field::Empty` in current span.
+macro_rules! record {
+    ($field:expr, $value:expr) => {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record($field, $value);
+    };
+}
+
+/// Instrument future with debug span. Span is created before future expression is evaluated, so
+/// span fields can use values that are moved to the future.
+macro_rules! instrument {
+    ($future:expr, $($span:tt)*) => {{
+        #[cfg(feature = "tracing")]
+        let span = tracing::debug_span!($($span)*);
+        #[cfg(feature = "tracing")]
+        let future = tracing::Instrument::instrument($future, span);
+        #[cfg(not(feature = "tracing"))]
+        let future = $future;
+        future
+    }};
+}
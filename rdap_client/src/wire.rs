@@ -0,0 +1,102 @@
+//! Wire debug output similar to `curl -v`, written to standard error.
+
+use reqwest::header::{self, HeaderMap, HeaderName};
+use std::fmt::Write;
+
+/// Headers which values are never written.
+const SECRET_HEADERS: [HeaderName; 4] = [
+    header::AUTHORIZATION,
+    header::PROXY_AUTHORIZATION,
+    header::COOKIE,
+    header::SET_COOKIE,
+];
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct WireDebug {
+    pub(crate) max_body_bytes: usize,
+}
+
+impl WireDebug {
+    pub(crate) fn request(&self, request: &reqwest::Request) {
+        eprint!("{}", format_request(request));
+    }
+
+    pub(crate) fn response(&self, response: &reqwest::Response) {
+        eprint!("{}", format_response(response));
+    }
+
+    pub(crate) fn body(&self, body: &[u8]) {
+        eprint!("{}", format_body(body, self.max_body_bytes));
+    }
+}
+
+fn format_headers(out: &mut String, prefix: &str, headers: &HeaderMap) {
+    for (name, value) in headers {
+        if value.is_sensitive() || SECRET_HEADERS.contains(name) {
+            let _ = writeln!(out, "{} {}: [redacted]", prefix, name);
+        } else {
+            let value = String::from_utf8_lossy(value.as_bytes());
+            let _ = writeln!(out, "{} {}: {}", prefix, name, value);
+        }
+    }
+}
+
+fn format_request(request: &reqwest::Request) -> String {
+    let mut out = format!("> {} {}\n", request.method(), request.url());
+    format_headers(&mut out, ">", request.headers());
+    out
+}
+
+fn format_response(response: &reqwest::Response) -> String {
+    let mut out = format!("< {}\n", response.status());
+    format_headers(&mut out, "<", response.headers());
+    out
+}
+
+fn format_body(body: &[u8], max_body_bytes: usize) -> String {
+    let shown = &body[..body.len().min(max_body_bytes)];
+    let mut out = format!("< {}", String::from_utf8_lossy(shown));
+    if shown.len() < body.len() {
+        let _ = write!(out, "... ({} bytes total)", body.len());
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_body, format_request};
+    use reqwest::header::{self, HeaderValue};
+
+    #[test]
+    fn test_format_request() {
+        let mut request = reqwest::Request::new(
+            reqwest::Method::GET,
+            "https://rdap.example/domain/example.com".parse().unwrap(),
+        );
+        let headers = request.headers_mut();
+        headers.insert(
+            header::ACCEPT,
+            HeaderValue::from_static("application/rdap+json"),
+        );
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret"),
+        );
+        assert_eq!(
+            "> GET https://rdap.example/domain/example.com\n\
+             > accept: application/rdap+json\n\
+             > authorization: [redacted]\n",
+            format_request(&request)
+        );
+    }
+
+    #[test]
+    fn test_format_body() {
+        assert_eq!("< {}\n", format_body(b"{}", 10));
+        assert_eq!(
+            "< {\"a\"... (8 bytes total)\n",
+            format_body(b"{\"a\": 1}", 4)
+        );
+    }
+}
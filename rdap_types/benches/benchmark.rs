@@ -14,11 +14,27 @@ fn criterion_benchmark(c: &mut Criterion) {
         });
     });
 
+    c.bench_function("parse_domain_search", |b| {
+        let json = std::fs::read_to_string("test_data/domain/domain_search.json").unwrap();
+
+        b.iter(|| {
+            serde_json::from_str::<rdap_types::DomainSearchResults>(&json).unwrap();
+        });
+    });
+
+    c.bench_function("parse_domain_search_borrowed", |b| {
+        let json = std::fs::read_to_string("test_data/domain/domain_search.json").unwrap();
+
+        b.iter(|| {
+            serde_json::from_str::<rdap_types::borrowed::DomainSearchResults>(&json).unwrap();
+        });
+    });
+
     c.bench_function("deserialize_enum", |b| {
         let json = r#""last changed""#;
 
         b.iter(|| {
-            serde_json::from_str::<rdap_types::EventAction>(&json).unwrap();
+            serde_json::from_str::<rdap_types::EventAction>(json).unwrap();
         });
     });
 
@@ -26,7 +42,7 @@ fn criterion_benchmark(c: &mut Criterion) {
         let json = r#"["vcard",[["version",{},"text","4.0"],["fn",{},"text",""],["adr",{"cc":"US","iso-3166-1-alpha-2":"US"},"text","","","","","Washington","",""],["org",{},"text","Amazon Registry Services, Inc."]]]"#;
 
         b.iter(|| {
-            serde_json::from_str::<rdap_types::JCard>(&json).unwrap();
+            serde_json::from_str::<rdap_types::JCard>(json).unwrap();
         });
     });
 
@@ -0,0 +1,333 @@
+//! Random but realistic objects for property testing and fuzzing of code that processes RDAP data.
+//! Objects are created by builders, so they are always valid.
+
+use crate::{
+    AutNum, CountryCode, Domain, Entity, Event, EventAction, IpNetwork, JCard, JCardItem,
+    JCardItemDataType, Link, Nameserver, Object, Role, Status, EPP_STATUSES,
+};
+use arbitrary::{Arbitrary, Error, Result, Unstructured};
+use chrono::{DateTime, FixedOffset, TimeZone};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+const TLDS: [&str; 6] = ["com", "net", "org", "cz", "de", "example"];
+const OBJECT_TAGS: [&str; 5] = ["ARIN", "RIPE", "APNIC", "LACNIC", "AFRINIC"];
+const COUNTRIES: [&str; 6] = ["US", "CZ", "DE", "BR", "JP", "EU"];
+const FIRST_NAMES: [&str; 6] = ["Jane", "John", "Alice", "Bob", "Petr", "Yuki"];
+const LAST_NAMES: [&str; 6] = ["Doe", "Smith", "Novak", "Silva", "Tanaka", "Miller"];
+const ROLES: [Role; 11] = [
+    Role::Registrant,
+    Role::Technical,
+    Role::Administrative,
+    Role::Abuse,
+    Role::Billing,
+    Role::Registrar,
+    Role::Reseller,
+    Role::Sponsor,
+    Role::Proxy,
+    Role::Notifications,
+    Role::Noc,
+];
+const EVENT_ACTIONS: [EventAction; 9] = [
+    EventAction::Registration,
+    EventAction::Reregistration,
+    EventAction::LastChanged,
+    EventAction::Expiration,
+    EventAction::Deletion,
+    EventAction::Reinstantiation,
+    EventAction::Transfer,
+    EventAction::Locked,
+    EventAction::Unlocked,
+];
+
+/// Label of letters and digits, like `a1b2`.
+fn label(u: &mut Unstructured<'_>) -> Result<String> {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    let len = u.int_in_range(1..=12)?;
+    (0..len)
+        .map(|_| u.choose(CHARS).map(|c| *c as char))
+        .collect()
+}
+
+fn domain_name(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(format!("{}.{}", label(u)?, u.choose(&TLDS)?))
+}
+
+/// Handle with object tag, like `AB123-RIPE`.
+fn handle(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(format!(
+        "{}{}-{}",
+        label(u)?.to_uppercase(),
+        u.int_in_range(1..=99999u32)?,
+        u.choose(&OBJECT_TAGS)?
+    ))
+}
+
+fn country(u: &mut Unstructured<'_>) -> Result<CountryCode> {
+    CountryCode::from_str(u.choose(&COUNTRIES)?).map_err(|_| Error::IncorrectFormat)
+}
+
+/// Date between years 1985 and 2040 with offset in whole hours.
+fn date(u: &mut Unstructured<'_>) -> Result<DateTime<FixedOffset>> {
+    let offset =
+        FixedOffset::east_opt(u.int_in_range(-12..=14)? * 3600).ok_or(Error::IncorrectFormat)?;
+    offset
+        .timestamp_opt(u.int_in_range(473_385_600..=2_208_988_800)?, 0)
+        .single()
+        .ok_or(Error::IncorrectFormat)
+}
+
+fn statuses(u: &mut Unstructured<'_>) -> Result<Vec<Status>> {
+    let len = u.int_in_range(0..=3)?;
+    (0..len).map(|_| Status::arbitrary(u)).collect()
+}
+
+/// Entity, `depth` limits how deep entities are nested.
+fn entity(u: &mut Unstructured<'_>, depth: u8) -> Result<Entity> {
+    let handle = handle(u)?;
+    let mut builder = Entity::builder()
+        .handle(handle.as_str())
+        .vcard(JCard::arbitrary(u)?)
+        .role(Role::arbitrary(u)?)
+        .self_link(format!("https://rdap.example/entity/{}", handle));
+    if u.ratio(1, 3)? {
+        builder = builder.role(Role::arbitrary(u)?);
+    }
+    if u.ratio(1, 2)? {
+        builder = builder.event(EventAction::LastChanged, date(u)?);
+    }
+    if depth > 0 && u.ratio(1, 3)? {
+        builder = builder.entity(entity(u, depth - 1)?);
+    }
+    builder.build().map_err(|_| Error::IncorrectFormat)
+}
+
+fn nameserver(u: &mut Unstructured<'_>, domain: &str) -> Result<Nameserver> {
+    let ldh_name = format!("ns{}.{}", u.int_in_range(1..=9)?, domain);
+    let mut builder =
+        Nameserver::builder().self_link(format!("https://rdap.example/nameserver/{}", ldh_name));
+    if u.ratio(1, 2)? {
+        builder = builder.ip_address(IpAddr::V4(Ipv4Addr::from(u32::arbitrary(u)?)));
+    }
+    if u.ratio(1, 3)? {
+        builder = builder.ip_address(IpAddr::V6(Ipv6Addr::from(u128::arbitrary(u)?)));
+    }
+    builder
+        .ldh_name(ldh_name)
+        .build()
+        .map_err(|_| Error::IncorrectFormat)
+}
+
+impl<'a> Arbitrary<'a> for Role {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(u.choose(&ROLES)?.clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for EventAction {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&EVENT_ACTIONS)?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Status {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&EPP_STATUSES).map(|(_, status)| status.clone())
+    }
+}
+
+impl<'a> Arbitrary<'a> for Link {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let href = format!("https://{}/{}", domain_name(u)?, label(u)?);
+        Ok(Link {
+            value: Some(href.clone()),
+            rel: Some(
+                u.choose(&["self", "related", "alternate", "about"])?
+                    .to_string(),
+            ),
+            href,
+            href_lang: None,
+            title: None,
+            media: None,
+            r#type: Some("application/rdap+json".to_owned()),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Event {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Event {
+            actor: None,
+            action: EventAction::arbitrary(u)?,
+            date: date(u)?,
+            links: None,
+            raw_date: None,
+        })
+    }
+}
+
+/// Contact card with name and some of organization, email, phone and address.
+impl<'a> Arbitrary<'a> for JCard {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        fn text(name: &str, value: serde_json::Value) -> JCardItem {
+            JCardItem {
+                property_name: name.to_owned(),
+                parameters: serde_json::Map::new(),
+                type_identifier: JCardItemDataType::Text,
+                values: vec![value],
+            }
+        }
+
+        let first_name = u.choose(&FIRST_NAMES)?;
+        let last_name = u.choose(&LAST_NAMES)?;
+        let mut items = vec![
+            text("version", "4.0".into()),
+            text("fn", format!("{} {}", first_name, last_name).into()),
+        ];
+        if u.ratio(1, 2)? {
+            items.push(text("org", format!("{} Inc.", last_name).into()));
+        }
+        if u.ratio(2, 3)? {
+            let email = format!("{}@{}", first_name.to_lowercase(), domain_name(u)?);
+            items.push(text("email", email.into()));
+        }
+        if u.ratio(1, 2)? {
+            let phone = format!("+1.555{:07}", u.int_in_range(0..=9_999_999)?);
+            items.push(text("tel", phone.into()));
+        }
+        if u.ratio(1, 2)? {
+            let mut adr = text(
+                "adr",
+                serde_json::json!(["", "", "Main Street 1", "Springfield", "", "12345", ""]),
+            );
+            adr.parameters
+                .insert("cc".into(), country(u)?.to_string().into());
+            items.push(adr);
+        }
+        Ok(JCard::new(items))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Entity {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        entity(u, 1)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Nameserver {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let domain = domain_name(u)?;
+        nameserver(u, &domain)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Domain {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let ldh_name = domain_name(u)?;
+        let registration = date(u)?;
+        let expiration = registration + chrono::Duration::days(365 * u.int_in_range(1..=10)?);
+        let mut builder = Domain::builder()
+            .handle(format!(
+                "{}_DOMAIN-{}",
+                u.int_in_range(1..=9_999_999u32)?,
+                u.choose(&OBJECT_TAGS)?
+            ))
+            .self_link(format!("https://rdap.example/domain/{}", ldh_name))
+            .event(EventAction::Registration, registration)
+            .event(EventAction::Expiration, expiration);
+        for status in statuses(u)? {
+            builder = builder.status(status);
+        }
+        for _ in 0..u.int_in_range(0..=3)? {
+            builder = builder.entity(Entity::arbitrary(u)?);
+        }
+        for _ in 0..u.int_in_range(0..=3)? {
+            builder = builder.nameserver(nameserver(u, &ldh_name)?);
+        }
+        builder
+            .ldh_name(ldh_name)
+            .build()
+            .map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for IpNetwork {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let (start, end) = if u.arbitrary()? {
+            let prefix = u.int_in_range(8..=32)?;
+            let mask = u32::MAX.checked_shr(prefix).unwrap_or(0);
+            let address = u32::arbitrary(u)? & !mask;
+            (
+                IpAddr::V4(Ipv4Addr::from(address)),
+                IpAddr::V4(Ipv4Addr::from(address | mask)),
+            )
+        } else {
+            let prefix = u.int_in_range(16..=64)?;
+            let mask = u128::MAX.checked_shr(prefix).unwrap_or(0);
+            let address = u128::arbitrary(u)? & !mask;
+            (
+                IpAddr::V6(Ipv6Addr::from(address)),
+                IpAddr::V6(Ipv6Addr::from(address | mask)),
+            )
+        };
+        let mut builder = IpNetwork::builder()
+            .handle(format!("NET-{}-1", start).replace(['.', ':'], "-"))
+            .range(start, end)
+            .name(label(u)?.to_uppercase())
+            .country(country(u)?)
+            .event(EventAction::Registration, date(u)?);
+        for _ in 0..u.int_in_range(0..=2)? {
+            builder = builder.entity(Entity::arbitrary(u)?);
+        }
+        builder.build().map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for AutNum {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let start: u32 = u.int_in_range(1..=4_199_999_999)?;
+        let end = start.saturating_add(u.int_in_range(0..=1023)?);
+        let mut builder = AutNum::builder()
+            .range(start, end)
+            .name(label(u)?.to_uppercase())
+            .country(country(u)?)
+            .event(EventAction::Registration, date(u)?);
+        for _ in 0..u.int_in_range(0..=2)? {
+            builder = builder.entity(Entity::arbitrary(u)?);
+        }
+        builder.build().map_err(|_| Error::IncorrectFormat)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Object {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => Self::Domain(Box::new(Domain::arbitrary(u)?)),
+            1 => Self::Entity(Entity::arbitrary(u)?),
+            2 => Self::Nameserver(Nameserver::arbitrary(u)?),
+            3 => Self::IpNetwork(IpNetwork::arbitrary(u)?),
+            _ => Self::AutNum(AutNum::arbitrary(u)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Domain, Object};
+    use arbitrary::{Arbitrary, Unstructured};
+
+    #[test]
+    fn test_arbitrary() {
+        let data: Vec<u8> = (0..4096u32).map(|i| (i * 7919 % 251) as u8).collect();
+        let mut u = Unstructured::new(&data);
+        let domain = Domain::arbitrary(&mut u).unwrap();
+        assert!(domain.ldh_name.is_some());
+        assert!(Object::Domain(Box::new(domain)).validate().is_empty());
+
+        while !u.is_empty() {
+            let object = Object::arbitrary(&mut u).unwrap();
+            let json = serde_json::to_string(&object).unwrap();
+            assert_eq!(object, serde_json::from_str(&json).unwrap());
+        }
+    }
+}
@@ -0,0 +1,257 @@
+//! Borrowed variants of the most common types, for parsing large responses (like search results)
+//! without allocating string for every member. Strings are borrowed from input when they don't
+//! contain escape sequences. Types contain just the most used members, other members are ignored.
+//!
+//! ```
+//! let json = r#"{"domainSearchResults":[{"ldhName":"example.com","events":[]}]}"#;
+//! let parsed: rdap_types::borrowed::DomainSearchResults = serde_json::from_str(json).unwrap();
+//! assert_eq!(Some("example.com"), parsed.results[0].ldh_name.as_deref());
+//! ```
+
+use crate::{deserialize_datetime, EventAction, IpAddresses, NoticeOrRemarkType, Role, Status};
+use chrono::{DateTime, FixedOffset};
+use serde::de::Visitor;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Deref;
+
+/// String borrowed from input if possible.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct Str<'a>(pub Cow<'a, str>);
+
+impl<'a> Str<'a> {
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+
+    pub fn into_owned(self) -> String {
+        self.0.into_owned()
+    }
+}
+
+impl Deref for Str<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Str<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for Str<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct StrVisitor;
+
+        impl<'de> Visitor<'de> for StrVisitor {
+            type Value = Str<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(Str(Cow::Borrowed(v)))
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(Str(Cow::Owned(v.to_owned())))
+            }
+
+            fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(Str(Cow::Owned(v)))
+            }
+        }
+
+        deserializer.deserialize_str(StrVisitor)
+    }
+}
+
+/// Borrowed variant of [`crate::Link`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Link<'a> {
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Str<'a>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub rel: Option<Str<'a>>,
+    #[serde(borrow)]
+    pub href: Str<'a>,
+    #[serde(borrow, rename = "hreflang", skip_serializing_if = "Option::is_none")]
+    pub href_lang: Option<Vec<Str<'a>>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub title: Option<Str<'a>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub media: Option<Str<'a>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<Str<'a>>,
+}
+
+/// Borrowed variant of [`crate::Event`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Event<'a> {
+    #[serde(borrow, rename = "eventActor", skip_serializing_if = "Option::is_none")]
+    pub actor: Option<Str<'a>>,
+    #[serde(rename = "eventAction")]
+    pub action: EventAction,
+    #[serde(rename = "eventDate", deserialize_with = "deserialize_datetime")]
+    pub date: DateTime<FixedOffset>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Link<'a>>,
+}
+
+/// Borrowed variant of [`crate::NoticeOrRemark`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NoticeOrRemark<'a> {
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub title: Option<Str<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<NoticeOrRemarkType>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub description: Option<Vec<Str<'a>>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<Link<'a>>>,
+}
+
+/// Borrowed variant of [`crate::Entity`], without contact cards.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Entity<'a> {
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub handle: Option<Str<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<Role>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub entities: Option<Vec<Entity<'a>>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub remarks: Option<Vec<NoticeOrRemark<'a>>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<Link<'a>>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<Event<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Vec<Status>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub port43: Option<Str<'a>>,
+}
+
+/// Borrowed variant of [`crate::Nameserver`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Nameserver<'a> {
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub handle: Option<Str<'a>>,
+    #[serde(borrow)]
+    pub ldh_name: Str<'a>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub unicode_name: Option<Str<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_addresses: Option<IpAddresses>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub entities: Option<Vec<Entity<'a>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Vec<Status>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub remarks: Option<Vec<NoticeOrRemark<'a>>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<Link<'a>>>,
+}
+
+/// Borrowed variant of [`crate::Domain`], without DNSSEC data and variants.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Domain<'a> {
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub handle: Option<Str<'a>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub ldh_name: Option<Str<'a>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub unicode_name: Option<Str<'a>>,
+    // Often missing in search results.
+    #[serde(borrow, default)]
+    pub entities: Vec<Entity<'a>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<Link<'a>>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub nameservers: Option<Vec<Nameserver<'a>>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub remarks: Option<Vec<NoticeOrRemark<'a>>>,
+    #[serde(borrow, default)]
+    pub events: Vec<Event<'a>>,
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub port43: Option<Str<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Vec<Status>>,
+}
+
+/// Borrowed variant of [`crate::EntitySearchResults`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntitySearchResults<'a> {
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub notices: Option<Vec<NoticeOrRemark<'a>>>,
+    #[serde(borrow, rename = "entitySearchResults")]
+    pub results: Vec<Entity<'a>>,
+}
+
+/// Borrowed variant of [`crate::DomainSearchResults`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DomainSearchResults<'a> {
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub notices: Option<Vec<NoticeOrRemark<'a>>>,
+    #[serde(borrow, rename = "domainSearchResults")]
+    pub results: Vec<Domain<'a>>,
+}
+
+/// Borrowed variant of [`crate::NameserverSearchResults`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NameserverSearchResults<'a> {
+    #[serde(borrow, skip_serializing_if = "Option::is_none")]
+    pub notices: Option<Vec<NoticeOrRemark<'a>>>,
+    #[serde(borrow, rename = "nameserverSearchResults")]
+    pub results: Vec<Nameserver<'a>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DomainSearchResults, NameserverSearchResults};
+    use std::fs;
+
+    #[test]
+    fn test_borrowed() {
+        let json = fs::read_to_string("test_data/domain/domain_search.json").unwrap();
+        let parsed: DomainSearchResults = serde_json::from_str(&json).unwrap();
+        let owned: crate::DomainSearchResults = serde_json::from_str(&json).unwrap();
+        assert_eq!(owned.results.len(), parsed.results.len());
+        let domain = &parsed.results[0];
+        assert_eq!(Some("EXAMPLE.COM"), domain.ldh_name.as_deref());
+        assert!(domain.ldh_name.as_ref().unwrap().is_borrowed());
+        assert_eq!(2, domain.nameservers.as_ref().unwrap().len());
+        assert_eq!(owned.results[0].events, {
+            let json = serde_json::to_string(&domain.events).unwrap();
+            serde_json::from_str::<Vec<crate::Event>>(&json).unwrap()
+        });
+
+        // Strings with escape sequences can't be borrowed.
+        let json = r#"{"nameserverSearchResults":[{"ldhName":"ns1.example.com\u002e"}]}"#;
+        let parsed: NameserverSearchResults = serde_json::from_str(json).unwrap();
+        assert_eq!("ns1.example.com.", &*parsed.results[0].ldh_name);
+        assert!(!parsed.results[0].ldh_name.is_borrowed());
+    }
+}
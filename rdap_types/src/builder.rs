@@ -0,0 +1,815 @@
+//! Builders of RDAP objects, for tests or for serving RDAP responses.
+
+use crate::{
+    AutNum, Conformance, CountryCode, Domain, DomainName, Entity, Event, EventAction, Extension,
+    Handle, IpAddresses, IpNetwork, IpVersion, JCard, JSContactCard, Link, Nameserver,
+    NoticeOrRemark, Object, PublicId, Role, SecureDns, Status,
+};
+use chrono::{DateTime, FixedOffset};
+use std::fmt;
+use std::net::IpAddr;
+
+/// Error returned by `build` method of builders, when object would not be valid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// Required member was not set.
+    MissingMember(&'static str),
+    /// Member has value that is not allowed.
+    InvalidValue {
+        member: &'static str,
+        reason: &'static str,
+    },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingMember(member) => write!(f, "missing required member `{}`", member),
+            Self::InvalidValue { member, reason } => {
+                write!(f, "invalid value of `{}`: {}", member, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Objects are built as top-level responses, so they conform to `rdap_level_0` by default.
+fn default_conformance() -> Option<Conformance> {
+    Some(Conformance(vec![Extension::RdapLevel0]))
+}
+
+fn push<T>(items: &mut Option<Vec<T>>, item: T) {
+    items.get_or_insert_with(Vec::new).push(item);
+}
+
+fn add_extension(conformance: &mut Option<Conformance>, extension: Extension) {
+    conformance
+        .get_or_insert_with(|| Conformance(Vec::new()))
+        .0
+        .push(extension);
+}
+
+/// Link to object itself with `self` relation.
+fn self_link(href: String) -> Link {
+    Link {
+        value: Some(href.clone()),
+        rel: Some("self".to_owned()),
+        href,
+        href_lang: None,
+        title: None,
+        media: None,
+        r#type: Some("application/rdap+json".to_owned()),
+    }
+}
+
+fn event(action: EventAction, date: DateTime<FixedOffset>) -> Event {
+    Event {
+        actor: None,
+        action,
+        date,
+        links: None,
+        raw_date: None,
+    }
+}
+
+/// Nested entity is not top-level response, so it must not contain `rdapConformance`.
+fn nested_entity(mut entity: Entity) -> Object {
+    entity.rdap_conformance = None;
+    Object::Entity(entity)
+}
+
+/// Checks that name consists of labels with letters, digits and hyphens (RFC 5891), trailing
+/// dot of fully qualified name is allowed.
+fn check_ldh_name(member: &'static str, name: &str) -> Result<(), BuildError> {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    let valid = !name.is_empty()
+        && name.len() <= 253
+        && name.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label
+                    .bytes()
+                    .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(BuildError::InvalidValue {
+            member,
+            reason: "not a domain name in LDH form",
+        })
+    }
+}
+
+impl Domain {
+    pub fn builder() -> DomainBuilder {
+        DomainBuilder::new()
+    }
+}
+
+/// Builder of [`Domain`], `ldh_name` is required.
+#[derive(Debug)]
+pub struct DomainBuilder {
+    domain: Domain,
+}
+
+impl Default for DomainBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DomainBuilder {
+    pub fn new() -> Self {
+        Self {
+            domain: Domain {
+                rdap_conformance: default_conformance(),
+                ..Domain::default()
+            },
+        }
+    }
+
+    pub fn handle<S: Into<Handle>>(mut self, handle: S) -> Self {
+        self.domain.handle = Some(handle.into());
+        self
+    }
+
+    pub fn ldh_name<S: Into<DomainName>>(mut self, ldh_name: S) -> Self {
+        self.domain.ldh_name = Some(ldh_name.into());
+        self
+    }
+
+    pub fn unicode_name<S: Into<DomainName>>(mut self, unicode_name: S) -> Self {
+        self.domain.unicode_name = Some(unicode_name.into());
+        self
+    }
+
+    /// Adds related entity, its `rdapConformance` is removed.
+    pub fn entity(mut self, entity: Entity) -> Self {
+        self.domain.entities.push(nested_entity(entity));
+        self
+    }
+
+    pub fn nameserver(mut self, mut nameserver: Nameserver) -> Self {
+        nameserver.rdap_conformance = None;
+        push(&mut self.domain.nameservers, Object::Nameserver(nameserver));
+        self
+    }
+
+    pub fn secure_dns(mut self, secure_dns: SecureDns) -> Self {
+        self.domain.secure_dns = Some(secure_dns);
+        self
+    }
+
+    pub fn link(mut self, link: Link) -> Self {
+        push(&mut self.domain.links, link);
+        self
+    }
+
+    /// Adds link with `self` relation and RDAP media type.
+    pub fn self_link<S: Into<String>>(self, href: S) -> Self {
+        self.link(self_link(href.into()))
+    }
+
+    pub fn event(mut self, action: EventAction, date: DateTime<FixedOffset>) -> Self {
+        self.domain.events.push(event(action, date));
+        self
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        push(&mut self.domain.status, status);
+        self
+    }
+
+    pub fn remark(mut self, remark: NoticeOrRemark) -> Self {
+        push(&mut self.domain.remarks, remark);
+        self
+    }
+
+    pub fn notice(mut self, notice: NoticeOrRemark) -> Self {
+        push(&mut self.domain.notices, notice);
+        self
+    }
+
+    pub fn port43<S: Into<String>>(mut self, port43: S) -> Self {
+        self.domain.port43 = Some(port43.into());
+        self
+    }
+
+    pub fn lang<S: Into<String>>(mut self, lang: S) -> Self {
+        self.domain.lang = Some(lang.into());
+        self
+    }
+
+    /// Adds extension to `rdapConformance`, that contains just `rdap_level_0` by default.
+    pub fn extension(mut self, extension: Extension) -> Self {
+        add_extension(&mut self.domain.rdap_conformance, extension);
+        self
+    }
+
+    pub fn build(self) -> Result<Domain, BuildError> {
+        match &self.domain.ldh_name {
+            Some(ldh_name) => check_ldh_name("ldhName", ldh_name)?,
+            None => return Err(BuildError::MissingMember("ldhName")),
+        }
+        Ok(self.domain)
+    }
+}
+
+impl Entity {
+    pub fn builder() -> EntityBuilder {
+        EntityBuilder::new()
+    }
+}
+
+/// Builder of [`Entity`], it requires handle or contact card.
+#[derive(Debug)]
+pub struct EntityBuilder {
+    entity: Entity,
+}
+
+impl Default for EntityBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EntityBuilder {
+    pub fn new() -> Self {
+        Self {
+            entity: Entity {
+                rdap_conformance: default_conformance(),
+                ..Entity::default()
+            },
+        }
+    }
+
+    pub fn handle<S: Into<Handle>>(mut self, handle: S) -> Self {
+        self.entity.handle = Some(handle.into());
+        self
+    }
+
+    pub fn vcard(mut self, vcard: JCard) -> Self {
+        self.entity.vcard_array = Some(vcard);
+        self
+    }
+
+    /// Contact card from `jscard` extension.
+    pub fn jscard(mut self, card: JSContactCard) -> Self {
+        self.entity.jscard = Some(Box::new(card));
+        self
+    }
+
+    pub fn role(mut self, role: Role) -> Self {
+        push(&mut self.entity.roles, role);
+        self
+    }
+
+    pub fn public_id(mut self, public_id: PublicId) -> Self {
+        push(&mut self.entity.public_ids, public_id);
+        self
+    }
+
+    /// Adds related entity, its `rdapConformance` is removed.
+    pub fn entity(mut self, entity: Entity) -> Self {
+        push(&mut self.entity.entities, nested_entity(entity));
+        self
+    }
+
+    pub fn link(mut self, link: Link) -> Self {
+        push(&mut self.entity.links, link);
+        self
+    }
+
+    /// Adds link with `self` relation and RDAP media type.
+    pub fn self_link<S: Into<String>>(self, href: S) -> Self {
+        self.link(self_link(href.into()))
+    }
+
+    pub fn event(mut self, action: EventAction, date: DateTime<FixedOffset>) -> Self {
+        push(&mut self.entity.events, event(action, date));
+        self
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        push(&mut self.entity.status, status);
+        self
+    }
+
+    pub fn remark(mut self, remark: NoticeOrRemark) -> Self {
+        push(&mut self.entity.remarks, remark);
+        self
+    }
+
+    pub fn notice(mut self, notice: NoticeOrRemark) -> Self {
+        push(&mut self.entity.notices, notice);
+        self
+    }
+
+    pub fn port43<S: Into<String>>(mut self, port43: S) -> Self {
+        self.entity.port43 = Some(port43.into());
+        self
+    }
+
+    pub fn lang<S: Into<String>>(mut self, lang: S) -> Self {
+        self.entity.lang = Some(lang.into());
+        self
+    }
+
+    /// Adds extension to `rdapConformance`, that contains just `rdap_level_0` by default.
+    pub fn extension(mut self, extension: Extension) -> Self {
+        add_extension(&mut self.entity.rdap_conformance, extension);
+        self
+    }
+
+    pub fn build(self) -> Result<Entity, BuildError> {
+        let entity = &self.entity;
+        if entity.handle.is_none() && entity.vcard_array.is_none() && entity.jscard.is_none() {
+            return Err(BuildError::MissingMember("handle"));
+        }
+        Ok(self.entity)
+    }
+}
+
+impl Nameserver {
+    pub fn builder() -> NameserverBuilder {
+        NameserverBuilder::new()
+    }
+}
+
+/// Builder of [`Nameserver`], `ldh_name` is required.
+#[derive(Debug)]
+pub struct NameserverBuilder {
+    nameserver: Nameserver,
+}
+
+impl Default for NameserverBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NameserverBuilder {
+    pub fn new() -> Self {
+        Self {
+            nameserver: Nameserver {
+                rdap_conformance: default_conformance(),
+                ..Nameserver::default()
+            },
+        }
+    }
+
+    pub fn handle<S: Into<Handle>>(mut self, handle: S) -> Self {
+        self.nameserver.handle = Some(handle.into());
+        self
+    }
+
+    pub fn ldh_name<S: Into<DomainName>>(mut self, ldh_name: S) -> Self {
+        self.nameserver.ldh_name = ldh_name.into();
+        self
+    }
+
+    pub fn unicode_name<S: Into<DomainName>>(mut self, unicode_name: S) -> Self {
+        self.nameserver.unicode_name = Some(unicode_name.into());
+        self
+    }
+
+    /// Adds glue address, IPv4 or IPv6.
+    pub fn ip_address(mut self, address: IpAddr) -> Self {
+        let addresses = self
+            .nameserver
+            .ip_addresses
+            .get_or_insert_with(IpAddresses::default);
+        match address {
+            IpAddr::V4(address) => push(&mut addresses.v4, address),
+            IpAddr::V6(address) => push(&mut addresses.v6, address),
+        }
+        self
+    }
+
+    /// Adds related entity, its `rdapConformance` is removed.
+    pub fn entity(mut self, entity: Entity) -> Self {
+        push(&mut self.nameserver.entities, nested_entity(entity));
+        self
+    }
+
+    pub fn link(mut self, link: Link) -> Self {
+        push(&mut self.nameserver.links, link);
+        self
+    }
+
+    /// Adds link with `self` relation and RDAP media type.
+    pub fn self_link<S: Into<String>>(self, href: S) -> Self {
+        self.link(self_link(href.into()))
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        push(&mut self.nameserver.status, status);
+        self
+    }
+
+    pub fn remark(mut self, remark: NoticeOrRemark) -> Self {
+        push(&mut self.nameserver.remarks, remark);
+        self
+    }
+
+    pub fn notice(mut self, notice: NoticeOrRemark) -> Self {
+        push(&mut self.nameserver.notices, notice);
+        self
+    }
+
+    /// Adds extension to `rdapConformance`, that contains just `rdap_level_0` by default.
+    pub fn extension(mut self, extension: Extension) -> Self {
+        add_extension(&mut self.nameserver.rdap_conformance, extension);
+        self
+    }
+
+    pub fn build(self) -> Result<Nameserver, BuildError> {
+        if self.nameserver.ldh_name.is_empty() {
+            return Err(BuildError::MissingMember("ldhName"));
+        }
+        check_ldh_name("ldhName", &self.nameserver.ldh_name)?;
+        Ok(self.nameserver)
+    }
+}
+
+impl IpNetwork {
+    pub fn builder() -> IpNetworkBuilder {
+        IpNetworkBuilder::new()
+    }
+}
+
+/// Builder of [`IpNetwork`], `handle` and range of addresses are required.
+#[derive(Debug, Default)]
+pub struct IpNetworkBuilder {
+    handle: Option<Handle>,
+    range: Option<(IpAddr, IpAddr)>,
+    name: Option<String>,
+    country: Option<CountryCode>,
+    parent_handle: Option<Handle>,
+    r#type: Option<String>,
+    entities: Option<Vec<Object>>,
+    links: Option<Vec<Link>>,
+    remarks: Option<Vec<NoticeOrRemark>>,
+    events: Option<Vec<Event>>,
+    notices: Option<Vec<NoticeOrRemark>>,
+    status: Option<Vec<Status>>,
+    port43: Option<String>,
+    lang: Option<String>,
+    extensions: Vec<Extension>,
+}
+
+impl IpNetworkBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn handle<S: Into<Handle>>(mut self, handle: S) -> Self {
+        self.handle = Some(handle.into());
+        self
+    }
+
+    /// First and last address of network, both must be of the same version.
+    pub fn range(mut self, start_address: IpAddr, end_address: IpAddr) -> Self {
+        self.range = Some((start_address, end_address));
+        self
+    }
+
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn country(mut self, country: CountryCode) -> Self {
+        self.country = Some(country);
+        self
+    }
+
+    pub fn parent_handle<S: Into<Handle>>(mut self, parent_handle: S) -> Self {
+        self.parent_handle = Some(parent_handle.into());
+        self
+    }
+
+    /// Type of network assigned by registry, like `ALLOCATED PA`.
+    pub fn network_type<S: Into<String>>(mut self, r#type: S) -> Self {
+        self.r#type = Some(r#type.into());
+        self
+    }
+
+    /// Adds related entity, its `rdapConformance` is removed.
+    pub fn entity(mut self, entity: Entity) -> Self {
+        push(&mut self.entities, nested_entity(entity));
+        self
+    }
+
+    pub fn link(mut self, link: Link) -> Self {
+        push(&mut self.links, link);
+        self
+    }
+
+    /// Adds link with `self` relation and RDAP media type.
+    pub fn self_link<S: Into<String>>(self, href: S) -> Self {
+        self.link(self_link(href.into()))
+    }
+
+    pub fn event(mut self, action: EventAction, date: DateTime<FixedOffset>) -> Self {
+        push(&mut self.events, event(action, date));
+        self
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        push(&mut self.status, status);
+        self
+    }
+
+    pub fn remark(mut self, remark: NoticeOrRemark) -> Self {
+        push(&mut self.remarks, remark);
+        self
+    }
+
+    pub fn notice(mut self, notice: NoticeOrRemark) -> Self {
+        push(&mut self.notices, notice);
+        self
+    }
+
+    pub fn port43<S: Into<String>>(mut self, port43: S) -> Self {
+        self.port43 = Some(port43.into());
+        self
+    }
+
+    pub fn lang<S: Into<String>>(mut self, lang: S) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// Adds extension to `rdapConformance`, that contains just `rdap_level_0` by default.
+    pub fn extension(mut self, extension: Extension) -> Self {
+        self.extensions.push(extension);
+        self
+    }
+
+    pub fn build(self) -> Result<IpNetwork, BuildError> {
+        let handle = self.handle.ok_or(BuildError::MissingMember("handle"))?;
+        let (start_address, end_address) = self
+            .range
+            .ok_or(BuildError::MissingMember("startAddress"))?;
+        let ip_version = match (start_address, end_address) {
+            (IpAddr::V4(_), IpAddr::V4(_)) => IpVersion::V4,
+            (IpAddr::V6(_), IpAddr::V6(_)) => IpVersion::V6,
+            _ => {
+                return Err(BuildError::InvalidValue {
+                    member: "endAddress",
+                    reason: "different IP version than start address",
+                })
+            }
+        };
+        if start_address > end_address {
+            return Err(BuildError::InvalidValue {
+                member: "endAddress",
+                reason: "lower than start address",
+            });
+        }
+        let mut rdap_conformance = default_conformance();
+        for extension in self.extensions {
+            add_extension(&mut rdap_conformance, extension);
+        }
+        Ok(IpNetwork {
+            handle,
+            start_address,
+            end_address,
+            ip_version,
+            name: self.name,
+            country: self.country,
+            parent_handle: self.parent_handle,
+            r#type: self.r#type,
+            entities: self.entities,
+            links: self.links,
+            remarks: self.remarks,
+            events: self.events,
+            rdap_conformance,
+            notices: self.notices,
+            port43: self.port43,
+            status: self.status,
+            lang: self.lang,
+            cidr0_cidrs: None,
+            arin_originas0_originautnums: None,
+            #[cfg(feature = "extra-fields")]
+            extra: Default::default(),
+        })
+    }
+}
+
+impl AutNum {
+    pub fn builder() -> AutNumBuilder {
+        AutNumBuilder::new()
+    }
+}
+
+/// Builder of [`AutNum`], range of AS numbers is required and handle is `AS` followed by the
+/// first number by default.
+#[derive(Debug)]
+pub struct AutNumBuilder {
+    autnum: AutNum,
+}
+
+impl Default for AutNumBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AutNumBuilder {
+    pub fn new() -> Self {
+        Self {
+            autnum: AutNum {
+                rdap_conformance: default_conformance(),
+                ..AutNum::default()
+            },
+        }
+    }
+
+    pub fn handle<S: Into<Handle>>(mut self, handle: S) -> Self {
+        self.autnum.handle = handle.into();
+        self
+    }
+
+    /// First and last AS number of the block, single AS number has both the same.
+    pub fn range(mut self, start_autnum: u32, end_autnum: u32) -> Self {
+        self.autnum.start_autnum = Some(start_autnum);
+        self.autnum.end_autnum = Some(end_autnum);
+        self
+    }
+
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.autnum.name = Some(name.into());
+        self
+    }
+
+    pub fn country(mut self, country: CountryCode) -> Self {
+        self.autnum.country = Some(country);
+        self
+    }
+
+    /// Type of AS number block assigned by registry, like `DIRECT ALLOCATION`.
+    pub fn autnum_type<S: Into<String>>(mut self, r#type: S) -> Self {
+        self.autnum.r#type = Some(r#type.into());
+        self
+    }
+
+    /// Adds related entity, its `rdapConformance` is removed.
+    pub fn entity(mut self, entity: Entity) -> Self {
+        self.autnum.entities.push(nested_entity(entity));
+        self
+    }
+
+    pub fn link(mut self, link: Link) -> Self {
+        push(&mut self.autnum.links, link);
+        self
+    }
+
+    /// Adds link with `self` relation and RDAP media type.
+    pub fn self_link<S: Into<String>>(self, href: S) -> Self {
+        self.link(self_link(href.into()))
+    }
+
+    pub fn event(mut self, action: EventAction, date: DateTime<FixedOffset>) -> Self {
+        push(&mut self.autnum.events, event(action, date));
+        self
+    }
+
+    pub fn status(mut self, status: Status) -> Self {
+        push(&mut self.autnum.status, status);
+        self
+    }
+
+    pub fn remark(mut self, remark: NoticeOrRemark) -> Self {
+        push(&mut self.autnum.remarks, remark);
+        self
+    }
+
+    pub fn notice(mut self, notice: NoticeOrRemark) -> Self {
+        push(&mut self.autnum.notices, notice);
+        self
+    }
+
+    pub fn port43<S: Into<String>>(mut self, port43: S) -> Self {
+        self.autnum.port43 = Some(port43.into());
+        self
+    }
+
+    pub fn lang<S: Into<String>>(mut self, lang: S) -> Self {
+        self.autnum.lang = Some(lang.into());
+        self
+    }
+
+    /// Adds extension to `rdapConformance`, that contains just `rdap_level_0` by default.
+    pub fn extension(mut self, extension: Extension) -> Self {
+        add_extension(&mut self.autnum.rdap_conformance, extension);
+        self
+    }
+
+    pub fn build(mut self) -> Result<AutNum, BuildError> {
+        let (start, end) = match (self.autnum.start_autnum, self.autnum.end_autnum) {
+            (Some(start), Some(end)) => (start, end),
+            _ => return Err(BuildError::MissingMember("startAutnum")),
+        };
+        if start > end {
+            return Err(BuildError::InvalidValue {
+                member: "endAutnum",
+                reason: "lower than start AS number",
+            });
+        }
+        if self.autnum.handle.is_empty() {
+            self.autnum.handle = format!("AS{}", start).into();
+        }
+        Ok(self.autnum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BuildError;
+    use crate::{
+        AutNum, Domain, Entity, EventAction, Extension, IpNetwork, IpVersion, Nameserver, Object,
+        Role, Status,
+    };
+    use chrono::DateTime;
+
+    #[test]
+    fn test_build_domain() {
+        let registered = DateTime::parse_from_rfc3339("2020-01-01T10:00:00Z").unwrap();
+        let domain = Domain::builder()
+            .ldh_name("example.com")
+            .self_link("https://rdap.example/domain/example.com")
+            .event(EventAction::Registration, registered)
+            .status(Status::Active)
+            .entity(
+                Entity::builder()
+                    .handle("REGISTRAR")
+                    .role(Role::Registrar)
+                    .build()
+                    .unwrap(),
+            )
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("ns1.example.com")
+                    .ip_address("192.0.2.1".parse().unwrap())
+                    .build()
+                    .unwrap(),
+            )
+            .extension(Extension::Redacted)
+            .build()
+            .unwrap();
+        assert_eq!(Some("example.com"), domain.ldh_name.as_deref());
+        assert!(domain
+            .rdap_conformance
+            .as_ref()
+            .unwrap()
+            .has_extension("rdap_level_0"));
+        assert!(domain
+            .rdap_conformance
+            .as_ref()
+            .unwrap()
+            .has_extension("redacted"));
+        let Object::Entity(entity) = &domain.entities[0] else {
+            panic!("entity expected");
+        };
+        assert!(entity.rdap_conformance.is_none());
+        assert!(Object::Domain(Box::new(domain)).validate().is_empty());
+
+        assert_eq!(
+            Err(BuildError::MissingMember("ldhName")),
+            Domain::builder().handle("DOMAIN").build().map(|_| ())
+        );
+        assert!(matches!(
+            Domain::builder().ldh_name("exa mple.com").build(),
+            Err(BuildError::InvalidValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_build_ip_network_and_autnum() {
+        let network = IpNetwork::builder()
+            .handle("NET-192-0-2-0-1")
+            .range("192.0.2.0".parse().unwrap(), "192.0.2.255".parse().unwrap())
+            .build()
+            .unwrap();
+        assert_eq!(IpVersion::V4, network.ip_version);
+        assert!(IpNetwork::builder()
+            .handle("NET")
+            .range("192.0.2.0".parse().unwrap(), "2001:db8::".parse().unwrap())
+            .build()
+            .is_err());
+
+        let autnum = AutNum::builder().range(64496, 64511).build().unwrap();
+        assert_eq!("AS64496", autnum.handle);
+        assert_eq!(Some(64496..=64511), autnum.range());
+        assert_eq!(
+            Err(BuildError::MissingMember("startAutnum")),
+            AutNum::builder().handle("AS1").build().map(|_| ())
+        );
+    }
+}
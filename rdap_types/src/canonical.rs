@@ -0,0 +1,343 @@
+//! Canonical form of responses, for change detection and hashing of responses.
+
+use crate::{
+    AutNum, Domain, DomainName, Entity, Event, FredKeySet, FredNsSet, Help, IpNetwork, Link,
+    Nameserver, NoticeOrRemark, Object, RdapObject, Role, Status, TopLevelResponse,
+};
+use chrono::FixedOffset;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::Write;
+
+/// Normalization of values that can be written differently with the same meaning. Two responses
+/// with the same content have equal canonical JSON, even if servers ordered or formatted them
+/// differently.
+pub trait Canonicalize: Serialize + Clone {
+    /// Lowercases domain names and removes their trailing dot, sorts arrays without semantic order
+    /// (statuses, roles, links by relation and URL, events by date, entities by handle and
+    /// nameservers by name) and converts dates to UTC. Nested objects are canonicalized too.
+    fn canonicalize(&mut self);
+
+    /// Returns JSON of canonicalized copy with keys of objects sorted and without whitespace.
+    fn to_canonical_json(&self) -> String {
+        let mut canonical = self.clone();
+        canonical.canonicalize();
+        let value = serde_json::to_value(&canonical).expect("serializing to value can't fail");
+        let mut json = String::new();
+        write_sorted(&mut json, &value);
+        json
+    }
+}
+
+/// Writes JSON with keys of objects sorted, independently on `preserve_order` feature of
+/// `serde_json`.
+fn write_sorted(json: &mut String, value: &Value) {
+    match value {
+        Value::Array(items) => {
+            json.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                write_sorted(json, item);
+            }
+            json.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            json.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                let _ = write!(json, "{}:", Value::from(key.as_str()));
+                write_sorted(json, value);
+            }
+            json.push('}');
+        }
+        scalar => {
+            let _ = write!(json, "{}", scalar);
+        }
+    }
+}
+
+/// Serialized name of enum value, used for sorting.
+fn serialized_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn canonical_name(name: &mut DomainName) {
+    *name = name.trim_end_matches('.').to_lowercase().into();
+}
+
+fn canonical_statuses(statuses: &mut Option<Vec<Status>>) {
+    if let Some(statuses) = statuses {
+        statuses.sort_by_cached_key(serialized_name);
+        statuses.dedup();
+    }
+}
+
+fn canonical_roles(roles: &mut Option<Vec<Role>>) {
+    if let Some(roles) = roles {
+        roles.sort_by_cached_key(serialized_name);
+        roles.dedup();
+    }
+}
+
+fn canonical_links(links: &mut [Link]) {
+    links.sort_by(|a, b| (&a.rel, &a.href).cmp(&(&b.rel, &b.href)));
+}
+
+fn canonical_events(events: &mut [Event]) {
+    let utc = FixedOffset::east_opt(0).expect("zero offset is valid");
+    for event in events.iter_mut() {
+        event.date = event.date.with_timezone(&utc);
+        event.raw_date = None;
+    }
+    events.sort_by_cached_key(|event| (event.date, serialized_name(&event.action)));
+}
+
+fn canonical_notices(notices: &mut Option<Vec<NoticeOrRemark>>) {
+    for notice in notices.iter_mut().flatten() {
+        canonical_links(notice.links.as_deref_mut().unwrap_or_default());
+    }
+}
+
+fn canonical_objects(objects: &mut [Object]) {
+    for object in objects.iter_mut() {
+        object.canonicalize();
+    }
+    objects.sort_by_cached_key(|object| object.handle().map(str::to_ascii_lowercase));
+}
+
+impl Canonicalize for Domain {
+    fn canonicalize(&mut self) {
+        self.ldh_name.iter_mut().for_each(canonical_name);
+        self.unicode_name.iter_mut().for_each(canonical_name);
+        canonical_objects(&mut self.entities);
+        canonical_links(self.links.as_deref_mut().unwrap_or_default());
+        if let Some(nameservers) = &mut self.nameservers {
+            for nameserver in nameservers.iter_mut() {
+                nameserver.canonicalize();
+            }
+            nameservers.sort_by_cached_key(|object| match object {
+                Object::Nameserver(nameserver) => Some(nameserver.ldh_name.to_string()),
+                _ => None,
+            });
+        }
+        canonical_notices(&mut self.remarks);
+        canonical_events(&mut self.events);
+        if let Some(network) = &mut self.network {
+            network.canonicalize();
+        }
+        canonical_notices(&mut self.notices);
+        canonical_statuses(&mut self.status);
+        for object in self.fred_keyset.iter_mut().chain(&mut self.fred_nsset) {
+            object.canonicalize();
+        }
+    }
+}
+
+impl Canonicalize for Entity {
+    fn canonicalize(&mut self) {
+        canonical_roles(&mut self.roles);
+        canonical_objects(self.entities.as_deref_mut().unwrap_or_default());
+        canonical_notices(&mut self.remarks);
+        canonical_links(self.links.as_deref_mut().unwrap_or_default());
+        canonical_events(self.events.as_deref_mut().unwrap_or_default());
+        canonical_events(self.as_event_actor.as_deref_mut().unwrap_or_default());
+        canonical_statuses(&mut self.status);
+        canonical_notices(&mut self.notices);
+    }
+}
+
+impl Canonicalize for Nameserver {
+    fn canonicalize(&mut self) {
+        canonical_name(&mut self.ldh_name);
+        self.unicode_name.iter_mut().for_each(canonical_name);
+        if let Some(addresses) = &mut self.ip_addresses {
+            addresses.v4.iter_mut().for_each(|v4| v4.sort());
+            addresses.v6.iter_mut().for_each(|v6| v6.sort());
+        }
+        canonical_objects(self.entities.as_deref_mut().unwrap_or_default());
+        canonical_statuses(&mut self.status);
+        canonical_notices(&mut self.remarks);
+        canonical_notices(&mut self.notices);
+        canonical_links(self.links.as_deref_mut().unwrap_or_default());
+    }
+}
+
+impl Canonicalize for IpNetwork {
+    fn canonicalize(&mut self) {
+        canonical_objects(self.entities.as_deref_mut().unwrap_or_default());
+        canonical_links(self.links.as_deref_mut().unwrap_or_default());
+        canonical_notices(&mut self.remarks);
+        canonical_events(self.events.as_deref_mut().unwrap_or_default());
+        canonical_notices(&mut self.notices);
+        canonical_statuses(&mut self.status);
+    }
+}
+
+impl Canonicalize for AutNum {
+    fn canonicalize(&mut self) {
+        canonical_objects(&mut self.entities);
+        canonical_links(self.links.as_deref_mut().unwrap_or_default());
+        canonical_notices(&mut self.remarks);
+        canonical_events(self.events.as_deref_mut().unwrap_or_default());
+        canonical_notices(&mut self.notices);
+        canonical_statuses(&mut self.status);
+    }
+}
+
+impl Canonicalize for FredKeySet {
+    fn canonicalize(&mut self) {
+        canonical_links(&mut self.links);
+    }
+}
+
+impl Canonicalize for FredNsSet {
+    fn canonicalize(&mut self) {
+        canonical_links(&mut self.links);
+        for nameserver in self.nameservers.iter_mut() {
+            nameserver.canonicalize();
+        }
+        self.nameservers
+            .sort_by_cached_key(|nameserver| nameserver.ldh_name.to_string());
+    }
+}
+
+impl Canonicalize for Object {
+    fn canonicalize(&mut self) {
+        match self {
+            Self::AutNum(autnum) => autnum.canonicalize(),
+            Self::Domain(domain) => domain.canonicalize(),
+            Self::Entity(entity) => entity.canonicalize(),
+            Self::FredKeySet(keyset) => keyset.canonicalize(),
+            Self::FredNsSet(nsset) => nsset.canonicalize(),
+            Self::IpNetwork(network) => network.canonicalize(),
+            Self::Nameserver(nameserver) => nameserver.canonicalize(),
+        }
+    }
+}
+
+impl Canonicalize for Help {
+    fn canonicalize(&mut self) {
+        canonical_notices(&mut self.notices);
+    }
+}
+
+/// Search results are kept in order returned by server, because it can be requested sorting.
+impl Canonicalize for TopLevelResponse {
+    fn canonicalize(&mut self) {
+        match self {
+            Self::Object(object) => object.canonicalize(),
+            Self::DomainSearchResults(results) => {
+                canonical_notices(&mut results.notices);
+                results.results.iter_mut().for_each(Domain::canonicalize);
+            }
+            Self::NameserverSearchResults(results) => {
+                canonical_notices(&mut results.notices);
+                results
+                    .results
+                    .iter_mut()
+                    .for_each(Nameserver::canonicalize);
+            }
+            Self::EntitySearchResults(results) => {
+                canonical_notices(&mut results.notices);
+                results.results.iter_mut().for_each(Entity::canonicalize);
+            }
+            Self::ArinOriginas0NetworkSearchResults(results) => {
+                canonical_notices(&mut results.notices);
+                results.results.iter_mut().for_each(IpNetwork::canonicalize);
+            }
+            Self::Help(help) => help.canonicalize(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Canonicalize;
+    use crate::{Domain, EventAction, Link, Nameserver, Status};
+    use chrono::DateTime;
+
+    #[test]
+    fn test_canonical_json() {
+        let link = |rel: &str, href: &str| Link {
+            value: None,
+            rel: Some(rel.to_owned()),
+            href: href.to_owned(),
+            href_lang: None,
+            title: None,
+            media: None,
+            r#type: None,
+        };
+        let date = |date| DateTime::parse_from_rfc3339(date).unwrap();
+        let first = Domain::builder()
+            .ldh_name("Example.COM.")
+            .link(link("self", "https://rdap.example/domain/example.com"))
+            .link(link("related", "https://rdap.example/"))
+            .status(Status::Active)
+            .status(Status::ClientHold)
+            .event(EventAction::Expiration, date("2030-01-01T01:00:00+01:00"))
+            .event(EventAction::Registration, date("2020-01-01T00:00:00Z"))
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("NS2.example.com")
+                    .build()
+                    .unwrap(),
+            )
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("ns1.example.com")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let second = Domain::builder()
+            .ldh_name("example.com")
+            .link(link("related", "https://rdap.example/"))
+            .link(link("self", "https://rdap.example/domain/example.com"))
+            .status(Status::ClientHold)
+            .status(Status::Active)
+            .event(EventAction::Registration, date("2020-01-01T01:00:00+01:00"))
+            .event(EventAction::Expiration, date("2030-01-01T00:00:00Z"))
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("ns1.example.com.")
+                    .build()
+                    .unwrap(),
+            )
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("ns2.example.com")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        assert_ne!(
+            serde_json::to_string(&first).unwrap(),
+            serde_json::to_string(&second).unwrap()
+        );
+        assert_eq!(first.to_canonical_json(), second.to_canonical_json());
+
+        let mut canonical = first;
+        canonical.canonicalize();
+        assert_eq!(Some("example.com"), canonical.ldh_name.as_deref());
+        assert_eq!(
+            Some("related"),
+            canonical.links.as_ref().unwrap()[0].rel.as_deref()
+        );
+        assert_eq!(EventAction::Registration, canonical.events[0].action);
+        assert_eq!(
+            "2030-01-01T00:00:00+00:00",
+            canonical.events[1].date.to_rfc3339()
+        );
+        assert!(canonical
+            .to_canonical_json()
+            .starts_with(r#"{"entities":[],"events":"#));
+    }
+}
@@ -0,0 +1,215 @@
+//! Typed contact details extracted from jCard (RFC 7095) of entity.
+
+use crate::{Entity, JCard, JCardItem};
+use serde_json::Value;
+
+/// Contact details from jCard, properties that are not converted are available in `items`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Contact {
+    /// Formatted name from `fn` property.
+    pub full_name: Option<String>,
+    /// Kind of contact from `kind` property, like `individual` or `org`.
+    pub kind: Option<String>,
+    /// Organization name from `org` property, organizational units are left out.
+    pub organization: Option<String>,
+    pub addresses: Vec<Address>,
+    pub emails: Vec<TypedValue>,
+    /// Phone numbers, values of `uri` type keep `tel:` prefix.
+    pub phones: Vec<TypedValue>,
+    /// All original items of jCard.
+    pub items: Vec<JCardItem>,
+}
+
+/// Postal address from `adr` property. Components are empty or `None` when address is given just
+/// as `label`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Address {
+    /// Values of `type` parameter, like `work` or `home`.
+    pub types: Vec<String>,
+    /// Formatted address from `label` parameter.
+    pub label: Option<String>,
+    /// Country code from `cc` parameter (RFC 8605).
+    pub country_code: Option<String>,
+    pub post_office_box: Option<String>,
+    pub extended_address: Option<String>,
+    pub street: Vec<String>,
+    pub locality: Option<String>,
+    pub region: Option<String>,
+    pub postal_code: Option<String>,
+    pub country_name: Option<String>,
+}
+
+/// Value of property, like email or phone number, with values of its `type` parameter.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct TypedValue {
+    pub value: String,
+    /// Values of `type` parameter, like `work`, `voice` or `fax`.
+    pub types: Vec<String>,
+    /// Preference from `pref` parameter, 1 is the most preferred.
+    pub pref: Option<u8>,
+}
+
+impl From<&JCard> for Contact {
+    fn from(jcard: &JCard) -> Self {
+        let mut contact = Self {
+            items: jcard.items().clone(),
+            ..Self::default()
+        };
+        for item in jcard.items() {
+            match item.property_name.as_str() {
+                "fn" => contact.full_name = contact.full_name.or_else(|| text(&item.values[0])),
+                "kind" => contact.kind = contact.kind.or_else(|| text(&item.values[0])),
+                "org" => {
+                    contact.organization = contact.organization.or_else(|| text(&item.values[0]))
+                }
+                "adr" => contact.addresses.push(Address::from(item)),
+                "email" => contact.emails.extend(TypedValue::from_item(item)),
+                "tel" => contact.phones.extend(TypedValue::from_item(item)),
+                _ => {}
+            }
+        }
+        contact
+    }
+}
+
+impl From<&JCardItem> for Address {
+    fn from(item: &JCardItem) -> Self {
+        // Structured value is array of components, but some servers send components as separate
+        // values.
+        let components = match &item.values[..] {
+            [Value::Array(components)] => &components[..],
+            values => values,
+        };
+        let component = |index: usize| components.get(index).and_then(text);
+        Self {
+            types: parameter(item, "type"),
+            label: parameter(item, "label").pop(),
+            country_code: parameter(item, "cc").pop(),
+            post_office_box: component(0),
+            extended_address: component(1),
+            street: components.get(2).map_or_else(Vec::new, texts),
+            locality: component(3),
+            region: component(4),
+            postal_code: component(5),
+            country_name: component(6),
+        }
+    }
+}
+
+impl TypedValue {
+    fn from_item(item: &JCardItem) -> Option<Self> {
+        Some(Self {
+            value: text(&item.values[0])?,
+            types: parameter(item, "type"),
+            pref: parameter(item, "pref")
+                .pop()
+                .and_then(|pref| pref.parse().ok()),
+        })
+    }
+}
+
+impl Entity {
+    /// Returns contact details from jCard of entity, or from JSContact card when entity doesn't
+    /// have jCard. `None` when entity has neither.
+    pub fn contact(&self) -> Option<Contact> {
+        match (&self.vcard_array, &self.jscard) {
+            (Some(jcard), _) => Some(Contact::from(jcard)),
+            (None, Some(card)) => Some(Contact::from(&JCard::from(&**card))),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Returns non-empty string, array of strings (like organization with units) is represented by
+/// its first item.
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(string) if !string.is_empty() => Some(string.clone()),
+        Value::Array(values) => values.first().and_then(text),
+        _ => None,
+    }
+}
+
+/// Returns all non-empty strings from string or array of strings.
+fn texts(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(values) => values.iter().filter_map(text).collect(),
+        value => text(value).into_iter().collect(),
+    }
+}
+
+/// Returns values of parameter that can be string or array of strings, names of parameters are
+/// case insensitive.
+fn parameter(item: &JCardItem, name: &str) -> Vec<String> {
+    item.parameters
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(name))
+        .flat_map(|(_, value)| texts(value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Address, Contact, TypedValue};
+    use crate::{Entity, JCard};
+    use std::fs::File;
+
+    #[test]
+    fn test_contact() {
+        let file = File::open("test_data/entity/entity_15.json").unwrap();
+        let entity: Entity = serde_json::from_reader(file).unwrap();
+        let contact = entity.contact().unwrap();
+        assert_eq!(Some("Joe User"), contact.full_name.as_deref());
+        assert_eq!(Some("individual"), contact.kind.as_deref());
+        assert_eq!(Some("Example"), contact.organization.as_deref());
+        assert_eq!(2, contact.addresses.len());
+        assert_eq!(
+            Address {
+                types: vec!["work".into()],
+                extended_address: Some("Suite 1234".into()),
+                street: vec!["4321 Rue Somewhere".into()],
+                locality: Some("Quebec".into()),
+                region: Some("QC".into()),
+                postal_code: Some("G1V 2M2".into()),
+                country_name: Some("Canada".into()),
+                ..Address::default()
+            },
+            contact.addresses[0]
+        );
+        assert!(contact.addresses[1]
+            .label
+            .as_deref()
+            .unwrap()
+            .starts_with("123 Maple Ave\n"));
+        assert_eq!(
+            vec![TypedValue {
+                value: "joe.user@example.com".into(),
+                types: vec!["work".into()],
+                pref: None,
+            }],
+            contact.emails
+        );
+        assert_eq!(2, contact.phones.len());
+        assert_eq!("tel:+1-555-555-1234;ext=102", contact.phones[0].value);
+        assert_eq!(vec!["work", "voice"], contact.phones[0].types);
+        assert_eq!(Some(1), contact.phones[0].pref);
+        assert_eq!(
+            entity.vcard_array.unwrap().items().len(),
+            contact.items.len()
+        );
+    }
+
+    #[test]
+    fn test_contact_separate_address_components() {
+        let json = r#"["vcard",[["version",{},"text","4.0"],["fn",{},"text",""],["adr",{"CC":"US"},"text","","","","","Washington","",""],["org",{},"text","Amazon Registry Services, Inc."]]]"#;
+        let jcard: JCard = serde_json::from_str(json).unwrap();
+        let contact = Contact::from(&jcard);
+        assert_eq!(None, contact.full_name);
+        assert_eq!(
+            Some("Amazon Registry Services, Inc."),
+            contact.organization.as_deref()
+        );
+        assert_eq!(Some("US"), contact.addresses[0].country_code.as_deref());
+        assert_eq!(Some("Washington"), contact.addresses[0].region.as_deref());
+    }
+}
@@ -0,0 +1,336 @@
+//! DNSSEC key tags and DS digests (RFC 4034) of `secureDNS` data.
+
+use crate::{DsData, KeyData, SecureDns};
+#[cfg(feature = "dnssec")]
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// DNSSEC algorithm number, see https://www.iana.org/assignments/dns-sec-alg-numbers
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum DnssecAlgorithm {
+    RsaMd5,
+    Dsa,
+    RsaSha1,
+    DsaNsec3Sha1,
+    RsaSha1Nsec3Sha1,
+    RsaSha256,
+    RsaSha512,
+    EccGost,
+    EcdsaP256Sha256,
+    EcdsaP384Sha384,
+    Ed25519,
+    Ed448,
+    Unknown(u8),
+}
+
+impl From<u8> for DnssecAlgorithm {
+    fn from(number: u8) -> Self {
+        match number {
+            1 => Self::RsaMd5,
+            3 => Self::Dsa,
+            5 => Self::RsaSha1,
+            6 => Self::DsaNsec3Sha1,
+            7 => Self::RsaSha1Nsec3Sha1,
+            8 => Self::RsaSha256,
+            10 => Self::RsaSha512,
+            12 => Self::EccGost,
+            13 => Self::EcdsaP256Sha256,
+            14 => Self::EcdsaP384Sha384,
+            15 => Self::Ed25519,
+            16 => Self::Ed448,
+            number => Self::Unknown(number),
+        }
+    }
+}
+
+impl From<DnssecAlgorithm> for u8 {
+    fn from(algorithm: DnssecAlgorithm) -> Self {
+        match algorithm {
+            DnssecAlgorithm::RsaMd5 => 1,
+            DnssecAlgorithm::Dsa => 3,
+            DnssecAlgorithm::RsaSha1 => 5,
+            DnssecAlgorithm::DsaNsec3Sha1 => 6,
+            DnssecAlgorithm::RsaSha1Nsec3Sha1 => 7,
+            DnssecAlgorithm::RsaSha256 => 8,
+            DnssecAlgorithm::RsaSha512 => 10,
+            DnssecAlgorithm::EccGost => 12,
+            DnssecAlgorithm::EcdsaP256Sha256 => 13,
+            DnssecAlgorithm::EcdsaP384Sha384 => 14,
+            DnssecAlgorithm::Ed25519 => 15,
+            DnssecAlgorithm::Ed448 => 16,
+            DnssecAlgorithm::Unknown(number) => number,
+        }
+    }
+}
+
+/// Digest algorithm of DS record, see https://www.iana.org/assignments/ds-rr-types
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(from = "u8", into = "u8")]
+pub enum DigestType {
+    Sha1,
+    Sha256,
+    Gost,
+    Sha384,
+    Unknown(u8),
+}
+
+impl From<u8> for DigestType {
+    fn from(number: u8) -> Self {
+        match number {
+            1 => Self::Sha1,
+            2 => Self::Sha256,
+            3 => Self::Gost,
+            4 => Self::Sha384,
+            number => Self::Unknown(number),
+        }
+    }
+}
+
+impl From<DigestType> for u8 {
+    fn from(digest_type: DigestType) -> Self {
+        match digest_type {
+            DigestType::Sha1 => 1,
+            DigestType::Sha256 => 2,
+            DigestType::Gost => 3,
+            DigestType::Sha384 => 4,
+            DigestType::Unknown(number) => number,
+        }
+    }
+}
+
+impl KeyData {
+    pub fn new<S: Into<String>>(
+        flags: u16,
+        protocol: u8,
+        algorithm: DnssecAlgorithm,
+        public_key: S,
+    ) -> Self {
+        Self {
+            flags,
+            protocol,
+            public_key: public_key.into(),
+            algorithm,
+            events: None,
+            links: None,
+        }
+    }
+
+    pub fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    pub fn protocol(&self) -> u8 {
+        self.protocol
+    }
+
+    /// Public key in Base64.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    pub fn algorithm(&self) -> DnssecAlgorithm {
+        self.algorithm
+    }
+
+    /// Key is secure entry point (KSK), that is referenced by DS records in parent zone.
+    pub fn is_secure_entry_point(&self) -> bool {
+        self.flags & 0x0001 != 0
+    }
+
+    /// Returns DNSKEY RDATA in wire format (RFC 4034 section 2.1).
+    #[cfg(feature = "dnssec")]
+    pub fn rdata(&self) -> Result<Vec<u8>, &'static str> {
+        // Some registries split key to more lines.
+        let public_key: String = self
+            .public_key
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let public_key = base64::engine::general_purpose::STANDARD
+            .decode(public_key)
+            .map_err(|_| "public key is not valid Base64")?;
+        let mut rdata = Vec::with_capacity(4 + public_key.len());
+        rdata.extend_from_slice(&self.flags.to_be_bytes());
+        rdata.push(self.protocol);
+        rdata.push(self.algorithm.into());
+        rdata.extend_from_slice(&public_key);
+        Ok(rdata)
+    }
+
+    /// Computes key tag (RFC 4034 appendix B), that identifies key in DS records.
+    #[cfg(feature = "dnssec")]
+    pub fn key_tag(&self) -> Result<u16, &'static str> {
+        let rdata = self.rdata()?;
+        if self.algorithm == DnssecAlgorithm::RsaMd5 {
+            // Most significant 16 bits of the least significant 24 bits of modulus.
+            return match rdata.len() {
+                len if len >= 7 => Ok(u16::from_be_bytes([rdata[len - 3], rdata[len - 2]])),
+                _ => Err("public key is too short"),
+            };
+        }
+        let mut sum: u32 = 0;
+        for (i, byte) in rdata.iter().enumerate() {
+            sum += if i % 2 == 0 {
+                u32::from(*byte) << 8
+            } else {
+                u32::from(*byte)
+            };
+        }
+        sum += (sum >> 16) & 0xffff;
+        Ok((sum & 0xffff) as u16)
+    }
+}
+
+/// Returns domain name in canonical wire format (RFC 4034 section 6.2).
+#[cfg(feature = "dnssec")]
+fn owner_name_wire(owner: &str) -> Result<Vec<u8>, &'static str> {
+    let owner = owner.strip_suffix('.').unwrap_or(owner);
+    let mut wire = Vec::with_capacity(owner.len() + 2);
+    if !owner.is_empty() {
+        for label in owner.split('.') {
+            if label.is_empty() || label.len() > 63 || !label.is_ascii() {
+                return Err("invalid owner name");
+            }
+            wire.push(label.len() as u8);
+            wire.extend(label.bytes().map(|b| b.to_ascii_lowercase()));
+        }
+    }
+    wire.push(0);
+    Ok(wire)
+}
+
+impl DsData {
+    pub fn new<S: Into<String>>(
+        key_tag: u16,
+        algorithm: DnssecAlgorithm,
+        digest_type: DigestType,
+        digest: S,
+    ) -> Self {
+        Self {
+            key_tag: Some(key_tag),
+            algorithm,
+            digest: digest.into(),
+            digest_type,
+            events: None,
+            links: None,
+        }
+    }
+
+    /// Computes DS record of key of zone `owner` (RFC 4034 section 5.1.4), `owner` must be in
+    /// A-label form. SHA-1, SHA-256 and SHA-384 digests are supported.
+    #[cfg(feature = "dnssec")]
+    pub fn from_key(
+        owner: &str,
+        key: &KeyData,
+        digest_type: DigestType,
+    ) -> Result<Self, &'static str> {
+        use sha1::Digest;
+
+        let mut data = owner_name_wire(owner)?;
+        data.extend_from_slice(&key.rdata()?);
+        let digest = match digest_type {
+            DigestType::Sha1 => sha1::Sha1::digest(&data).to_vec(),
+            DigestType::Sha256 => sha2::Sha256::digest(&data).to_vec(),
+            DigestType::Sha384 => sha2::Sha384::digest(&data).to_vec(),
+            _ => return Err("unsupported digest type"),
+        };
+        let digest: String = digest.iter().map(|b| format!("{:02X}", b)).collect();
+        Ok(Self::new(
+            key.key_tag()?,
+            key.algorithm,
+            digest_type,
+            digest,
+        ))
+    }
+
+    pub fn key_tag(&self) -> Option<u16> {
+        self.key_tag
+    }
+
+    pub fn algorithm(&self) -> DnssecAlgorithm {
+        self.algorithm
+    }
+
+    /// Digest in hexadecimal form.
+    pub fn digest(&self) -> &str {
+        &self.digest
+    }
+
+    pub fn digest_type(&self) -> DigestType {
+        self.digest_type
+    }
+
+    /// Checks that this DS record references `key` of zone `owner`, that is algorithm, key tag
+    /// (if present) and digest match. Returns error if digest can't be computed.
+    #[cfg(feature = "dnssec")]
+    pub fn matches(&self, owner: &str, key: &KeyData) -> Result<bool, &'static str> {
+        let expected = Self::from_key(owner, key, self.digest_type)?;
+        let digest: String = self.digest.chars().filter(|c| !c.is_whitespace()).collect();
+        Ok(self.algorithm == expected.algorithm
+            && self
+                .key_tag
+                .map_or(true, |tag| Some(tag) == expected.key_tag)
+            && digest.eq_ignore_ascii_case(&expected.digest))
+    }
+}
+
+impl SecureDns {
+    pub fn zone_signed(&self) -> Option<bool> {
+        self.zone_signed
+    }
+
+    pub fn delegation_signed(&self) -> Option<bool> {
+        self.delegation_signed
+    }
+
+    /// Maximal signature lifetime in seconds.
+    pub fn max_sig_life(&self) -> Option<u32> {
+        self.max_sig_life
+    }
+
+    pub fn ds_data(&self) -> &[DsData] {
+        self.ds_data.as_deref().unwrap_or_default()
+    }
+
+    pub fn key_data(&self) -> &[KeyData] {
+        self.key_data.as_deref().unwrap_or_default()
+    }
+}
+
+#[cfg(all(test, feature = "dnssec"))]
+mod tests {
+    use super::{DigestType, DnssecAlgorithm};
+    use crate::{DsData, KeyData};
+
+    // Example from RFC 4034 section 5.4 and RFC 4509 section 2.2.
+    const PUBLIC_KEY: &str = "AQOeiiR0GOMYkDshWoSKz9XzfwJr1AYtsmx3TGkJaNXVbfi/2pHm822aJ5iI9BMz
+        NXxeYCmZDRD99WYwYqUSdjMmmAphXdvxegXd/M5+X7OrzKBaMbCVdFLUUh6DhweJBjEVv5f2wwjM9XzcnOf+EPbt
+        G9DMBmADjFDc2w/rljwvFw==";
+
+    #[test]
+    fn test_dnssec() {
+        let key = KeyData::new(256, 3, DnssecAlgorithm::RsaSha1, PUBLIC_KEY);
+        assert_eq!(Ok(60485), key.key_tag());
+        assert!(!key.is_secure_entry_point());
+
+        let ds = DsData::from_key("dskey.example.com.", &key, DigestType::Sha1).unwrap();
+        assert_eq!("2BB183AF5F22588179A53B0A98631FAD1A292118", ds.digest());
+        assert_eq!(Some(60485), ds.key_tag());
+        let ds = DsData::from_key("DSKEY.example.com", &key, DigestType::Sha256).unwrap();
+        assert_eq!(
+            "D4B7D520E7BB5F0F67674A0CCEB1E3E0614B93C4F9E99B8383F6A1E4469DA50A",
+            ds.digest()
+        );
+        assert_eq!(Ok(true), ds.matches("dskey.example.com", &key));
+        assert_eq!(Ok(false), ds.matches("example.com", &key));
+        assert!(DsData::from_key("example.com", &key, DigestType::Gost).is_err());
+
+        let json = r#"{"keyTag":60485,"algorithm":5,"digestType":1,"digest":"2bb183af5f22588179a53b0a98631fad1a292118"}"#;
+        let ds: DsData = serde_json::from_str(json).unwrap();
+        assert_eq!(DnssecAlgorithm::RsaSha1, ds.algorithm());
+        assert_eq!(DigestType::Sha1, ds.digest_type());
+        assert_eq!(Ok(true), ds.matches("dskey.example.com", &key));
+        assert_eq!(5, serde_json::to_value(&ds).unwrap()["algorithm"]);
+    }
+}
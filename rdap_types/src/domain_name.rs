@@ -0,0 +1,177 @@
+//! Domain names in A-label or U-label form.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// Name of domain or nameserver, like `xn--pklad-zsa96e.cz` in `ldhName` or `příklad.cz` in
+/// `unicodeName`. Names are compared case insensitively and without trailing dot, but original
+/// form is kept.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct DomainName(String);
+
+impl DomainName {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self(name.into())
+    }
+
+    /// Converts name with U-labels, like `příklad.cz`, to A-labels.
+    pub fn from_unicode(name: &str) -> Result<Self, &'static str> {
+        idna::domain_to_ascii(name)
+            .map(Self)
+            .map_err(|_| "invalid domain name")
+    }
+
+    /// Returns name with A-labels converted to U-labels, like `příklad.cz` for
+    /// `xn--pklad-zsa96e.cz`. Labels that are not valid are kept as they are.
+    pub fn to_unicode(&self) -> String {
+        idna::domain_to_unicode(&self.0).0
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Returns labels of name from the leftmost, trailing dot is ignored.
+    pub fn labels(&self) -> impl DoubleEndedIterator<Item = &str> {
+        let name = self.without_trailing_dot();
+        (!name.is_empty())
+            .then(|| name.split('.'))
+            .into_iter()
+            .flatten()
+    }
+
+    fn without_trailing_dot(&self) -> &str {
+        self.0.trim_end_matches('.')
+    }
+
+    fn normalized(&self) -> impl Iterator<Item = char> + '_ {
+        normalized(&self.0)
+    }
+}
+
+/// Characters of name in lowercase and without trailing dot, used for comparison.
+fn normalized(name: &str) -> impl Iterator<Item = char> + '_ {
+    name.trim_end_matches('.')
+        .chars()
+        .flat_map(char::to_lowercase)
+}
+
+impl Deref for DomainName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for DomainName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for DomainName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for DomainName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq for DomainName {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized().eq(other.normalized())
+    }
+}
+
+impl Eq for DomainName {}
+
+impl Hash for DomainName {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for c in self.normalized() {
+            state.write_u32(c as u32);
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl PartialEq<str> for DomainName {
+    fn eq(&self, other: &str) -> bool {
+        self.normalized().eq(normalized(other))
+    }
+}
+
+impl PartialEq<&str> for DomainName {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<DomainName> for str {
+    fn eq(&self, other: &DomainName) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<DomainName> for &str {
+    fn eq(&self, other: &DomainName) -> bool {
+        other == *self
+    }
+}
+
+impl From<String> for DomainName {
+    fn from(name: String) -> Self {
+        Self(name)
+    }
+}
+
+impl From<&str> for DomainName {
+    fn from(name: &str) -> Self {
+        Self(name.to_owned())
+    }
+}
+
+impl From<DomainName> for String {
+    fn from(name: DomainName) -> Self {
+        name.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DomainName;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_domain_name() {
+        let name = DomainName::from("NS1.xn--pklad-zsa96e.cz.");
+        assert_eq!("ns1.příklad.cz.", name.to_unicode());
+        assert_eq!(name, DomainName::from_unicode("ns1.Příklad.cz").unwrap());
+        assert!(DomainName::from_unicode("xn--.cz").is_err());
+        assert_eq!(
+            vec!["NS1", "xn--pklad-zsa96e", "cz"],
+            name.labels().collect::<Vec<_>>()
+        );
+        assert_eq!(0, DomainName::from(".").labels().count());
+
+        assert_eq!("ns1.xn--pklad-zsa96e.cz", name);
+        assert_ne!("ns1.xn--pklad-zsa96e", name);
+        assert_eq!(
+            DomainName::from("Příklad.cz"),
+            DomainName::from("příklad.CZ.")
+        );
+        let names: HashSet<DomainName> = ["EXAMPLE.com.".into(), "example.COM".into()].into();
+        assert_eq!(1, names.len());
+    }
+}
@@ -0,0 +1,152 @@
+//! Handles of objects with object tags (RFC 8521).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+
+/// Registry-unique identifier of object, like `XXXX-RIPE`. Handles are compared case
+/// insensitively, but original case is kept.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(transparent)]
+pub struct Handle(String);
+
+impl Handle {
+    pub fn new<S: Into<String>>(handle: S) -> Self {
+        Self(handle.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Returns handle without object tag, like `XXXX` for `XXXX-RIPE`.
+    pub fn base(&self) -> &str {
+        split_handle(&self.0).0
+    }
+
+    /// Returns object tag (RFC 8521), that is part after the last hyphen, like `RIPE` for
+    /// `XXXX-RIPE`.
+    pub fn tag(&self) -> Option<&str> {
+        split_handle(&self.0).1
+    }
+}
+
+/// Splits handle to base and object tag (RFC 8521), that is part after the last hyphen.
+pub fn split_handle(handle: &str) -> (&str, Option<&str>) {
+    match handle.rsplit_once('-') {
+        Some((base, tag)) if !tag.is_empty() => (base, Some(tag)),
+        _ => (handle, None),
+    }
+}
+
+impl Deref for Handle {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Handle {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Display for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq for Handle {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl Eq for Handle {}
+
+impl Hash for Handle {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.0.bytes() {
+            state.write_u8(byte.to_ascii_lowercase());
+        }
+        state.write_u8(0xff);
+    }
+}
+
+impl PartialEq<str> for Handle {
+    fn eq(&self, other: &str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<&str> for Handle {
+    fn eq(&self, other: &&str) -> bool {
+        self.0.eq_ignore_ascii_case(other)
+    }
+}
+
+impl PartialEq<Handle> for str {
+    fn eq(&self, other: &Handle) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<Handle> for &str {
+    fn eq(&self, other: &Handle) -> bool {
+        other == self
+    }
+}
+
+impl From<String> for Handle {
+    fn from(handle: String) -> Self {
+        Self(handle)
+    }
+}
+
+impl From<&str> for Handle {
+    fn from(handle: &str) -> Self {
+        Self(handle.to_owned())
+    }
+}
+
+impl From<Handle> for String {
+    fn from(handle: Handle) -> Self {
+        handle.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Handle;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_handle() {
+        let handle = Handle::from("XXXX-RIPE");
+        assert_eq!("XXXX", handle.base());
+        assert_eq!(Some("RIPE"), handle.tag());
+        assert_eq!(None, Handle::from("12345").tag());
+        assert_eq!(None, Handle::from("ABC-").tag());
+        assert_eq!(Some("ARIN"), Handle::from("A-B-ARIN").tag());
+
+        assert_eq!(Handle::from("xxxx-ripe"), handle);
+        assert_eq!("xxxx-ripe", handle);
+        assert_eq!("XXXX-RIPE", handle.to_string());
+        let handles: HashSet<Handle> = ["XXXX-RIPE".into(), "xxxx-ripe".into()].into();
+        assert_eq!(1, handles.len());
+    }
+}
@@ -0,0 +1,483 @@
+//! JSContact (RFC 9553) card, that servers with `jscard` extension return in entity instead of or
+//! in addition to jCard, and conversion between both representations.
+
+use crate::contact::{Address, Contact};
+use crate::{Entity, JCard, JCardItem, JCardItemDataType, JCardType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// https://www.rfc-editor.org/rfc/rfc9553#section-2
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct JSContactCard {
+    #[serde(rename = "@type", default = "card_type")]
+    pub r#type: String,
+    #[serde(default = "card_version")]
+    pub version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uid: Option<String>,
+    /// Like `individual`, `org` or `group`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<JSContactName>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organizations: Option<BTreeMap<String, JSContactOrganization>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emails: Option<BTreeMap<String, JSContactEmail>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub phones: Option<BTreeMap<String, JSContactPhone>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addresses: Option<BTreeMap<String, JSContactAddress>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<BTreeMap<String, JSContactLink>>,
+    /// Other properties, like `localizations` or `titles`.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, Value>,
+}
+
+fn card_type() -> String {
+    "Card".to_owned()
+}
+
+fn card_version() -> String {
+    "1.0".to_owned()
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9553#section-2.2.1
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JSContactName {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<JSContactComponent>>,
+}
+
+/// Component of name or address, `kind` is for example `given`, `surname`, `locality` or
+/// `postcode`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JSContactComponent {
+    pub kind: String,
+    pub value: String,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9553#section-2.2.3
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JSContactOrganization {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub units: Option<Vec<JSContactOrgUnit>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JSContactOrgUnit {
+    pub name: String,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9553#section-2.3.1
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JSContactEmail {
+    pub address: String,
+    /// Like `work` or `private`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<BTreeMap<String, bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9553#section-2.3.3
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JSContactPhone {
+    /// Phone number, usually `tel:` URI.
+    pub number: String,
+    /// Like `voice`, `fax` or `mobile`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub features: Option<BTreeMap<String, bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<BTreeMap<String, bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9553#section-2.5.1
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct JSContactAddress {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<JSContactComponent>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country_code: Option<String>,
+    /// Formatted address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub full: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contexts: Option<BTreeMap<String, bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pref: Option<u8>,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9553#section-2.6.3
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JSContactLink {
+    pub uri: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+/// Phone features of JSContact and matching values of vCard `type` parameter.
+const PHONE_FEATURES: [(&str, &str); 7] = [
+    ("voice", "voice"),
+    ("fax", "fax"),
+    ("mobile", "cell"),
+    ("text", "text"),
+    ("video", "video"),
+    ("pager", "pager"),
+    ("textphone", "textphone"),
+];
+
+/// Contexts of JSContact and matching values of vCard `type` parameter.
+const CONTEXTS: [(&str, &str); 2] = [("work", "work"), ("private", "home")];
+
+impl From<&JCard> for JSContactCard {
+    fn from(jcard: &JCard) -> Self {
+        let contact = Contact::from(jcard);
+        let components = jcard
+            .items_by_name("n")
+            .first()
+            .map(|item| name_components(item))
+            .filter(|components| !components.is_empty());
+        let name = match (contact.full_name, components) {
+            (None, None) => None,
+            (full, components) => Some(JSContactName { full, components }),
+        };
+        let organizations = contact.organization.map(|name| {
+            let organization = JSContactOrganization {
+                name: Some(name),
+                units: None,
+            };
+            ids("org", vec![organization])
+        });
+        let emails = contact.emails.into_iter().map(|email| JSContactEmail {
+            contexts: contexts(&email.types),
+            pref: email.pref,
+            address: email.value,
+        });
+        let phones = contact.phones.into_iter().map(|phone| {
+            let features: BTreeMap<_, _> = PHONE_FEATURES
+                .iter()
+                .filter(|(_, vcard)| phone.types.iter().any(|t| t.eq_ignore_ascii_case(vcard)))
+                .map(|(feature, _)| (feature.to_string(), true))
+                .collect();
+            JSContactPhone {
+                features: Some(features).filter(|features| !features.is_empty()),
+                contexts: contexts(&phone.types),
+                pref: phone.pref,
+                number: phone.value,
+            }
+        });
+        let addresses = contact.addresses.iter().map(JSContactAddress::from);
+        Self {
+            r#type: card_type(),
+            version: card_version(),
+            kind: contact.kind,
+            name,
+            organizations,
+            emails: non_empty(ids("email", emails.collect())),
+            phones: non_empty(ids("phone", phones.collect())),
+            addresses: non_empty(ids("addr", addresses.collect())),
+            ..Self::default()
+        }
+    }
+}
+
+impl From<&Address> for JSContactAddress {
+    fn from(address: &Address) -> Self {
+        let mut components = Vec::new();
+        let mut push = |kind: &str, value: &Option<String>| {
+            if let Some(value) = value {
+                components.push(JSContactComponent {
+                    kind: kind.to_owned(),
+                    value: value.clone(),
+                });
+            }
+        };
+        push("postOfficeBox", &address.post_office_box);
+        push("apartment", &address.extended_address);
+        for street in &address.street {
+            push("name", &Some(street.clone()));
+        }
+        push("locality", &address.locality);
+        push("region", &address.region);
+        push("postcode", &address.postal_code);
+        push("country", &address.country_name);
+        Self {
+            components: Some(components).filter(|components| !components.is_empty()),
+            country_code: address.country_code.clone(),
+            full: address.label.clone(),
+            contexts: contexts(&address.types),
+            pref: None,
+        }
+    }
+}
+
+impl From<&JSContactCard> for JCard {
+    fn from(card: &JSContactCard) -> Self {
+        let mut items = vec![item("version", Vec::new(), "4.0".into())];
+        let full_name = card.name.as_ref().and_then(|name| {
+            name.full.clone().or_else(|| {
+                let components = name.components.as_ref()?;
+                let values: Vec<_> = components.iter().map(|c| c.value.as_str()).collect();
+                Some(values.join(" "))
+            })
+        });
+        items.push(item("fn", Vec::new(), full_name.unwrap_or_default().into()));
+        if let Some(kind) = &card.kind {
+            items.push(item("kind", Vec::new(), kind.as_str().into()));
+        }
+        for organization in card.organizations.iter().flat_map(BTreeMap::values) {
+            if let Some(name) = &organization.name {
+                items.push(item("org", Vec::new(), name.as_str().into()));
+            }
+        }
+        for address in card.addresses.iter().flat_map(BTreeMap::values) {
+            items.push(address_item(address));
+        }
+        for phone in card.phones.iter().flat_map(BTreeMap::values) {
+            let mut types = vcard_types(&phone.contexts);
+            for (feature, vcard) in PHONE_FEATURES {
+                if is_set(&phone.features, feature) {
+                    types.push(vcard);
+                }
+            }
+            let mut tel = item("tel", types, phone.number.as_str().into());
+            if phone.number.starts_with("tel:") {
+                tel.type_identifier = JCardItemDataType::Uri;
+            }
+            set_pref(&mut tel, phone.pref);
+            items.push(tel);
+        }
+        for email in card.emails.iter().flat_map(BTreeMap::values) {
+            let types = vcard_types(&email.contexts);
+            let mut email_item = item("email", types, email.address.as_str().into());
+            set_pref(&mut email_item, email.pref);
+            items.push(email_item);
+        }
+        JCard(JCardType::Vcard, items)
+    }
+}
+
+impl Entity {
+    /// Returns contact of entity converted to JSContact, either `jscard` member or converted
+    /// jCard.
+    pub fn jscontact(&self) -> Option<JSContactCard> {
+        match (&self.jscard, &self.vcard_array) {
+            (Some(card), _) => Some((**card).clone()),
+            (None, Some(jcard)) => Some(JSContactCard::from(jcard)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Converts `n` property to name components.
+fn name_components(item: &JCardItem) -> Vec<JSContactComponent> {
+    let values = match &item.values[..] {
+        [Value::Array(values)] => &values[..],
+        values => values,
+    };
+    let kinds = ["surname", "given", "given2", "title", "credential"];
+    kinds
+        .iter()
+        .zip(values)
+        .flat_map(|(kind, value)| {
+            let values = match value {
+                Value::Array(values) => values.clone(),
+                value => vec![value.clone()],
+            };
+            values.into_iter().filter_map(move |value| match value {
+                Value::String(value) if !value.is_empty() => Some(JSContactComponent {
+                    kind: kind.to_string(),
+                    value,
+                }),
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+/// Converts structured address to `adr` item with seven components.
+fn address_item(address: &JSContactAddress) -> JCardItem {
+    let component = |kinds: &[&str]| {
+        let values: Vec<_> = address
+            .components
+            .iter()
+            .flatten()
+            .filter(|component| kinds.contains(&component.kind.as_str()))
+            .map(|component| component.value.as_str())
+            .collect();
+        Value::from(values.join(" "))
+    };
+    let components = vec![
+        component(&["postOfficeBox"]),
+        component(&["apartment", "room", "floor", "building"]),
+        component(&["number", "name", "direction", "block"]),
+        component(&["locality", "district", "subdistrict"]),
+        component(&["region"]),
+        component(&["postcode"]),
+        component(&["country"]),
+    ];
+    let mut adr = item(
+        "adr",
+        vcard_types(&address.contexts),
+        Value::Array(components),
+    );
+    if let Some(full) = &address.full {
+        adr.parameters.insert("label".into(), full.as_str().into());
+    }
+    if let Some(country_code) = &address.country_code {
+        adr.parameters
+            .insert("cc".into(), country_code.as_str().into());
+    }
+    set_pref(&mut adr, address.pref);
+    adr
+}
+
+fn item(name: &str, types: Vec<&str>, value: Value) -> JCardItem {
+    let mut parameters = serde_json::Map::new();
+    match &types[..] {
+        [] => {}
+        [single] => {
+            parameters.insert("type".into(), (*single).into());
+        }
+        types => {
+            parameters.insert("type".into(), types.to_vec().into());
+        }
+    }
+    JCardItem {
+        property_name: name.to_owned(),
+        parameters,
+        type_identifier: JCardItemDataType::Text,
+        values: vec![value],
+    }
+}
+
+fn set_pref(item: &mut JCardItem, pref: Option<u8>) {
+    if let Some(pref) = pref {
+        item.parameters
+            .insert("pref".into(), pref.to_string().into());
+    }
+}
+
+/// Converts values of vCard `type` parameter to JSContact contexts.
+fn contexts(types: &[String]) -> Option<BTreeMap<String, bool>> {
+    let contexts: BTreeMap<_, _> = CONTEXTS
+        .iter()
+        .filter(|(_, vcard)| types.iter().any(|t| t.eq_ignore_ascii_case(vcard)))
+        .map(|(context, _)| (context.to_string(), true))
+        .collect();
+    Some(contexts).filter(|contexts| !contexts.is_empty())
+}
+
+/// Converts JSContact contexts to values of vCard `type` parameter.
+fn vcard_types(contexts: &Option<BTreeMap<String, bool>>) -> Vec<&'static str> {
+    CONTEXTS
+        .iter()
+        .filter(|(context, _)| is_set(contexts, context))
+        .map(|(_, vcard)| *vcard)
+        .collect()
+}
+
+fn is_set(map: &Option<BTreeMap<String, bool>>, key: &str) -> bool {
+    map.as_ref()
+        .and_then(|map| map.get(key))
+        .copied()
+        .unwrap_or(false)
+}
+
+/// Assigns ids to values, like `email`, `email2` and so on.
+fn ids<T>(prefix: &str, values: Vec<T>) -> BTreeMap<String, T> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(index, value)| match index {
+            0 => (prefix.to_owned(), value),
+            index => (format!("{}{}", prefix, index + 1), value),
+        })
+        .collect()
+}
+
+fn non_empty<T>(map: BTreeMap<String, T>) -> Option<BTreeMap<String, T>> {
+    Some(map).filter(|map| !map.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JSContactCard;
+    use crate::{Contact, Entity, JCard};
+    use std::fs::File;
+
+    fn entity(path: &str) -> Entity {
+        let file = File::open(format!("test_data/{}", path)).unwrap();
+        serde_json::from_reader(file).unwrap()
+    }
+
+    #[test]
+    fn test_parse_jscard() {
+        let entity = entity("entity/entity_jscard.json");
+        let card = entity.jscard.as_ref().unwrap();
+        assert_eq!("Card", card.r#type);
+        assert_eq!(Some("individual"), card.kind.as_deref());
+        let email = &card.emails.as_ref().unwrap()["email"];
+        assert_eq!("joe.user@example.com", email.address);
+        assert!(card.other.contains_key("titles"));
+
+        let json = serde_json::to_value(card).unwrap();
+        assert_eq!(Some("Card"), json["@type"].as_str());
+        assert!(json["titles"].is_object());
+
+        // Entity without jCard is converted to contact from JSContact.
+        let contact = entity.contact().unwrap();
+        assert_eq!(Some("Joe User"), contact.full_name.as_deref());
+        assert_eq!(Some("Example"), contact.organization.as_deref());
+        assert_eq!(vec!["work", "voice"], contact.phones[0].types);
+        assert_eq!(Some("CA"), contact.addresses[0].country_code.as_deref());
+        assert_eq!(vec!["4321 Rue Somewhere"], contact.addresses[0].street);
+    }
+
+    #[test]
+    fn test_jcard_to_jscontact() {
+        let entity = entity("entity/entity_15.json");
+        let jcard = entity.vcard_array.as_ref().unwrap();
+        let card = entity.jscontact().unwrap();
+        let name = card.name.as_ref().unwrap();
+        assert_eq!(Some("Joe User"), name.full.as_deref());
+        assert_eq!(4, name.components.as_ref().unwrap().len());
+        let phones = card.phones.as_ref().unwrap();
+        assert_eq!(2, phones.len());
+        assert!(phones["phone2"].features.as_ref().unwrap()["mobile"]);
+        assert_eq!(2, card.addresses.as_ref().unwrap().len());
+
+        let original = Contact::from(jcard);
+        let converted = Contact::from(&JCard::from(&card));
+        assert_eq!(original.full_name, converted.full_name);
+        assert_eq!(original.kind, converted.kind);
+        assert_eq!(original.organization, converted.organization);
+        assert_eq!(original.addresses, converted.addresses);
+        assert_eq!(original.emails, converted.emails);
+        assert_eq!(original.phones[0], converted.phones[0]);
+    }
+
+    #[test]
+    fn test_empty_jcard() {
+        let jcard: JCard =
+            serde_json::from_str(r#"["vcard",[["version",{},"text","4.0"]]]"#).unwrap();
+        let card = JSContactCard::from(&jcard);
+        assert_eq!(
+            r#"{"@type":"Card","version":"1.0"}"#,
+            serde_json::to_string(&card).unwrap()
+        );
+    }
+}
@@ -1,12 +1,49 @@
 use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::str::FromStr;
 
-use chrono::{DateTime, FixedOffset, Offset, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, TimeZone, Utc};
 use serde::de::{IntoDeserializer, SeqAccess, Unexpected, Visitor};
-use serde::ser::SerializeSeq;
+use serde::ser::{SerializeSeq, SerializeStruct};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod borrowed;
+mod builder;
+mod canonical;
+mod contact;
+mod dnssec;
+mod domain_name;
+mod handle;
+mod jscontact;
+mod network;
+#[cfg(feature = "time")]
+mod offset_date_time;
+mod options;
+mod rdap_object;
+mod validate;
+mod vcf;
+
+pub use builder::{
+    AutNumBuilder, BuildError, DomainBuilder, EntityBuilder, IpNetworkBuilder, NameserverBuilder,
+};
+pub use canonical::Canonicalize;
+pub use contact::{Address, Contact, TypedValue};
+pub use dnssec::{DigestType, DnssecAlgorithm};
+pub use domain_name::DomainName;
+pub use handle::{split_handle, Handle};
+pub use jscontact::{
+    JSContactAddress, JSContactCard, JSContactComponent, JSContactEmail, JSContactLink,
+    JSContactName, JSContactOrgUnit, JSContactOrganization, JSContactPhone,
+};
+#[cfg(feature = "time")]
+pub use offset_date_time::{from_offset_date_time, to_offset_date_time};
+pub use options::ParseOptions;
+pub use rdap_object::RdapObject;
+pub use validate::{Violation, ViolationKind};
+
 fn deserialize_string_lowercase<'de, D>(deserializer: D) -> Result<String, D::Error>
 where
     D: Deserializer<'de>,
@@ -14,7 +51,7 @@ where
     let mut string = String::deserialize(deserializer)?;
     // Just convert in case that string contains uppercase character
     // This solution is about 70% faster than convert it in all cases
-    if string.chars().any(|c| c.is_uppercase()) {
+    if ParseOptions::accepts_lenient_values() && string.chars().any(|c| c.is_uppercase()) {
         string = string.to_lowercase();
     }
     Ok(string)
@@ -27,30 +64,36 @@ where
 /// - %Y-%m-%dT%H:%M:%S
 /// - %Y-%m-%dT%H:%M:%SZ%z
 /// - %Y-%m-%d %H:%M:%S
+///
+/// Only RFC 3339 format is accepted when lenient datetimes are disabled in [`ParseOptions`].
 fn deserialize_datetime<'de, D>(deserializer: D) -> Result<DateTime<FixedOffset>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let string = String::deserialize(deserializer)?;
+    parse_datetime(&string).map_err(serde::de::Error::custom)
+}
 
-    DateTime::parse_from_rfc3339(&string)
-        .or_else(|_| {
-            if string.contains('T') {
-                Utc.datetime_from_str(&string, "%Y-%m-%dT%H:%M:%S")
-                    .map(|d| d.with_timezone(&Utc.fix()))
-                    .or_else(|_| DateTime::parse_from_str(&string, "%Y-%m-%dT%H:%M:%SZ%z"))
-            } else {
-                Utc.datetime_from_str(&string, "%Y-%m-%d %H:%M:%S")
-                    .map(|d| d.with_timezone(&Utc.fix())) // for `xn--rhqv96g` domain
-            }
-        })
-        .map_err(serde::de::Error::custom)
+/// Parses datetime in formats accepted by `deserialize_datetime`.
+fn parse_datetime(string: &str) -> Result<DateTime<FixedOffset>, chrono::ParseError> {
+    DateTime::parse_from_rfc3339(string).or_else(|e| {
+        if !ParseOptions::accepts_lenient_datetimes() {
+            Err(e)
+        } else if string.contains('T') {
+            NaiveDateTime::parse_from_str(string, "%Y-%m-%dT%H:%M:%S")
+                .map(|d| Utc.from_utc_datetime(&d).with_timezone(&Utc.fix()))
+                .or_else(|_| DateTime::parse_from_str(string, "%Y-%m-%dT%H:%M:%SZ%z"))
+        } else {
+            NaiveDateTime::parse_from_str(string, "%Y-%m-%d %H:%M:%S")
+                .map(|d| Utc.from_utc_datetime(&d).with_timezone(&Utc.fix())) // for `xn--rhqv96g` domain
+        }
+    })
 }
 
 /// Two letters (usually ISO 3166-1) country code.
 // Some registries uses codes that are not ISO 3166-1 countries (for example RIPe uses 'EU'
 // as country), so we store that string as two bytes and not as for example isocountry::CountryCode.
-#[derive(PartialEq)]
+#[derive(PartialEq, Clone, Eq, Hash)]
 pub struct CountryCode([u8; 2]);
 
 impl FromStr for CountryCode {
@@ -120,7 +163,7 @@ impl<'de> Deserialize<'de> for CountryCode {
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-4.2
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Link {
     /// This is optional in RFC 7483, but became mandatory in 9083.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -144,7 +187,7 @@ pub struct Link {
 ///
 /// [RFC 7483]: https://tools.ietf.org/html/rfc7483#section-10.2.4
 /// [RDAP JSON Values]: https://www.iana.org/assignments/rdap-json-values/rdap-json-values.xhtml
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Eq, Hash)]
 #[serde(rename_all = "lowercase", remote = "Role")]
 pub enum Role {
     /// The entity object instance is the registrant of the registration. In some registries, this is known as a maintainer.
@@ -184,7 +227,7 @@ impl<'de> Deserialize<'de> for Role {
             "technical" => Self::Technical,
             "administrative" => Self::Administrative,
             "abuse" => Self::Abuse,
-            "biilling" => Self::Billing,
+            "billing" => Self::Billing,
             "registrar" => Self::Registrar,
             "reseller" => Self::Reseller,
             "sponsor" => Self::Sponsor,
@@ -206,20 +249,20 @@ impl Serialize for Role {
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-4.8
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PublicId {
     pub r#type: String,
     pub identifier: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum JCardType {
     Vcard,
 }
 
 /// https://tools.ietf.org/html/rfc6350#section-4
-#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "kebab-case")]
 #[serde(remote = "JCardItemDataType")]
 pub enum JCardItemDataType {
@@ -260,7 +303,7 @@ impl Serialize for JCardItemDataType {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JCardItem {
     pub property_name: String,
     pub parameters: serde_json::Map<String, serde_json::Value>,
@@ -338,10 +381,15 @@ impl Serialize for JCardItem {
 }
 
 /// https://tools.ietf.org/html/rfc7095
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JCard(JCardType, Vec<JCardItem>);
 
 impl JCard {
+    /// Creates vCard of given items, `version` item is not added automatically.
+    pub fn new(items: Vec<JCardItem>) -> Self {
+        Self(JCardType::Vcard, items)
+    }
+
     pub fn typ(&self) -> JCardType {
         self.0
     }
@@ -356,13 +404,16 @@ impl JCard {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Entity {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub handle: Option<String>,
+    pub handle: Option<Handle>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vcard_array: Option<JCard>,
+    /// JSContact card from `jscard` extension, older servers use `jscard_0` member.
+    #[serde(alias = "jscard_0", skip_serializing_if = "Option::is_none")]
+    pub jscard: Option<Box<JSContactCard>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub roles: Option<Vec<Role>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -383,9 +434,20 @@ pub struct Entity {
     pub port43: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lang: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rdap_conformance: Option<Conformance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notices: Option<Vec<NoticeOrRemark>>,
+    // redacted extension
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redacted: Option<Vec<Redacted>>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(tag = "objectClassName", rename_all = "lowercase")]
 pub enum Object {
     AutNum(AutNum),
@@ -399,7 +461,7 @@ pub enum Object {
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-10.2.2
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Eq, Hash)]
 #[serde(rename_all = "lowercase", from = "String")]
 pub enum Status {
     Validated,
@@ -514,7 +576,135 @@ impl From<String> for Status {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+/// Pairs of EPP status codes (RFC 5731, 5732, 5733 and 3915) and RDAP statuses as mapped by
+/// RFC 8056.
+const EPP_STATUSES: [(&str, Status); 25] = [
+    ("addPeriod", Status::AddPeriod),
+    ("autoRenewPeriod", Status::AutoRenewPeriod),
+    ("clientDeleteProhibited", Status::ClientDeleteProhibited),
+    ("clientHold", Status::ClientHold),
+    ("clientRenewProhibited", Status::ClientRenewProhibited),
+    ("clientTransferProhibited", Status::ClientTransferProhibited),
+    ("clientUpdateProhibited", Status::ClientUpdateProhibited),
+    ("inactive", Status::Inactive),
+    ("linked", Status::Associated),
+    ("ok", Status::Active),
+    ("pendingCreate", Status::PendingCreate),
+    ("pendingDelete", Status::PendingDelete),
+    ("pendingRenew", Status::PendingRenew),
+    ("pendingRestore", Status::PendingRestore),
+    ("pendingTransfer", Status::PendingTransfer),
+    ("pendingUpdate", Status::PendingUpdate),
+    ("redemptionPeriod", Status::RedemptionPeriod),
+    ("renewPeriod", Status::RenewPeriod),
+    ("serverDeleteProhibited", Status::ServerDeleteProhibited),
+    ("serverRenewProhibited", Status::ServerRenewProhibited),
+    ("serverTransferProhibited", Status::ServerTransferProhibited),
+    ("serverUpdateProhibited", Status::ServerUpdateProhibited),
+    ("serverHold", Status::ServerHold),
+    ("transferPeriod", Status::TransferPeriod),
+    // Non standard RDAP status of some registries, that is EPP status without mapping.
+    ("ok", Status::Ok),
+];
+
+impl Status {
+    /// Converts EPP status code like `clientTransferProhibited` to RDAP status according to
+    /// RFC 8056.
+    pub fn from_epp(code: &str) -> Option<Self> {
+        EPP_STATUSES
+            .iter()
+            .find(|(epp, _)| *epp == code)
+            .map(|(_, status)| status.clone())
+    }
+
+    /// Returns EPP status code according to RFC 8056, `None` for statuses without EPP equivalent.
+    pub fn to_epp(&self) -> Option<&'static str> {
+        EPP_STATUSES
+            .iter()
+            .find(|(_, status)| status == self)
+            .map(|(epp, _)| *epp)
+    }
+
+    /// Object can't be changed, transferred, renewed or deleted on request of registrar.
+    pub fn is_client_locked(&self) -> bool {
+        matches!(
+            self,
+            Self::ClientDeleteProhibited
+                | Self::ClientRenewProhibited
+                | Self::ClientTransferProhibited
+                | Self::ClientUpdateProhibited
+        )
+    }
+
+    /// Object can't be changed, transferred, renewed or deleted by registry policy.
+    pub fn is_server_locked(&self) -> bool {
+        matches!(
+            self,
+            Self::ServerDeleteProhibited
+                | Self::ServerRenewProhibited
+                | Self::ServerTransferProhibited
+                | Self::ServerUpdateProhibited
+        )
+    }
+
+    /// Any status that prohibits some action, set by registrar, registry or not specified.
+    pub fn is_prohibited(&self) -> bool {
+        self.is_client_locked()
+            || self.is_server_locked()
+            || matches!(
+                self,
+                Self::RenewProhibited
+                    | Self::UpdateProhibited
+                    | Self::TransferProhibited
+                    | Self::DeleteProhibited
+                    | Self::Locked
+            )
+    }
+
+    /// Object can't be transferred to another registrar.
+    pub fn is_transfer_prohibited(&self) -> bool {
+        matches!(
+            self,
+            Self::TransferProhibited
+                | Self::ClientTransferProhibited
+                | Self::ServerTransferProhibited
+                | Self::Locked
+        )
+    }
+
+    /// Domain is not published in DNS.
+    pub fn is_on_hold(&self) -> bool {
+        matches!(self, Self::ClientHold | Self::ServerHold)
+    }
+
+    /// Operation with object is requested but not finished yet.
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self,
+            Self::PendingCreate
+                | Self::PendingRenew
+                | Self::PendingTransfer
+                | Self::PendingUpdate
+                | Self::PendingDelete
+                | Self::PendingRestore
+        )
+    }
+
+    /// Object is deleted or it is going to be deleted, but it can still be restored.
+    pub fn is_pending_removal(&self) -> bool {
+        matches!(self, Self::PendingDelete | Self::RedemptionPeriod)
+    }
+
+    /// Grace period after operation (RFC 3915), when operation can be undone with refund.
+    pub fn is_grace_period(&self) -> bool {
+        matches!(
+            self,
+            Self::AddPeriod | Self::AutoRenewPeriod | Self::RenewPeriod | Self::TransferPeriod
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct IpAddresses {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub v4: Option<Vec<Ipv4Addr>>,
@@ -523,14 +713,14 @@ pub struct IpAddresses {
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-5.2
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Nameserver {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub handle: Option<String>,
-    pub ldh_name: String,
+    pub handle: Option<Handle>,
+    pub ldh_name: DomainName,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub unicode_name: Option<String>,
+    pub unicode_name: Option<DomainName>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ip_addresses: Option<IpAddresses>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -543,10 +733,19 @@ pub struct Nameserver {
     pub notices: Option<Vec<NoticeOrRemark>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub links: Option<Vec<Link>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rdap_conformance: Option<Conformance>,
+    // redacted extension
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redacted: Option<Vec<Redacted>>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-10.2.3 and https://www.iana.org/assignments/rdap-json-values/rdap-json-values.xhtml
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 #[serde(remote = "EventAction")]
 pub enum EventAction {
@@ -608,21 +807,132 @@ impl Serialize for EventAction {
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-4.5
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(rename_all = "camelCase")]
+///
+/// Events are compared without `raw_date`, so date in different format is the same event.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(try_from = "RawEvent")]
 pub struct Event {
-    #[serde(rename = "eventActor", skip_serializing_if = "Option::is_none")]
     pub actor: Option<String>,
-    #[serde(rename = "eventAction")]
     pub action: EventAction,
-    #[serde(rename = "eventDate", deserialize_with = "deserialize_datetime")]
     pub date: DateTime<FixedOffset>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub links: Option<Link>,
+    /// `eventDate` exactly as sent by server. It is serialized instead of `date`, while `date`
+    /// is not changed, so parsed responses are serialized with original formatting of dates.
+    pub raw_date: Option<String>,
+}
+
+impl Event {
+    /// Returns `raw_date` if it is the same date in the same time zone as `date`.
+    fn unchanged_raw_date(&self) -> Option<&str> {
+        let raw_date = self.raw_date.as_deref()?;
+        match parse_datetime(raw_date) {
+            Ok(date) if date == self.date && date.offset() == self.date.offset() => Some(raw_date),
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for Event {
+    fn eq(&self, other: &Self) -> bool {
+        self.actor == other.actor
+            && self.action == other.action
+            && self.date == other.date
+            && self.links == other.links
+    }
+}
+
+impl Eq for Event {}
+
+impl Hash for Event {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.actor.hash(state);
+        self.action.hash(state);
+        self.date.hash(state);
+        self.links.hash(state);
+    }
+}
+
+/// Event as it is sent by server, before parsing its date.
+#[derive(Deserialize)]
+struct RawEvent {
+    #[serde(rename = "eventActor")]
+    actor: Option<String>,
+    #[serde(rename = "eventAction")]
+    action: EventAction,
+    #[serde(rename = "eventDate")]
+    date: String,
+    links: Option<Link>,
+}
+
+impl TryFrom<RawEvent> for Event {
+    type Error = chrono::ParseError;
+
+    fn try_from(event: RawEvent) -> Result<Self, Self::Error> {
+        Ok(Self {
+            actor: event.actor,
+            action: event.action,
+            date: parse_datetime(&event.date)?,
+            links: event.links,
+            raw_date: Some(event.date),
+        })
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Event", 4)?;
+        match &self.actor {
+            Some(actor) => state.serialize_field("eventActor", actor)?,
+            None => state.skip_field("eventActor")?,
+        }
+        state.serialize_field("eventAction", &self.action)?;
+        match self.unchanged_raw_date() {
+            Some(raw_date) => state.serialize_field("eventDate", raw_date)?,
+            None => state.serialize_field("eventDate", &self.date)?,
+        }
+        match &self.links {
+            Some(links) => state.serialize_field("links", links)?,
+            None => state.skip_field("links")?,
+        }
+        state.end()
+    }
+}
+
+/// Dates of events by action, like `domain.events.registration()`.
+pub trait Events {
+    /// Returns date of the first event with given action.
+    fn date_of(&self, action: EventAction) -> Option<DateTime<FixedOffset>>;
+
+    fn registration(&self) -> Option<DateTime<FixedOffset>> {
+        self.date_of(EventAction::Registration)
+    }
+
+    fn expiration(&self) -> Option<DateTime<FixedOffset>> {
+        self.date_of(EventAction::Expiration)
+    }
+
+    fn last_changed(&self) -> Option<DateTime<FixedOffset>> {
+        self.date_of(EventAction::LastChanged)
+    }
+
+    fn last_update_of_rdap_database(&self) -> Option<DateTime<FixedOffset>> {
+        self.date_of(EventAction::LastUpdateOfRdapDatabase)
+    }
+}
+
+impl Events for [Event] {
+    fn date_of(&self, action: EventAction) -> Option<DateTime<FixedOffset>> {
+        self.iter()
+            .find(|event| event.action == action)
+            .map(|event| event.date)
+    }
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-10.2.1 and https://www.iana.org/assignments/rdap-json-values/rdap-json-values.xhtml
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Eq, Hash)]
 #[serde(remote = "NoticeOrRemarkType")]
 pub enum NoticeOrRemarkType {
     #[serde(rename = "result set truncated due to authorization")]
@@ -655,7 +965,7 @@ impl<'de> Deserialize<'de> for NoticeOrRemarkType {
         D: Deserializer<'de>,
     {
         let s = deserialize_string_lowercase(deserializer)?;
-        if s == "object redacted due to authorization." {
+        if s == "object redacted due to authorization." && ParseOptions::accepts_lenient_values() {
             // `lat` domain registry contains typo and value ends with dot :/
             Ok(Self::ObjectRedactedDueToAuthorization)
         } else {
@@ -673,7 +983,7 @@ impl Serialize for NoticeOrRemarkType {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct NoticeOrRemark {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -687,7 +997,7 @@ pub struct NoticeOrRemark {
 
 /// An enum signifying the IP protocol version of the network: "v4" signifies an IPv4 network,
 /// and "v6" signifies an IPv6 network.
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone, Copy, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub enum IpVersion {
     V4,
@@ -695,7 +1005,7 @@ pub enum IpVersion {
 }
 
 /// From 'cidr0' extension. https://bitbucket.org/nroecg/nro-rdap-cidr/src/master/nro-rdap-cidr.txt
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CidrOCidr {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub v4prefix: Option<Ipv4Addr>,
@@ -704,10 +1014,10 @@ pub struct CidrOCidr {
     pub length: u8,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct IpNetwork {
-    pub handle: String,
+    pub handle: Handle,
     pub start_address: IpAddr,
     pub end_address: IpAddr,
     pub ip_version: IpVersion,
@@ -716,7 +1026,7 @@ pub struct IpNetwork {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub country: Option<CountryCode>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub parent_handle: Option<String>,
+    pub parent_handle: Option<Handle>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub r#type: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -728,7 +1038,7 @@ pub struct IpNetwork {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub events: Option<Vec<Event>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub rdap_conformance: Option<Vec<String>>,
+    pub rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notices: Option<Vec<NoticeOrRemark>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -746,13 +1056,17 @@ pub struct IpNetwork {
         skip_serializing_if = "Option::is_none"
     )]
     pub arin_originas0_originautnums: Option<Vec<u32>>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-5.5
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct AutNum {
-    pub handle: String,
+    pub handle: Handle,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start_autnum: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -771,7 +1085,7 @@ pub struct AutNum {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub events: Option<Vec<Event>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub rdap_conformance: Option<Vec<String>>,
+    pub rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notices: Option<Vec<NoticeOrRemark>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -780,12 +1094,98 @@ pub struct AutNum {
     pub status: Option<Vec<Status>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lang: Option<String>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AutNum {
+    /// Returns range of AS numbers from `startAutnum` and `endAutnum`. When they are missing,
+    /// number is parsed from handle, because some servers (like LACNIC) return just handle like
+    /// `27648` instead of `AS27648`.
+    pub fn range(&self) -> Option<std::ops::RangeInclusive<u32>> {
+        match (self.start_autnum, self.end_autnum) {
+            (Some(start), end) => Some(start..=end.unwrap_or(start)),
+            (None, _) => parse_asn(&self.handle).map(|asn| asn..=asn),
+        }
+    }
+
+    /// Returns true if AS number is in range of AS numbers of object.
+    pub fn contains<A: Into<u32>>(&self, asn: A) -> bool {
+        let asn = asn.into();
+        self.range().map_or(false, |range| range.contains(&asn))
+    }
+}
+
+/// Autonomous system number, that can be parsed from asplain (`4200000000`), asdot
+/// (`64086.59904`, RFC 5396) or with `AS` prefix.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(transparent)]
+pub struct Asn(pub u32);
+
+impl Asn {
+    /// Returns number in asdot notation, numbers lower than 65536 are in asplain notation.
+    pub fn to_asdot(self) -> String {
+        if self.0 > 0xffff {
+            format!("{}.{}", self.0 >> 16, self.0 & 0xffff)
+        } else {
+            self.0.to_string()
+        }
+    }
+}
+
+impl FromStr for Asn {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        parse_asn(string).map(Self).ok_or("string is not AS number")
+    }
+}
+
+/// Formats number in asplain notation, like `4200000000`.
+impl fmt::Display for Asn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<u32> for Asn {
+    fn from(asn: u32) -> Self {
+        Self(asn)
+    }
+}
+
+impl From<Asn> for u32 {
+    fn from(asn: Asn) -> Self {
+        asn.0
+    }
+}
+
+/// Parse AS number like `AS1234`, `1234` or `AS1.10` in asdot notation (RFC 5396).
+pub fn parse_asn(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let number = match value.get(..2) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("as") => &value[2..],
+        _ => value,
+    };
+    if !number.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+        return None;
+    }
+    match number.split_once('.') {
+        Some((high, low)) => {
+            let high: u16 = high.parse().ok()?;
+            let low: u16 = low.parse().ok()?;
+            Some((high as u32) << 16 | low as u32)
+        }
+        None => number.parse().ok(),
+    }
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-10.2.5
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
-enum DomainVariantRelation {
+pub enum DomainVariantRelation {
     Registered,
     Unregistered,
     #[serde(rename = "registration restricted")]
@@ -795,32 +1195,39 @@ enum DomainVariantRelation {
     Conjoined,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct VariantName {
-    ldh_name: String,
-    unicode_name: String,
+    pub ldh_name: DomainName,
+    pub unicode_name: DomainName,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Group of IDN variants of domain with the same relation to domain.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Variant {
-    relation: Vec<DomainVariantRelation>,
+    pub relation: Vec<DomainVariantRelation>,
+    /// Name of IDN table of registry, that defines variants.
     #[serde(skip_serializing_if = "Option::is_none")]
-    idn_table: Option<String>,
-    #[serde(rename = "variantNames")]
-    names: Vec<VariantName>,
+    pub idn_table: Option<String>,
+    pub variant_names: Vec<VariantName>,
+}
+
+impl Variant {
+    pub fn has_relation(&self, relation: DomainVariantRelation) -> bool {
+        self.relation.contains(&relation)
+    }
 }
 
 /// For field sizes see https://tools.ietf.org/html/rfc4034#section-5.1
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct DsData {
     #[serde(skip_serializing_if = "Option::is_none")]
     key_tag: Option<u16>,
-    algorithm: u8,
+    algorithm: DnssecAlgorithm,
     digest: String,
-    digest_type: u8,
+    digest_type: DigestType,
     #[serde(skip_serializing_if = "Option::is_none")]
     events: Option<Vec<Event>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -828,20 +1235,20 @@ pub struct DsData {
 }
 
 /// For field sizes see https://tools.ietf.org/html/rfc4034#section-2.1
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct KeyData {
     flags: u16,
     protocol: u8,
     public_key: String,
-    algorithm: u8,
+    algorithm: DnssecAlgorithm,
     #[serde(skip_serializing_if = "Option::is_none")]
     events: Option<Vec<Event>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     links: Option<Vec<Link>>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct SecureDns {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -857,34 +1264,44 @@ pub struct SecureDns {
 }
 
 /// https://fred.nic.cz/rdap-extension/
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct FredKeySet {
     pub links: Vec<Link>,
-    pub handle: String,
+    pub handle: Handle,
     #[serde(rename = "dns_keys")]
     pub dns_keys: Vec<KeyData>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// https://fred.nic.cz/rdap-extension/
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct FredNsSet {
     pub links: Vec<Link>,
-    pub handle: String,
+    pub handle: Handle,
     pub nameservers: Vec<Nameserver>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-5.3
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Domain {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub handle: Option<String>,
+    pub handle: Option<Handle>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub ldh_name: Option<String>,
+    pub ldh_name: Option<DomainName>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub unicode_name: Option<String>,
+    pub unicode_name: Option<DomainName>,
+    // Often missing in search results.
+    #[serde(default)]
     pub entities: Vec<Object>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub links: Option<Vec<Link>>,
@@ -896,11 +1313,12 @@ pub struct Domain {
     pub secure_dns: Option<SecureDns>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub remarks: Option<Vec<NoticeOrRemark>>,
+    #[serde(default)]
     pub events: Vec<Event>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<Object>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub rdap_conformance: Option<Vec<String>>,
+    pub rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub notices: Option<Vec<NoticeOrRemark>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -914,61 +1332,581 @@ pub struct Domain {
     pub fred_keyset: Option<Object>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fred_nsset: Option<Object>,
+    // redacted extension
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redacted: Option<Vec<Redacted>>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Specification and extension identifier from `rdapConformance`.
+/// https://www.iana.org/assignments/rdap-extensions/rdap-extensions.xhtml
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum Extension {
+    RdapLevel0,
+    Cidr0,
+    Fred,
+    ArinOriginas0,
+    RdapObjectTag,
+    IcannRdapResponseProfile0,
+    IcannRdapResponseProfile1,
+    IcannRdapTechnicalImplementationGuide0,
+    IcannRdapTechnicalImplementationGuide1,
+    NroRdapProfile0,
+    Farv1,
+    Redacted,
+    Sorting,
+    Paging,
+    Subsetting,
+    ReverseSearch,
+    Jscard,
+    Unknown(String),
+}
+
+impl Extension {
+    /// Identifier of extension as used in `rdapConformance`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::RdapLevel0 => "rdap_level_0",
+            Self::Cidr0 => "cidr0",
+            Self::Fred => "fred",
+            Self::ArinOriginas0 => "arin_originas0",
+            Self::RdapObjectTag => "rdap_objectTag",
+            Self::IcannRdapResponseProfile0 => "icann_rdap_response_profile_0",
+            Self::IcannRdapResponseProfile1 => "icann_rdap_response_profile_1",
+            Self::IcannRdapTechnicalImplementationGuide0 => {
+                "icann_rdap_technical_implementation_guide_0"
+            }
+            Self::IcannRdapTechnicalImplementationGuide1 => {
+                "icann_rdap_technical_implementation_guide_1"
+            }
+            Self::NroRdapProfile0 => "nro_rdap_profile_0",
+            Self::Farv1 => "farv1",
+            Self::Redacted => "redacted",
+            Self::Sorting => "sorting",
+            Self::Paging => "paging",
+            Self::Subsetting => "subsetting",
+            Self::ReverseSearch => "reverse_search",
+            Self::Jscard => "jscard",
+            Self::Unknown(s) => s,
+        }
+    }
+}
+
+impl From<String> for Extension {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "rdap_level_0" => Self::RdapLevel0,
+            "cidr0" => Self::Cidr0,
+            "fred" => Self::Fred,
+            "arin_originas0" => Self::ArinOriginas0,
+            "rdap_objectTag" => Self::RdapObjectTag,
+            "icann_rdap_response_profile_0" => Self::IcannRdapResponseProfile0,
+            "icann_rdap_response_profile_1" => Self::IcannRdapResponseProfile1,
+            "icann_rdap_technical_implementation_guide_0" => {
+                Self::IcannRdapTechnicalImplementationGuide0
+            }
+            "icann_rdap_technical_implementation_guide_1" => {
+                Self::IcannRdapTechnicalImplementationGuide1
+            }
+            "nro_rdap_profile_0" => Self::NroRdapProfile0,
+            "farv1" => Self::Farv1,
+            "redacted" => Self::Redacted,
+            "sorting" => Self::Sorting,
+            "paging" => Self::Paging,
+            "subsetting" => Self::Subsetting,
+            "reverse_search" => Self::ReverseSearch,
+            "jscard" => Self::Jscard,
+            _ => Self::Unknown(s),
+        }
+    }
+}
+
+impl From<Extension> for String {
+    fn from(extension: Extension) -> Self {
+        match extension {
+            Extension::Unknown(s) => s,
+            extension => extension.as_str().to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// List of specifications and extensions from `rdapConformance`, that response conforms to or
+/// server supports. Unknown identifiers are kept as `Extension::Unknown`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct Conformance(pub Vec<Extension>);
+
+impl Conformance {
+    /// Returns true when list contains given identifier, like `redacted` or `cidr0`.
+    pub fn has_extension(&self, identifier: &str) -> bool {
+        self.0
+            .iter()
+            .any(|extension| extension.as_str() == identifier)
+    }
+
+    pub fn contains(&self, extension: &Extension) -> bool {
+        self.0.contains(extension)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Extension> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Conformance {
+    type Item = &'a Extension;
+    type IntoIter = std::slice::Iter<'a, Extension>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
 }
 
 /// https://tools.ietf.org/html/rfc7483.html#section-7
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Help {
+    /// Specifications and extensions supported by server.
     #[serde(skip_serializing_if = "Option::is_none")]
-    rdap_conformance: Option<Vec<String>>,
+    pub rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    notices: Option<Vec<NoticeOrRemark>>,
+    pub notices: Option<Vec<NoticeOrRemark>>,
+    // farv1 extension
+    #[serde(
+        rename = "farv1_openidcConfiguration",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub farv1_openidc_configuration: Option<Farv1OpenidcConfiguration>,
+    #[serde(
+        rename = "farv1_openidcProviders",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub farv1_openidc_providers: Option<Vec<Farv1OpenidcProvider>>,
+    // reverse_search extension
+    #[serde(
+        rename = "reverse_search_properties",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub reverse_search_properties: Option<Vec<ReverseSearchProperty>>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Help {
+    /// Returns link to terms of service from notices, that is link with `terms-of-service` rel.
+    pub fn terms_of_service(&self) -> Option<&Link> {
+        self.notices
+            .iter()
+            .flatten()
+            .flat_map(|notice| notice.links.iter().flatten())
+            .find(|link| link.rel.as_deref() == Some("terms-of-service"))
+    }
+
+    /// Returns true if server announces reverse search of given resource type (like `domains`)
+    /// by given property (like `fn`) of related entity.
+    pub fn supports_reverse_search(&self, searchable_resource_type: &str, property: &str) -> bool {
+        self.reverse_search_properties.iter().flatten().any(|item| {
+            item.searchable_resource_type == searchable_resource_type && item.property == property
+        })
+    }
+}
+
+/// Property of related object that can be used in reverse search.
+/// https://www.rfc-editor.org/rfc/rfc9536#section-5
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ReverseSearchProperty {
+    pub searchable_resource_type: String,
+    pub related_resource_type: String,
+    pub property: String,
+    /// JSONPath of property in related object.
+    pub property_path: String,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9560#section-4.1
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Farv1OpenidcConfiguration {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issuer_identifier_supported: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implicit_token_refresh_supported: Option<bool>,
+    /// Other configuration members.
+    #[serde(flatten)]
+    pub other: serde_json::Map<String, serde_json::Value>,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9560#section-4.1
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Farv1OpenidcProvider {
+    pub iss: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<bool>,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9560#section-5.1
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Farv1SessionInfo {
+    /// Number of seconds until access token expires.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_expiration: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_refresh: Option<bool>,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc9560#section-5.1
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Farv1DeviceInfo {
+    pub verification_url: String,
+    pub user_code: String,
+    /// Number of seconds until device code expires.
+    pub expires_in: u64,
+    /// Minimal number of seconds between polling requests.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<u64>,
+}
+
+/// Response of `farv1_session` endpoints (login, status, refresh, logout, device and
+/// devicepoll).
+///
+/// https://www.rfc-editor.org/rfc/rfc9560#section-5.1
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Farv1Session {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rdap_conformance: Option<Conformance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notices: Option<Vec<NoticeOrRemark>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+    /// Claims about authenticated end-user, like `sub` or `name`.
+    #[serde(rename = "farv1_userClaims", skip_serializing_if = "Option::is_none")]
+    pub farv1_user_claims: Option<serde_json::Map<String, serde_json::Value>>,
+    #[serde(rename = "farv1_sessionInfo", skip_serializing_if = "Option::is_none")]
+    pub farv1_session_info: Option<Farv1SessionInfo>,
+    #[serde(rename = "farv1_deviceInfo", skip_serializing_if = "Option::is_none")]
+    pub farv1_device_info: Option<Farv1DeviceInfo>,
 }
 
 // https://tools.ietf.org/html/rfc7483#section-8
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct EntitySearchResults {
     #[serde(skip_serializing_if = "Option::is_none")]
-    rdap_conformance: Option<Vec<String>>,
+    pub rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    notices: Option<Vec<NoticeOrRemark>>,
+    pub notices: Option<Vec<NoticeOrRemark>>,
     #[serde(rename = "entitySearchResults")]
-    results: Vec<Entity>,
+    pub results: Vec<Entity>,
+    // sorting and paging extensions
+    #[serde(rename = "sorting_metadata", skip_serializing_if = "Option::is_none")]
+    pub sorting_metadata: Option<SortingMetadata>,
+    #[serde(rename = "paging_metadata", skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct DomainSearchResults {
     #[serde(skip_serializing_if = "Option::is_none")]
-    rdap_conformance: Option<Vec<String>>,
+    pub rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    notices: Option<Vec<NoticeOrRemark>>,
+    pub notices: Option<Vec<NoticeOrRemark>>,
     #[serde(rename = "domainSearchResults")]
-    results: Vec<Entity>,
+    pub results: Vec<Domain>,
+    // sorting and paging extensions
+    #[serde(rename = "sorting_metadata", skip_serializing_if = "Option::is_none")]
+    pub sorting_metadata: Option<SortingMetadata>,
+    #[serde(rename = "paging_metadata", skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct NameserverSearchResults {
     #[serde(skip_serializing_if = "Option::is_none")]
-    rdap_conformance: Option<Vec<String>>,
+    pub rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    notices: Option<Vec<NoticeOrRemark>>,
+    pub notices: Option<Vec<NoticeOrRemark>>,
     #[serde(rename = "nameserverSearchResults")]
-    results: Vec<Entity>,
+    pub results: Vec<Nameserver>,
+    // sorting and paging extensions
+    #[serde(rename = "sorting_metadata", skip_serializing_if = "Option::is_none")]
+    pub sorting_metadata: Option<SortingMetadata>,
+    #[serde(rename = "paging_metadata", skip_serializing_if = "Option::is_none")]
+    pub paging_metadata: Option<PagingMetadata>,
+}
+
+/// Sort property that server supports for search.
+/// https://www.rfc-editor.org/rfc/rfc8977#section-2.3.1
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct AvailableSort {
+    pub property: String,
+    /// JSONPath of sorted value in search results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub json_path: Option<String>,
+    /// True if results are sorted by this property when `sort` parameter is not given.
+    pub default: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<Link>>,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc8977#section-2.3.1
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct SortingMetadata {
+    /// Value of `sort` parameter that was applied to results.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_sort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_sorts: Option<Vec<AvailableSort>>,
+}
+
+/// https://www.rfc-editor.org/rfc/rfc8977#section-2.3.2
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct PagingMetadata {
+    /// Number of all results, returned when search was sent with `count=true` parameter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Vec<Link>>,
+}
+
+impl PagingMetadata {
+    /// Returns link to the next page of results, that is link with `next` rel. It is missing on
+    /// the last page.
+    pub fn next_page(&self) -> Option<&Link> {
+        self.links
+            .iter()
+            .flatten()
+            .find(|link| link.rel.as_deref() == Some("next"))
+    }
+}
+
+/// Field removed or changed by server, described in `redacted` member.
+/// https://www.rfc-editor.org/rfc/rfc9537#section-4.2
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct Redacted {
+    /// Logical name of redacted field, like `Registrant Phone`.
+    pub name: RedactedDescription,
+    /// JSONPath of removed field in response before redaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_path: Option<String>,
+    /// JSONPath of field with empty, partial or replacement value in redacted response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replacement_path: Option<String>,
+    /// Language of paths, `jsonpath` when missing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_lang: Option<String>,
+    #[serde(default)]
+    pub method: RedactionMethod,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<RedactedDescription>,
+}
+
+impl Redacted {
+    /// Returns name of redacted field, registered `type` or just `description`.
+    pub fn field_name(&self) -> Option<&str> {
+        self.name
+            .r#type
+            .as_deref()
+            .or(self.name.description.as_deref())
+    }
+}
+
+/// Name or reason of redaction, that is registered type or human readable description.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RedactedDescription {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// https://www.rfc-editor.org/rfc/rfc9537#section-3
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[serde(from = "String", into = "String")]
+pub enum RedactionMethod {
+    /// Field was removed from response.
+    #[default]
+    Removal,
+    /// Field contains empty value.
+    EmptyValue,
+    /// Field contains just part of value.
+    PartialValue,
+    /// Field contains different value, like email of web form instead of registrant email.
+    ReplacementValue,
+    Unknown(String),
+}
+
+impl From<String> for RedactionMethod {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "removal" => Self::Removal,
+            "emptyValue" => Self::EmptyValue,
+            "partialValue" => Self::PartialValue,
+            "replacementValue" => Self::ReplacementValue,
+            _ => Self::Unknown(s),
+        }
+    }
+}
+
+impl From<RedactionMethod> for String {
+    fn from(method: RedactionMethod) -> Self {
+        match method {
+            RedactionMethod::Removal => "removal".to_owned(),
+            RedactionMethod::EmptyValue => "emptyValue".to_owned(),
+            RedactionMethod::PartialValue => "partialValue".to_owned(),
+            RedactionMethod::ReplacementValue => "replacementValue".to_owned(),
+            RedactionMethod::Unknown(s) => s,
+        }
+    }
+}
+
+/// Returns names of redacted fields.
+fn redacted_fields(redacted: &Option<Vec<Redacted>>) -> Vec<&str> {
+    redacted
+        .iter()
+        .flatten()
+        .filter_map(Redacted::field_name)
+        .collect()
+}
+
+impl Domain {
+    /// Returns names of fields redacted by server (RFC 9537), like `Registrant Name`.
+    pub fn redacted_fields(&self) -> Vec<&str> {
+        redacted_fields(&self.redacted)
+    }
+
+    /// Returns names of variants with given relation to domain.
+    pub fn variants_with_relation(&self, relation: DomainVariantRelation) -> Vec<&VariantName> {
+        self.variants
+            .iter()
+            .flatten()
+            .filter(|variant| variant.has_relation(relation))
+            .flat_map(|variant| &variant.variant_names)
+            .collect()
+    }
+
+    /// Returns names of variants that are registered, as domain itself or as another domain.
+    pub fn registered_variants(&self) -> Vec<&VariantName> {
+        self.variants_with_relation(DomainVariantRelation::Registered)
+    }
+
+    /// Returns entity with `registrar` role and its IANA Registrar ID.
+    pub fn registrar(&self) -> Option<Registrar<'_>> {
+        let entity = self
+            .entities_with_role(Role::Registrar)
+            .into_iter()
+            .next()?;
+        let iana_id = entity
+            .public_ids
+            .iter()
+            .flatten()
+            .find(|id| id.r#type.eq_ignore_ascii_case("IANA Registrar ID"))
+            .and_then(|id| id.identifier.trim().parse().ok());
+        Some(Registrar { entity, iana_id })
+    }
+
+    /// Returns entity with `registrant` role.
+    pub fn registrant(&self) -> Option<&Entity> {
+        self.entities_with_role(Role::Registrant).into_iter().next()
+    }
+
+    /// Returns names of nameservers in lowercase and without trailing dot.
+    pub fn nameserver_hosts(&self) -> Vec<String> {
+        self.nameservers
+            .iter()
+            .flatten()
+            .filter_map(|object| match object {
+                Object::Nameserver(nameserver) => Some(
+                    nameserver
+                        .ldh_name
+                        .trim_end_matches('.')
+                        .to_ascii_lowercase(),
+                ),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// Registrar of domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Registrar<'a> {
+    pub entity: &'a Entity,
+    /// ID assigned by IANA to ICANN accredited registrar, from `publicIds` of entity.
+    pub iana_id: Option<u32>,
+}
+
+impl Registrar<'_> {
+    /// Returns name of registrar from contact card of entity.
+    pub fn name(&self) -> Option<String> {
+        self.entity.contact()?.full_name
+    }
+}
+
+impl Entity {
+    /// Returns names of fields redacted by server (RFC 9537), like `Registrant Email`.
+    pub fn redacted_fields(&self) -> Vec<&str> {
+        redacted_fields(&self.redacted)
+    }
+}
+
+impl Nameserver {
+    /// Returns names of fields redacted by server (RFC 9537).
+    pub fn redacted_fields(&self) -> Vec<&str> {
+        redacted_fields(&self.redacted)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct ArinOriginas0OriginautnumsResults {
     #[serde(skip_serializing_if = "Option::is_none")]
-    rdap_conformance: Option<Vec<String>>,
+    pub rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    notices: Option<Vec<NoticeOrRemark>>,
+    pub notices: Option<Vec<NoticeOrRemark>>,
     #[serde(rename = "arin_originas0_networkSearchResults")]
-    results: Vec<IpNetwork>,
+    pub results: Vec<IpNetwork>,
+}
+
+/// Any successful top-level RDAP response: object, search results or help, for responses from
+/// URLs which type is not known in advance. Variants are tried in order, so response that is not
+/// object nor search results is parsed as help.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(untagged)]
+pub enum TopLevelResponse {
+    Object(Box<Object>),
+    DomainSearchResults(DomainSearchResults),
+    NameserverSearchResults(NameserverSearchResults),
+    EntitySearchResults(EntitySearchResults),
+    ArinOriginas0NetworkSearchResults(ArinOriginas0OriginautnumsResults),
+    Help(Help),
 }
 
 // Some servers returns error code as string, so this function can deserialize
@@ -999,6 +1937,9 @@ where
         where
             E: serde::de::Error,
         {
+            if !ParseOptions::accepts_string_error_codes() {
+                return Err(serde::de::Error::invalid_type(Unexpected::Str(v), &self));
+            }
             u16::from_str(v)
                 .map_err(|_| serde::de::Error::invalid_value(Unexpected::Str(v), &"an error code"))
         }
@@ -1008,7 +1949,7 @@ where
 }
 
 /// https://tools.ietf.org/html/rfc7483#section-6
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct Error {
     #[serde(deserialize_with = "deserialize_error_code")]
@@ -1017,11 +1958,48 @@ pub struct Error {
     #[serde(skip_serializing_if = "Option::is_none")]
     description: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    rdap_conformance: Option<Vec<String>>,
+    rdap_conformance: Option<Conformance>,
     #[serde(skip_serializing_if = "Option::is_none")]
     notices: Option<Vec<NoticeOrRemark>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     lang: Option<String>,
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Error {
+    /// Error code, usually the same as HTTP status code of response.
+    pub fn error_code(&self) -> u16 {
+        self.error_code
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn description(&self) -> Option<&Vec<String>> {
+        self.description.as_ref()
+    }
+
+    pub fn rdap_conformance(&self) -> Option<&Conformance> {
+        self.rdap_conformance.as_ref()
+    }
+
+    pub fn notices(&self) -> Option<&Vec<NoticeOrRemark>> {
+        self.notices.as_ref()
+    }
+
+    pub fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    /// Members unknown to this crate, like nonstandard members of some registries.
+    #[cfg(feature = "extra-fields")]
+    pub fn extra(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.extra
+    }
 }
 
 pub trait BootstrapService {
@@ -1029,7 +2007,8 @@ pub trait BootstrapService {
     fn servers(&self) -> &Vec<String>;
 }
 
-#[derive(Deserialize, Debug)]
+/// Service serialized as array of keys and servers, `[["cz"], ["https://rdap.nic.cz/"]]`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BootstrapServiceRfc7484(Vec<String>, Vec<String>);
 
 impl BootstrapService for BootstrapServiceRfc7484 {
@@ -1041,9 +2020,17 @@ impl BootstrapService for BootstrapServiceRfc7484 {
     }
 }
 
-#[derive(Deserialize, Debug)]
+/// Service serialized as array of contacts, tags and servers.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct BootstrapServiceRfc8521(Vec<String>, Vec<String>, Vec<String>);
 
+impl BootstrapServiceRfc8521 {
+    /// Contact information of service provider (usually email address).
+    pub fn contacts(&self) -> &Vec<String> {
+        &self.0
+    }
+}
+
 impl BootstrapService for BootstrapServiceRfc8521 {
     fn keys(&self) -> &Vec<String> {
         &self.1
@@ -1053,8 +2040,9 @@ impl BootstrapService for BootstrapServiceRfc8521 {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Bootstrap<T> {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
     pub publication: DateTime<FixedOffset>,
     pub services: Vec<T>,
@@ -1067,6 +2055,7 @@ pub type BootstrapRfc7484 = Bootstrap<BootstrapServiceRfc7484>;
 pub type BootstrapRfc8521 = Bootstrap<BootstrapServiceRfc8521>;
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow, clippy::len_zero)] // Keep style of older tests.
 mod tests {
     use super::*;
     use serde::de::DeserializeOwned;
@@ -1149,6 +2138,18 @@ mod tests {
         let json = r#"{"eventAction":"last changed","eventDate":"2016-04-13 08:18:43"}"#;
         let item: Event = serde_json::from_str(&json).unwrap();
         assert_eq!(item.date.to_rfc3339(), "2016-04-13T08:18:43+00:00");
+        assert_eq!(Some("2016-04-13 08:18:43"), item.raw_date.as_deref());
+        let serialized = serde_json::to_value(&item).unwrap();
+        assert_eq!("2016-04-13 08:18:43", serialized["eventDate"]);
+
+        // Changed date is serialized instead of original one.
+        let mut changed = item.clone();
+        changed.date = changed
+            .date
+            .with_timezone(&FixedOffset::east_opt(3600).unwrap());
+        assert_eq!(item, changed);
+        let serialized = serde_json::to_value(&changed).unwrap();
+        assert_eq!("2016-04-13T09:18:43+01:00", serialized["eventDate"]);
     }
 
     // `mtr` domain registry format
@@ -1281,6 +2282,19 @@ mod tests {
         let Object::Domain(parsed) = deserialize_and_serialize("domain/domain_24.json") else {
             panic!("invalid object class");
         };
+        let registered: Vec<_> = parsed
+            .registered_variants()
+            .into_iter()
+            .map(|name| name.ldh_name.as_str())
+            .collect();
+        assert_eq!(vec!["xn--fo-cka.example", "xn--fo-fka.example"], registered);
+        let restricted =
+            parsed.variants_with_relation(DomainVariantRelation::RegistrationRestricted);
+        assert_eq!("xn--fo-8ja.example", restricted[0].ldh_name);
+        assert_eq!(
+            Some(".EXAMPLE Swedish"),
+            parsed.variants.as_ref().unwrap()[1].idn_table.as_deref()
+        );
         assert_eq!("XXXX", parsed.handle.unwrap());
     }
 
@@ -1289,6 +2303,16 @@ mod tests {
         let Object::Domain(parsed) = deserialize_and_serialize("domain/domain_fred.json") else {
             panic!("invalid object class");
         };
+        assert_eq!(
+            "1997-10-30T00:00:00+00:00",
+            parsed.events.registration().unwrap().to_rfc3339()
+        );
+        assert_eq!(
+            parsed.events.expiration(),
+            parsed.events.date_of(EventAction::Expiration)
+        );
+        assert!(parsed.last_changed().is_some());
+        assert!(parsed.events.last_update_of_rdap_database().is_none());
         assert_eq!("nic.cz", parsed.handle.unwrap());
     }
 
@@ -1402,6 +2426,20 @@ mod tests {
             panic!("invalid object class");
         };
         assert_eq!("27648", parsed.handle);
+        assert_eq!(Some(27648..=27648), parsed.range());
+    }
+
+    #[test]
+    fn test_parse_asn() {
+        assert_eq!(Some(1234), parse_asn("AS1234"));
+        assert_eq!(Some(1234), parse_asn("as1234"));
+        assert_eq!(Some(1234), parse_asn(" 1234 "));
+        assert_eq!(Some(65546), parse_asn("AS1.10"));
+        assert_eq!(Some(u32::MAX), parse_asn("4294967295"));
+        assert_eq!(None, parse_asn("4294967296"));
+        assert_eq!(None, parse_asn("AS+1"));
+        assert_eq!(None, parse_asn("AS1.65536"));
+        assert_eq!(None, parse_asn("ASX"));
     }
 
     #[test]
@@ -1411,6 +2449,214 @@ mod tests {
         assert!(parsed.results.len() > 0);
     }
 
+    #[test]
+    fn test_parse_domain_search_paging() {
+        let parsed: DomainSearchResults =
+            deserialize_and_serialize("domain/domain_search_paging.json");
+        let sorting = parsed.sorting_metadata.as_ref().unwrap();
+        assert_eq!(Some("name"), sorting.current_sort.as_deref());
+        assert_eq!(2, sorting.available_sorts.as_ref().unwrap().len());
+        let paging = parsed.paging_metadata.as_ref().unwrap();
+        assert_eq!(Some(73), paging.total_count);
+        assert_eq!(Some(1), paging.page_number);
+        assert!(paging.next_page().unwrap().href.contains("cursor="));
+    }
+
+    #[test]
+    fn test_parse_domain_search() {
+        let parsed: DomainSearchResults = deserialize_and_serialize("domain/domain_search.json");
+        assert_eq!(2, parsed.results.len());
+        assert_eq!(Some("EXAMPLE.COM"), parsed.results[0].ldh_name.as_deref());
+        assert_eq!(2, parsed.results[0].nameservers.as_ref().unwrap().len());
+        assert!(parsed.results[1].events.is_empty());
+
+        let json = serde_json::to_string(&parsed).unwrap();
+        let parsed: DomainSearchResults = serde_json::from_str(&json).unwrap();
+        assert_eq!(2, parsed.results.len());
+    }
+
+    #[test]
+    fn test_parse_nameserver_search() {
+        let parsed: NameserverSearchResults =
+            deserialize_and_serialize("nameserver/nameserver_search.json");
+        assert_eq!(2, parsed.results.len());
+        assert_eq!("a.ns.nic.cz", parsed.results[0].ldh_name);
+        let ip_addresses = parsed.results[0].ip_addresses.as_ref().unwrap();
+        assert_eq!(1, ip_addresses.v6.as_ref().unwrap().len());
+
+        let json = serde_json::to_string(&parsed).unwrap();
+        let parsed: NameserverSearchResults = serde_json::from_str(&json).unwrap();
+        assert_eq!("b.ns.nic.cz", parsed.results[1].ldh_name);
+    }
+
+    #[test]
+    fn test_conformance() {
+        let parsed: Domain = deserialize_and_serialize("domain/domain_redacted.json");
+        let conformance = parsed.rdap_conformance.as_ref().unwrap();
+        assert!(conformance.has_extension("redacted"));
+        assert!(conformance.contains(&Extension::RdapLevel0));
+        assert!(!conformance.has_extension("cidr0"));
+
+        let json = r#"["rdap_level_0","icann_rdap_response_profile_0","example_ext"]"#;
+        let conformance: Conformance = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            vec![
+                Extension::RdapLevel0,
+                Extension::IcannRdapResponseProfile0,
+                Extension::Unknown("example_ext".into())
+            ],
+            conformance.0
+        );
+        assert!(conformance.has_extension("example_ext"));
+        assert_eq!(json, serde_json::to_string(&conformance).unwrap());
+    }
+
+    #[test]
+    fn test_parse_domain_redacted() {
+        let parsed: Domain = deserialize_and_serialize("domain/domain_redacted.json");
+        assert_eq!(
+            vec![
+                "Registry Domain ID",
+                "Registrant Name",
+                "Registrant Email",
+                "Registrant Phone"
+            ],
+            parsed.redacted_fields()
+        );
+        let redacted = parsed.redacted.as_ref().unwrap();
+        assert_eq!(Some("$.handle"), redacted[0].pre_path.as_deref());
+        assert_eq!(RedactionMethod::EmptyValue, redacted[1].method);
+        assert_eq!(RedactionMethod::ReplacementValue, redacted[2].method);
+        // Default method is removal.
+        assert_eq!(RedactionMethod::Removal, redacted[3].method);
+
+        let json = serde_json::to_value(&redacted[2]).unwrap();
+        assert_eq!("replacementValue", json["method"]);
+    }
+
+    #[cfg(feature = "extra-fields")]
+    #[test]
+    fn test_extra_fields() {
+        let json = r#"{"ldhName":"example.cz","entities":[],"events":[],"registry_lock":{"enabled":true},"nicbr_publicationStatus":"published"}"#;
+        let parsed: Domain = serde_json::from_str(json).unwrap();
+        assert_eq!(2, parsed.extra.len());
+        assert_eq!(true, parsed.extra["registry_lock"]["enabled"]);
+        assert_eq!("published", parsed.extra["nicbr_publicationStatus"]);
+
+        let serialized = serde_json::to_value(&parsed).unwrap();
+        assert_eq!(
+            serde_json::json!({"enabled": true}),
+            serialized["registry_lock"]
+        );
+        assert_eq!("published", serialized["nicbr_publicationStatus"]);
+    }
+
+    #[test]
+    fn test_parse_top_level_response() {
+        let parsed: TopLevelResponse = deserialize_and_serialize("domain/domain_fred.json");
+        let TopLevelResponse::Object(object) = parsed else {
+            panic!("invalid response type");
+        };
+        assert!(matches!(*object, Object::Domain(_)));
+        let parsed: TopLevelResponse =
+            deserialize_and_serialize("domain/domain_search_paging.json");
+        assert!(matches!(parsed, TopLevelResponse::DomainSearchResults(_)));
+        let parsed: TopLevelResponse = deserialize_and_serialize("help/help_reverse_search.json");
+        assert!(matches!(parsed, TopLevelResponse::Help(_)));
+    }
+
+    #[test]
+    fn test_domain_registrar() {
+        let json =
+            r#"["vcard",[["version",{},"text","4.0"],["fn",{},"text","Example Registrar, Inc."]]]"#;
+        let domain = Domain::builder()
+            .ldh_name("example.com")
+            .entity(
+                Entity::builder()
+                    .handle("292")
+                    .role(Role::Registrar)
+                    .vcard(serde_json::from_str(json).unwrap())
+                    .public_id(PublicId {
+                        r#type: "IANA Registrar ID".into(),
+                        identifier: "292".into(),
+                    })
+                    .build()
+                    .unwrap(),
+            )
+            .entity(
+                Entity::builder()
+                    .handle("REGISTRANT")
+                    .role(Role::Registrant)
+                    .build()
+                    .unwrap(),
+            )
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("NS1.Example.com.")
+                    .build()
+                    .unwrap(),
+            )
+            .nameserver(
+                Nameserver::builder()
+                    .ldh_name("ns2.example.com")
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap();
+        let registrar = domain.registrar().unwrap();
+        assert_eq!(Some(292), registrar.iana_id);
+        assert_eq!(Some("Example Registrar, Inc."), registrar.name().as_deref());
+        assert_eq!(
+            Some("REGISTRANT"),
+            domain.registrant().unwrap().handle.as_deref()
+        );
+        assert_eq!(
+            vec!["ns1.example.com", "ns2.example.com"],
+            domain.nameserver_hosts()
+        );
+    }
+
+    #[test]
+    fn test_asn() {
+        assert_eq!(Ok(Asn(4200000000)), "4200000000".parse());
+        assert_eq!(Ok(Asn(4200000000)), "64086.59904".parse());
+        assert_eq!(Ok(Asn(65546)), "AS1.10".parse());
+        assert_eq!(Ok(Asn(1234)), "as1234".parse());
+        assert!("AS1.65536".parse::<Asn>().is_err());
+        assert!("example".parse::<Asn>().is_err());
+        assert_eq!("64086.59904", Asn(4200000000).to_asdot());
+        assert_eq!("1234", Asn(1234).to_asdot());
+        assert_eq!("4200000000", Asn(4200000000).to_string());
+
+        let autnum = AutNum::builder().range(65536, 65551).build().unwrap();
+        assert!(autnum.contains(65540u32));
+        assert!(autnum.contains(Asn(65551)));
+        assert!(!autnum.contains(Asn(65552)));
+    }
+
+    #[test]
+    fn test_status_epp() {
+        assert_eq!(Some(Status::Active), Status::from_epp("ok"));
+        assert_eq!(Some(Status::Associated), Status::from_epp("linked"));
+        assert_eq!(None, Status::from_epp("unknown"));
+        assert_eq!(Some("linked"), Status::Associated.to_epp());
+        assert_eq!(Some("ok"), Status::Ok.to_epp());
+        assert_eq!(None, Status::Validated.to_epp());
+        for (epp, status) in EPP_STATUSES {
+            assert_eq!(Some(epp), status.to_epp());
+        }
+
+        assert!(Status::ClientTransferProhibited.is_client_locked());
+        assert!(!Status::ServerTransferProhibited.is_client_locked());
+        assert!(Status::ServerTransferProhibited.is_transfer_prohibited());
+        assert!(Status::DeleteProhibited.is_prohibited());
+        assert!(Status::RedemptionPeriod.is_pending_removal());
+        assert!(Status::ServerHold.is_on_hold());
+        assert!(Status::AddPeriod.is_grace_period());
+        assert!(!Status::Active.is_pending());
+    }
+
     #[test]
     fn test_parse_error_28() {
         let parsed: Error = deserialize_and_serialize("error/error_28.json");
@@ -1421,6 +2667,14 @@ mod tests {
     fn test_parse_error_29() {
         let parsed: Error = deserialize_and_serialize("error/error_29.json");
         assert_eq!(418, parsed.error_code);
+        assert_eq!(Some("en"), parsed.lang());
+        #[cfg(feature = "extra-fields")]
+        assert!(parsed.extra().is_empty());
+        assert!(parsed.rdap_conformance().is_some());
+        assert_eq!(
+            Some("Beverage Policy"),
+            parsed.notices().unwrap()[0].title.as_deref()
+        );
     }
 
     #[test]
@@ -1435,6 +2689,47 @@ mod tests {
         assert_eq!(404, parsed.error_code);
     }
 
+    #[test]
+    fn test_parse_help_farv1() {
+        let parsed: Help = deserialize_and_serialize("farv1/help_farv1.json");
+        let configuration = parsed.farv1_openidc_configuration.unwrap();
+        assert_eq!(Some(true), configuration.issuer_identifier_supported);
+        assert!(configuration.other.contains_key("dnsRR"));
+        let providers = parsed.farv1_openidc_providers.unwrap();
+        assert_eq!("https://idp.example.com", providers[0].iss);
+        assert_eq!(Some(true), providers[0].default);
+    }
+
+    #[test]
+    fn test_parse_help_reverse_search() {
+        let parsed: Help = deserialize_and_serialize("help/help_reverse_search.json");
+        let properties = parsed.reverse_search_properties.as_ref().unwrap();
+        assert_eq!(2, properties.len());
+        assert_eq!("entity", properties[0].related_resource_type);
+        assert!(parsed.supports_reverse_search("domains", "role"));
+        assert!(!parsed.supports_reverse_search("nameservers", "fn"));
+    }
+
+    #[test]
+    fn test_parse_session_farv1() {
+        let parsed: Farv1Session = deserialize_and_serialize("farv1/session_farv1.json");
+        let claims = parsed.farv1_user_claims.unwrap();
+        assert_eq!("103692", claims["sub"]);
+        assert_eq!(
+            Some(3599),
+            parsed.farv1_session_info.unwrap().token_expiration
+        );
+        assert!(parsed.farv1_device_info.is_none());
+    }
+
+    #[test]
+    fn test_parse_device_farv1() {
+        let parsed: Farv1Session = deserialize_and_serialize("farv1/device_farv1.json");
+        let device_info = parsed.farv1_device_info.unwrap();
+        assert_eq!("WDJB-MJHT", device_info.user_code);
+        assert_eq!(Some(5), device_info.interval);
+    }
+
     #[test]
     fn test_parse_bootstrap_asn() {
         let parsed: BootstrapRfc7484 = deserialize("bootstrap/asn.json");
@@ -1464,4 +2759,30 @@ mod tests {
         let parsed: BootstrapRfc8521 = deserialize("bootstrap/object-tags.json");
         assert!(parsed.services.len() > 0);
     }
+
+    #[test]
+    fn test_bootstrap_round_trip() {
+        let parsed: BootstrapRfc7484 = deserialize_and_serialize("bootstrap/dns.json");
+        let json = serde_json::to_value(&parsed).unwrap();
+        assert!(json["services"][0][0].is_array());
+        assert!(json["services"][0][1].is_array());
+        assert_eq!(parsed, serde_json::from_value(json).unwrap());
+
+        let parsed: BootstrapRfc8521 = deserialize_and_serialize("bootstrap/object-tags.json");
+        let json = serde_json::to_value(&parsed).unwrap();
+        assert_eq!(3, json["services"][0].as_array().unwrap().len());
+        assert_eq!(parsed, serde_json::from_value(json).unwrap());
+    }
+
+    #[test]
+    fn test_clone_eq_hash() {
+        let parsed: Domain = deserialize("domain/domain_fred.json");
+        let json = serde_json::to_value(&parsed).unwrap();
+        assert_eq!(parsed, serde_json::from_value(json).unwrap());
+        assert_eq!(parsed, parsed.clone());
+
+        let entities: std::collections::HashSet<_> =
+            parsed.entities.iter().chain(&parsed.entities).collect();
+        assert_eq!(parsed.entities.len(), entities.len());
+    }
 }
@@ -0,0 +1,233 @@
+//! Prefixes and ranges of IP networks.
+
+use crate::IpNetwork;
+#[cfg(feature = "ipnet")]
+use ipnet::IpNet;
+use std::cmp::Ordering;
+use std::net::IpAddr;
+#[cfg(feature = "ipnet")]
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::RangeInclusive;
+
+/// Returns the smallest list of prefixes (as address and length) that covers range from `start`
+/// to `end` of addresses with `bits` length.
+#[cfg_attr(not(feature = "ipnet"), allow(dead_code))]
+fn range_to_prefixes(mut start: u128, end: u128, bits: u32) -> Vec<(u128, u8)> {
+    let mask = |size: u32| {
+        if size >= 128 {
+            u128::MAX
+        } else {
+            (1 << size) - 1
+        }
+    };
+    let mut prefixes = Vec::new();
+    while start <= end {
+        // The largest block aligned to start, that doesn't exceed end.
+        let mut size = start.trailing_zeros().min(bits);
+        while start | mask(size) > end {
+            size -= 1;
+        }
+        prefixes.push((start, (bits - size) as u8));
+        let last = start | mask(size);
+        if last == end {
+            break;
+        }
+        start = last + 1;
+    }
+    prefixes
+}
+
+impl IpNetwork {
+    /// Range of addresses from `start_address` to `end_address`.
+    pub fn range(&self) -> RangeInclusive<IpAddr> {
+        self.start_address..=self.end_address
+    }
+
+    /// Returns true if address is in network, for example to check that server returned network
+    /// that covers queried address.
+    pub fn contains(&self, address: IpAddr) -> bool {
+        self.start_address <= address && address <= self.end_address
+    }
+
+    /// Returns true if networks have at least one address in common.
+    pub fn overlaps(&self, other: &IpNetwork) -> bool {
+        self.start_address <= other.end_address && other.start_address <= self.end_address
+    }
+
+    /// Returns true if all addresses of network are in other network.
+    pub fn is_subnet_of(&self, other: &IpNetwork) -> bool {
+        other.start_address <= self.start_address && self.end_address <= other.end_address
+    }
+
+    /// Orders networks by start address and larger networks first when they start on the same
+    /// address, so parent networks are sorted before their subnets. IPv4 networks are before IPv6.
+    pub fn cmp_range(&self, other: &IpNetwork) -> Ordering {
+        self.start_address
+            .cmp(&other.start_address)
+            .then_with(|| other.end_address.cmp(&self.end_address))
+    }
+}
+
+#[cfg(feature = "ipnet")]
+impl IpNetwork {
+    /// Returns the smallest list of prefixes that covers range from `start_address` to
+    /// `end_address`. Prefixes from `cidr0` extension are used when server returns them.
+    pub fn cidrs(&self) -> Vec<IpNet> {
+        let cidr0: Option<Vec<IpNet>> = self
+            .cidr0_cidrs
+            .iter()
+            .flatten()
+            .map(|cidr| match (cidr.v4prefix, cidr.v6prefix) {
+                (Some(prefix), _) => IpNet::new(prefix.into(), cidr.length).ok(),
+                (None, Some(prefix)) => IpNet::new(prefix.into(), cidr.length).ok(),
+                (None, None) => None,
+            })
+            .collect();
+        match cidr0 {
+            Some(cidrs) if !cidrs.is_empty() => return cidrs,
+            _ => {}
+        }
+
+        match (self.start_address, self.end_address) {
+            (IpAddr::V4(start), IpAddr::V4(end)) => {
+                range_to_prefixes(u32::from(start).into(), u32::from(end).into(), 32)
+                    .into_iter()
+                    .map(|(address, length)| {
+                        // Address fits to 32 bits and length is at most 32.
+                        let address = Ipv4Addr::from(address as u32);
+                        IpNet::new(address.into(), length).unwrap()
+                    })
+                    .collect()
+            }
+            (IpAddr::V6(start), IpAddr::V6(end)) => {
+                range_to_prefixes(start.into(), end.into(), 128)
+                    .into_iter()
+                    .map(|(address, length)| {
+                        IpNet::new(Ipv6Addr::from(address).into(), length).unwrap()
+                    })
+                    .collect()
+            }
+            // Invalid network with addresses of different version.
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Converts network that is a single prefix.
+#[cfg(feature = "ipnet")]
+impl TryFrom<&IpNetwork> for IpNet {
+    type Error = &'static str;
+
+    fn try_from(network: &IpNetwork) -> Result<Self, Self::Error> {
+        match network.cidrs()[..] {
+            [cidr] => Ok(cidr),
+            _ => Err("network is not a single prefix"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::range_to_prefixes;
+    use crate::IpNetwork;
+    use std::cmp::Ordering;
+    use std::net::IpAddr;
+
+    fn build_network(start: &str, end: &str) -> IpNetwork {
+        IpNetwork::builder()
+            .handle("NET")
+            .range(start.parse().unwrap(), end.parse().unwrap())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_contains_and_overlaps() {
+        let parent = build_network("192.0.2.0", "192.0.2.255");
+        let subnet = build_network("192.0.2.128", "192.0.2.255");
+        let other = build_network("198.51.100.0", "198.51.100.255");
+        assert!(parent.contains("192.0.2.1".parse().unwrap()));
+        assert!(!parent.contains("192.0.3.0".parse().unwrap()));
+        assert!(!parent.contains("2001:db8::".parse().unwrap()));
+        assert!(parent.overlaps(&subnet) && subnet.overlaps(&parent));
+        assert!(!parent.overlaps(&other));
+        assert!(subnet.is_subnet_of(&parent));
+        assert!(!parent.is_subnet_of(&subnet));
+        assert_eq!(Ordering::Less, parent.cmp_range(&subnet));
+        assert_eq!(Ordering::Less, subnet.cmp_range(&other));
+
+        let ipv6 = build_network("2001:db8::", "2001:db8::ffff");
+        let mut networks = [&ipv6, &other, &subnet, &parent];
+        networks.sort_by(|a, b| a.cmp_range(b));
+        let starts: Vec<IpAddr> = networks.iter().map(|n| n.start_address).collect();
+        assert_eq!(
+            vec![
+                parent.start_address,
+                subnet.start_address,
+                other.start_address,
+                ipv6.start_address
+            ],
+            starts
+        );
+    }
+
+    #[test]
+    fn test_range_to_prefixes() {
+        assert_eq!(vec![(0, 0)], range_to_prefixes(0, u32::MAX.into(), 32));
+        assert_eq!(vec![(0, 0)], range_to_prefixes(0, u128::MAX, 128));
+        assert_eq!(vec![(10, 32)], range_to_prefixes(10, 10, 32));
+        // 192.0.2.1 - 192.0.2.6
+        assert_eq!(
+            vec![
+                (0xc0000201, 32),
+                (0xc0000202, 31),
+                (0xc0000204, 31),
+                (0xc0000206, 32)
+            ],
+            range_to_prefixes(0xc0000201, 0xc0000206, 32)
+        );
+        assert_eq!(
+            vec![(u32::MAX.into(), 32)],
+            range_to_prefixes(u32::MAX.into(), u32::MAX.into(), 32)
+        );
+    }
+
+    #[cfg(feature = "ipnet")]
+    #[test]
+    fn test_cidrs() {
+        use ipnet::IpNet;
+        use std::fs::File;
+
+        let file = File::open("test_data/ip_network/ip_network_br.json").unwrap();
+        let network: IpNetwork = serde_json::from_reader(file).unwrap();
+        assert_eq!(
+            vec!["177.0.0.0/14".parse::<IpNet>().unwrap()],
+            network.cidrs()
+        );
+        assert_eq!(
+            "177.0.0.0/14".parse::<IpNet>().unwrap(),
+            IpNet::try_from(&network).unwrap()
+        );
+
+        let file = File::open("test_data/ip_network/ip_network_arin_3_3_3_3.json").unwrap();
+        let network: IpNetwork = serde_json::from_reader(file).unwrap();
+        assert_eq!(vec!["3.0.0.0/9".parse::<IpNet>().unwrap()], network.cidrs());
+
+        let file = File::open("test_data/ip_network/ip_network_26.json").unwrap();
+        let network: IpNetwork = serde_json::from_reader(file).unwrap();
+        assert_eq!(
+            vec!["2001:db8::/48".parse::<IpNet>().unwrap()],
+            network.cidrs()
+        );
+
+        let network = build_network("192.0.2.0", "192.0.3.127");
+        assert_eq!(
+            vec![
+                "192.0.2.0/24".parse::<IpNet>().unwrap(),
+                "192.0.3.0/25".parse().unwrap()
+            ],
+            network.cidrs()
+        );
+        assert!(IpNet::try_from(&network).is_err());
+    }
+}
@@ -0,0 +1,66 @@
+//! Conversion of dates to `time::OffsetDateTime`, for users of `time` crate.
+
+use crate::Event;
+use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use time::{OffsetDateTime, UtcOffset};
+
+/// Converts date to `OffsetDateTime`. Fails for dates out of range of `time` crate (years
+/// before -9999 or after 9999).
+pub fn to_offset_date_time(date: &DateTime<FixedOffset>) -> Result<OffsetDateTime, &'static str> {
+    let offset = UtcOffset::from_whole_seconds(date.offset().local_minus_utc())
+        .map_err(|_| "offset is out of range")?;
+    OffsetDateTime::from_unix_timestamp(date.timestamp())
+        .and_then(|utc| utc.replace_nanosecond(date.timestamp_subsec_nanos()))
+        .ok()
+        .and_then(|utc| utc.checked_to_offset(offset))
+        .ok_or("date is out of range")
+}
+
+/// Converts `OffsetDateTime` to date used in RDAP types. Fails for offsets of whole day or more,
+/// which `chrono` doesn't support.
+pub fn from_offset_date_time(date: OffsetDateTime) -> Result<DateTime<FixedOffset>, &'static str> {
+    let offset =
+        FixedOffset::east_opt(date.offset().whole_seconds()).ok_or("offset is out of range")?;
+    Utc.timestamp_opt(date.unix_timestamp(), date.nanosecond())
+        .single()
+        .map(|utc| utc.with_timezone(&offset))
+        .ok_or("date is out of range")
+}
+
+impl Event {
+    /// Returns `date` as `OffsetDateTime`, see `to_offset_date_time`.
+    pub fn offset_date_time(&self) -> Result<OffsetDateTime, &'static str> {
+        to_offset_date_time(&self.date)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_offset_date_time, to_offset_date_time};
+    use crate::Event;
+    use chrono::{FixedOffset, TimeZone};
+    use time::{Month, UtcOffset};
+
+    #[test]
+    fn test_offset_date_time() {
+        let json = r#"{"eventAction":"expiration","eventDate":"2030-01-31T12:30:45.5-04:00"}"#;
+        let event: Event = serde_json::from_str(json).unwrap();
+        let converted = event.offset_date_time().unwrap();
+        assert_eq!(
+            (2030, Month::January, 31),
+            (converted.year(), converted.month(), converted.day())
+        );
+        assert_eq!((12, 30, 45), converted.to_hms());
+        assert_eq!(500_000_000, converted.nanosecond());
+        assert_eq!(-4, converted.offset().whole_hours());
+        assert_eq!(Ok(event.date), from_offset_date_time(converted));
+
+        let date = FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(10000, 1, 1, 0, 0, 0)
+            .unwrap();
+        assert!(to_offset_date_time(&date).is_err());
+        let offset = UtcOffset::from_hms(25, 0, 0).unwrap();
+        assert!(from_offset_date_time(converted.to_offset(offset)).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+//! Tolerances for responses of servers that are not compliant with RFC 9083.
+
+use serde::de::DeserializeOwned;
+use std::cell::Cell;
+use std::io::Read;
+
+std::thread_local! {
+    static CURRENT: Cell<ParseOptions> = const { Cell::new(ParseOptions::lenient()) };
+}
+
+/// Options of parsing, that choose between accepting everything that some registries return
+/// (default) and strict compliance with specification.
+///
+/// Options are not passed to deserializers, they are set for current thread while parsing done by
+/// [`ParseOptions::parse_str`] and similar methods runs. Plain `serde_json` functions called outside
+/// of these methods ignore options of `ParseOptions` and always parse with lenient options.
+///
+/// ```
+/// use rdap_types::{Event, ParseOptions};
+///
+/// let json = r#"{"eventAction": "registration", "eventDate": "2020-01-01 10:00:00"}"#;
+/// assert!(ParseOptions::strict().parse_str::<Event>(json).is_err());
+/// assert!(serde_json::from_str::<Event>(json).is_ok());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParseOptions {
+    lenient_datetimes: bool,
+    string_error_codes: bool,
+    lenient_values: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self::lenient()
+    }
+}
+
+impl ParseOptions {
+    /// Accept anything that is known to be returned by some registry.
+    pub const fn lenient() -> Self {
+        Self {
+            lenient_datetimes: true,
+            string_error_codes: true,
+            lenient_values: true,
+        }
+    }
+
+    /// Accept just responses that comply with specification.
+    pub const fn strict() -> Self {
+        Self {
+            lenient_datetimes: false,
+            string_error_codes: false,
+            lenient_values: false,
+        }
+    }
+
+    /// Accept dates like `2020-01-01 10:00:00` or `2020-01-01T10:00:00` without time zone, not
+    /// just RFC 3339 ones.
+    pub fn lenient_datetimes(mut self, lenient: bool) -> Self {
+        self.lenient_datetimes = lenient;
+        self
+    }
+
+    /// Accept error code of error response as string, like `"404"`.
+    pub fn string_error_codes(mut self, accept: bool) -> Self {
+        self.string_error_codes = accept;
+        self
+    }
+
+    /// Accept values of event actions, notice and remark types and jCard value types in any case
+    /// and with known typos.
+    pub fn lenient_values(mut self, lenient: bool) -> Self {
+        self.lenient_values = lenient;
+        self
+    }
+
+    /// Parses JSON string with these options. Unlike `serde_json::from_str`, which ignores options.
+    pub fn parse_str<T: DeserializeOwned>(&self, json: &str) -> serde_json::Result<T> {
+        self.apply(|| serde_json::from_str(json))
+    }
+
+    /// Parses JSON bytes with these options. Unlike `serde_json::from_slice`, which ignores options.
+    pub fn parse_slice<T: DeserializeOwned>(&self, json: &[u8]) -> serde_json::Result<T> {
+        self.apply(|| serde_json::from_slice(json))
+    }
+
+    /// Parses JSON from reader with these options. Unlike `serde_json::from_reader`, which ignores
+    /// options.
+    pub fn parse_reader<R: Read, T: DeserializeOwned>(&self, reader: R) -> serde_json::Result<T> {
+        self.apply(|| serde_json::from_reader(reader))
+    }
+
+    /// Parses JSON value with these options. Unlike `serde_json::from_value`, which ignores options.
+    pub fn parse_value<T: DeserializeOwned>(
+        &self,
+        json: serde_json::Value,
+    ) -> serde_json::Result<T> {
+        self.apply(|| serde_json::from_value(json))
+    }
+
+    /// Runs function with these options used for all parsing on current thread. Options don't apply
+    /// to parsing done by other threads, so function must parse synchronously, not by spawning
+    /// tasks or threads.
+    pub fn apply<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(ParseOptions);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| current.set(self.0));
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(*self)));
+        f()
+    }
+
+    pub(crate) fn current() -> Self {
+        CURRENT.with(Cell::get)
+    }
+
+    pub(crate) fn accepts_lenient_datetimes() -> bool {
+        Self::current().lenient_datetimes
+    }
+
+    pub(crate) fn accepts_string_error_codes() -> bool {
+        Self::current().string_error_codes
+    }
+
+    pub(crate) fn accepts_lenient_values() -> bool {
+        Self::current().lenient_values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParseOptions;
+    use crate::{Error, Event, NoticeOrRemark};
+
+    #[test]
+    fn test_parse_options() {
+        let event = r#"{"eventAction":"Last Changed","eventDate":"2020-01-01 10:00:00"}"#;
+        assert!(ParseOptions::lenient().parse_str::<Event>(event).is_ok());
+        assert!(ParseOptions::strict().parse_str::<Event>(event).is_err());
+        let options = ParseOptions::strict().lenient_datetimes(true);
+        assert!(options.parse_str::<Event>(event).is_err());
+        let options = options.lenient_values(true);
+        assert!(options.parse_str::<Event>(event).is_ok());
+        // Options are restored after parsing.
+        assert!(serde_json::from_str::<Event>(event).is_ok());
+
+        let event =
+            r#"{"eventAction":"last update of RDAP database","eventDate":"2020-01-01T10:00:00Z"}"#;
+        assert!(ParseOptions::strict().parse_str::<Event>(event).is_ok());
+
+        let remark = r#"{"type":"object redacted due to authorization.","description":[]}"#;
+        assert!(ParseOptions::lenient()
+            .parse_str::<NoticeOrRemark>(remark)
+            .is_ok());
+        assert!(ParseOptions::strict()
+            .parse_str::<NoticeOrRemark>(remark)
+            .is_err());
+
+        let error = r#"{"errorCode":"404","title":"Not Found"}"#;
+        assert_eq!(
+            404,
+            ParseOptions::default()
+                .parse_str::<Error>(error)
+                .unwrap()
+                .error_code()
+        );
+        assert!(ParseOptions::default()
+            .string_error_codes(false)
+            .parse_str::<Error>(error)
+            .is_err());
+    }
+}
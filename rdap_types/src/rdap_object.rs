@@ -0,0 +1,471 @@
+//! Uniform access to members shared by all object classes.
+
+use crate::{
+    AutNum, Conformance, Contact, Domain, Entity, Event, Events, FredKeySet, FredNsSet, IpNetwork,
+    Link, Nameserver, NoticeOrRemark, Object, Role, Status,
+};
+use chrono::{DateTime, FixedOffset};
+use std::collections::VecDeque;
+
+/// Members common to object classes, so generic code doesn't have to match every class. Members
+/// that object class doesn't have are returned as empty.
+pub trait RdapObject {
+    /// Value of `objectClassName` member, like `domain` or `ip network`.
+    fn object_class_name(&self) -> &'static str;
+
+    fn handle(&self) -> Option<&str>;
+
+    fn links(&self) -> &[Link] {
+        &[]
+    }
+
+    fn events(&self) -> &[Event] {
+        &[]
+    }
+
+    fn status(&self) -> &[Status] {
+        &[]
+    }
+
+    fn entities(&self) -> &[Object] {
+        &[]
+    }
+
+    fn remarks(&self) -> &[NoticeOrRemark] {
+        &[]
+    }
+
+    fn notices(&self) -> &[NoticeOrRemark] {
+        &[]
+    }
+
+    fn port43(&self) -> Option<&str> {
+        None
+    }
+
+    fn lang(&self) -> Option<&str> {
+        None
+    }
+
+    fn rdap_conformance(&self) -> Option<&Conformance> {
+        None
+    }
+
+    /// Date of `registration` event.
+    fn registration(&self) -> Option<DateTime<FixedOffset>> {
+        self.events().registration()
+    }
+
+    /// Date of `expiration` event.
+    fn expiration(&self) -> Option<DateTime<FixedOffset>> {
+        self.events().expiration()
+    }
+
+    /// Date of `last changed` event.
+    fn last_changed(&self) -> Option<DateTime<FixedOffset>> {
+        self.events().last_changed()
+    }
+
+    /// Returns related entities with given role, nested entities are not included.
+    fn entities_with_role(&self, role: Role) -> Vec<&Entity> {
+        self.entities()
+            .iter()
+            .filter_map(|object| match object {
+                Object::Entity(entity) if entity.roles.iter().flatten().any(|r| *r == role) => {
+                    Some(entity)
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns contact of entity with `abuse` role, that is searched also in entities nested in
+    /// related entities (like abuse contact of registrant of IP network). Entity that has email or
+    /// phone is preferred.
+    fn abuse_contact(&self) -> Option<Contact> {
+        let mut abuse_contacts = Vec::new();
+        let mut queue: VecDeque<&Object> = self.entities().iter().collect();
+        while let Some(object) = queue.pop_front() {
+            if let Object::Entity(entity) = object {
+                if entity.has_role(Role::Abuse) {
+                    if let Some(contact) = entity.contact() {
+                        if !contact.emails.is_empty() || !contact.phones.is_empty() {
+                            return Some(contact);
+                        }
+                        abuse_contacts.push(contact);
+                    }
+                }
+                queue.extend(entity.entities.iter().flatten());
+            }
+        }
+        abuse_contacts.into_iter().next()
+    }
+
+    /// Returns link with `self` relation, that is URL of object.
+    fn self_link(&self) -> Option<&Link> {
+        self.links()
+            .iter()
+            .find(|link| link.rel.as_deref() == Some("self"))
+    }
+}
+
+fn slice<T>(items: &Option<Vec<T>>) -> &[T] {
+    items.as_deref().unwrap_or(&[])
+}
+
+impl RdapObject for Domain {
+    fn object_class_name(&self) -> &'static str {
+        "domain"
+    }
+
+    fn handle(&self) -> Option<&str> {
+        self.handle.as_deref()
+    }
+
+    fn links(&self) -> &[Link] {
+        slice(&self.links)
+    }
+
+    fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    fn status(&self) -> &[Status] {
+        slice(&self.status)
+    }
+
+    fn entities(&self) -> &[Object] {
+        &self.entities
+    }
+
+    fn remarks(&self) -> &[NoticeOrRemark] {
+        slice(&self.remarks)
+    }
+
+    fn notices(&self) -> &[NoticeOrRemark] {
+        slice(&self.notices)
+    }
+
+    fn port43(&self) -> Option<&str> {
+        self.port43.as_deref()
+    }
+
+    fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    fn rdap_conformance(&self) -> Option<&Conformance> {
+        self.rdap_conformance.as_ref()
+    }
+}
+
+impl RdapObject for Entity {
+    fn object_class_name(&self) -> &'static str {
+        "entity"
+    }
+
+    fn handle(&self) -> Option<&str> {
+        self.handle.as_deref()
+    }
+
+    fn links(&self) -> &[Link] {
+        slice(&self.links)
+    }
+
+    fn events(&self) -> &[Event] {
+        slice(&self.events)
+    }
+
+    fn status(&self) -> &[Status] {
+        slice(&self.status)
+    }
+
+    fn entities(&self) -> &[Object] {
+        slice(&self.entities)
+    }
+
+    fn remarks(&self) -> &[NoticeOrRemark] {
+        slice(&self.remarks)
+    }
+
+    fn notices(&self) -> &[NoticeOrRemark] {
+        slice(&self.notices)
+    }
+
+    fn port43(&self) -> Option<&str> {
+        self.port43.as_deref()
+    }
+
+    fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    fn rdap_conformance(&self) -> Option<&Conformance> {
+        self.rdap_conformance.as_ref()
+    }
+}
+
+impl RdapObject for Nameserver {
+    fn object_class_name(&self) -> &'static str {
+        "nameserver"
+    }
+
+    fn handle(&self) -> Option<&str> {
+        self.handle.as_deref()
+    }
+
+    fn links(&self) -> &[Link] {
+        slice(&self.links)
+    }
+
+    fn status(&self) -> &[Status] {
+        slice(&self.status)
+    }
+
+    fn entities(&self) -> &[Object] {
+        slice(&self.entities)
+    }
+
+    fn remarks(&self) -> &[NoticeOrRemark] {
+        slice(&self.remarks)
+    }
+
+    fn notices(&self) -> &[NoticeOrRemark] {
+        slice(&self.notices)
+    }
+
+    fn rdap_conformance(&self) -> Option<&Conformance> {
+        self.rdap_conformance.as_ref()
+    }
+}
+
+impl RdapObject for IpNetwork {
+    fn object_class_name(&self) -> &'static str {
+        "ip network"
+    }
+
+    fn handle(&self) -> Option<&str> {
+        Some(self.handle.as_str())
+    }
+
+    fn links(&self) -> &[Link] {
+        slice(&self.links)
+    }
+
+    fn events(&self) -> &[Event] {
+        slice(&self.events)
+    }
+
+    fn status(&self) -> &[Status] {
+        slice(&self.status)
+    }
+
+    fn entities(&self) -> &[Object] {
+        slice(&self.entities)
+    }
+
+    fn remarks(&self) -> &[NoticeOrRemark] {
+        slice(&self.remarks)
+    }
+
+    fn notices(&self) -> &[NoticeOrRemark] {
+        slice(&self.notices)
+    }
+
+    fn port43(&self) -> Option<&str> {
+        self.port43.as_deref()
+    }
+
+    fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    fn rdap_conformance(&self) -> Option<&Conformance> {
+        self.rdap_conformance.as_ref()
+    }
+}
+
+impl RdapObject for AutNum {
+    fn object_class_name(&self) -> &'static str {
+        "autnum"
+    }
+
+    fn handle(&self) -> Option<&str> {
+        Some(self.handle.as_str())
+    }
+
+    fn links(&self) -> &[Link] {
+        slice(&self.links)
+    }
+
+    fn events(&self) -> &[Event] {
+        slice(&self.events)
+    }
+
+    fn status(&self) -> &[Status] {
+        slice(&self.status)
+    }
+
+    fn entities(&self) -> &[Object] {
+        &self.entities
+    }
+
+    fn remarks(&self) -> &[NoticeOrRemark] {
+        slice(&self.remarks)
+    }
+
+    fn notices(&self) -> &[NoticeOrRemark] {
+        slice(&self.notices)
+    }
+
+    fn port43(&self) -> Option<&str> {
+        self.port43.as_deref()
+    }
+
+    fn lang(&self) -> Option<&str> {
+        self.lang.as_deref()
+    }
+
+    fn rdap_conformance(&self) -> Option<&Conformance> {
+        self.rdap_conformance.as_ref()
+    }
+}
+
+impl RdapObject for FredKeySet {
+    fn object_class_name(&self) -> &'static str {
+        "fredkeyset"
+    }
+
+    fn handle(&self) -> Option<&str> {
+        Some(self.handle.as_str())
+    }
+
+    fn links(&self) -> &[Link] {
+        &self.links
+    }
+}
+
+impl RdapObject for FredNsSet {
+    fn object_class_name(&self) -> &'static str {
+        "frednsset"
+    }
+
+    fn handle(&self) -> Option<&str> {
+        Some(self.handle.as_str())
+    }
+
+    fn links(&self) -> &[Link] {
+        &self.links
+    }
+}
+
+impl Entity {
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.iter().flatten().any(|r| *r == role)
+    }
+}
+
+impl Object {
+    /// Returns object as trait object with common members.
+    pub fn as_rdap_object(&self) -> &dyn RdapObject {
+        match self {
+            Self::AutNum(autnum) => autnum,
+            Self::Domain(domain) => &**domain,
+            Self::Entity(entity) => entity,
+            Self::FredKeySet(keyset) => keyset,
+            Self::FredNsSet(nsset) => nsset,
+            Self::IpNetwork(network) => network,
+            Self::Nameserver(nameserver) => nameserver,
+        }
+    }
+}
+
+impl RdapObject for Object {
+    fn object_class_name(&self) -> &'static str {
+        self.as_rdap_object().object_class_name()
+    }
+
+    fn handle(&self) -> Option<&str> {
+        self.as_rdap_object().handle()
+    }
+
+    fn links(&self) -> &[Link] {
+        self.as_rdap_object().links()
+    }
+
+    fn events(&self) -> &[Event] {
+        self.as_rdap_object().events()
+    }
+
+    fn status(&self) -> &[Status] {
+        self.as_rdap_object().status()
+    }
+
+    fn entities(&self) -> &[Object] {
+        self.as_rdap_object().entities()
+    }
+
+    fn remarks(&self) -> &[NoticeOrRemark] {
+        self.as_rdap_object().remarks()
+    }
+
+    fn notices(&self) -> &[NoticeOrRemark] {
+        self.as_rdap_object().notices()
+    }
+
+    fn port43(&self) -> Option<&str> {
+        self.as_rdap_object().port43()
+    }
+
+    fn lang(&self) -> Option<&str> {
+        self.as_rdap_object().lang()
+    }
+
+    fn rdap_conformance(&self) -> Option<&Conformance> {
+        self.as_rdap_object().rdap_conformance()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RdapObject;
+    use crate::{IpNetwork, Object, Role, Status};
+    use std::fs::File;
+
+    #[test]
+    fn test_rdap_object() {
+        let file = File::open("test_data/domain/domain_fred.json").unwrap();
+        let object: Object = serde_json::from_reader(file).unwrap();
+        assert_eq!("domain", object.object_class_name());
+        assert!(object.self_link().is_some());
+        assert!(!object.events().is_empty());
+        assert!(!object.entities().is_empty());
+        for entity in object.entities() {
+            assert_eq!("entity", entity.object_class_name());
+            assert!(entity.handle().is_some());
+        }
+
+        let file = File::open("test_data/nameserver/nameserver_search.json").unwrap();
+        let json: serde_json::Value = serde_json::from_reader(file).unwrap();
+        let object: Object =
+            serde_json::from_value(json["nameserverSearchResults"][0].clone()).unwrap();
+        assert_eq!("nameserver", object.object_class_name());
+        assert!(object.events().is_empty());
+        assert!(object.port43().is_none());
+        assert!(!object.status().contains(&Status::Removed));
+    }
+
+    #[test]
+    fn test_abuse_contact() {
+        let file = File::open("test_data/ip_network/ip_network_arin_3_3_3_3.json").unwrap();
+        let network: IpNetwork = serde_json::from_reader(file).unwrap();
+        let registrants = network.entities_with_role(Role::Registrant);
+        assert_eq!(1, registrants.len());
+        assert!(registrants[0].has_role(Role::Registrant));
+        assert!(network.entities_with_role(Role::Abuse).is_empty());
+
+        let contact = network.abuse_contact().unwrap();
+        assert_eq!("abuse@amazonaws.com", contact.emails[0].value);
+        assert_eq!("+1-206-266-4064", contact.phones[0].value);
+    }
+}
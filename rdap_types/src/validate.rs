@@ -0,0 +1,388 @@
+//! Strict validation of responses against RFC 9083, useful for testing of RDAP server
+//! implementations. Parser itself is lenient, so response that parses doesn't have to be valid.
+
+use crate::{
+    AutNum, Conformance, Domain, Entity, Event, EventAction, FredKeySet, FredNsSet, IpNetwork,
+    Link, Nameserver, NoticeOrRemark, Object, RdapObject, Status, TopLevelResponse,
+};
+use std::fmt;
+
+/// Violation of RFC 9083 found in response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    /// JSONPath of object that violates specification, like `$.entities[0].links[1]`.
+    pub path: String,
+    pub kind: ViolationKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ViolationKind {
+    /// Member required by specification is missing.
+    MissingMember(&'static str),
+    /// Object has different `objectClassName` than specification requires on its place.
+    UnexpectedObjectClass {
+        expected: &'static str,
+        found: &'static str,
+    },
+    /// Event action that is not registered in [RDAP JSON Values].
+    ///
+    /// [RDAP JSON Values]: https://www.iana.org/assignments/rdap-json-values/rdap-json-values.xhtml
+    UnregisteredEventAction(EventAction),
+    /// Status that is not registered in [RDAP JSON Values].
+    ///
+    /// [RDAP JSON Values]: https://www.iana.org/assignments/rdap-json-values/rdap-json-values.xhtml
+    UnregisteredStatus(String),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ViolationKind::MissingMember(name) => {
+                write!(f, "{}: missing required member `{}`", self.path, name)
+            }
+            ViolationKind::UnexpectedObjectClass { expected, found } => write!(
+                f,
+                "{}: expected object class `{}`, found `{}`",
+                self.path, expected, found
+            ),
+            ViolationKind::UnregisteredEventAction(action) => {
+                write!(f, "{}: unregistered event action {:?}", self.path, action)
+            }
+            ViolationKind::UnregisteredStatus(status) => {
+                write!(f, "{}: unregistered status `{}`", self.path, status)
+            }
+        }
+    }
+}
+
+impl TopLevelResponse {
+    /// Returns all violations of RFC 9083 in response, empty when response is valid.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut validator = Validator::default();
+        match self {
+            Self::Object(object) => validator.top_level_object(object),
+            Self::DomainSearchResults(results) => {
+                validator.conformance("$", results.rdap_conformance.as_ref());
+                validator.notices("$", "notices", &results.notices);
+                for (index, domain) in results.results.iter().enumerate() {
+                    validator.domain(&format!("$.domainSearchResults[{}]", index), domain);
+                }
+            }
+            Self::NameserverSearchResults(results) => {
+                validator.conformance("$", results.rdap_conformance.as_ref());
+                validator.notices("$", "notices", &results.notices);
+                for (index, nameserver) in results.results.iter().enumerate() {
+                    let path = format!("$.nameserverSearchResults[{}]", index);
+                    validator.nameserver(&path, nameserver);
+                }
+            }
+            Self::EntitySearchResults(results) => {
+                validator.conformance("$", results.rdap_conformance.as_ref());
+                validator.notices("$", "notices", &results.notices);
+                for (index, entity) in results.results.iter().enumerate() {
+                    validator.entity(&format!("$.entitySearchResults[{}]", index), entity);
+                }
+            }
+            Self::ArinOriginas0NetworkSearchResults(results) => {
+                validator.conformance("$", results.rdap_conformance.as_ref());
+                validator.notices("$", "notices", &results.notices);
+                for (index, network) in results.results.iter().enumerate() {
+                    let path = format!("$.arin_originas0_networkSearchResults[{}]", index);
+                    validator.ip_network(&path, network);
+                }
+            }
+            Self::Help(help) => {
+                validator.conformance("$", help.rdap_conformance.as_ref());
+                validator.notices("$", "notices", &help.notices);
+            }
+        }
+        validator.violations
+    }
+}
+
+impl Object {
+    /// Returns all violations of RFC 9083 in object, that is validated as top-level response.
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut validator = Validator::default();
+        validator.top_level_object(self);
+        validator.violations
+    }
+}
+
+#[derive(Default)]
+struct Validator {
+    violations: Vec<Violation>,
+}
+
+impl Validator {
+    fn violation(&mut self, path: &str, kind: ViolationKind) {
+        self.violations.push(Violation {
+            path: path.to_owned(),
+            kind,
+        });
+    }
+
+    fn top_level_object(&mut self, object: &Object) {
+        self.conformance("$", object.rdap_conformance());
+        self.object("$", object, None);
+    }
+
+    /// Top-level response must contain `rdapConformance` (RFC 9083 section 4.1).
+    fn conformance(&mut self, path: &str, conformance: Option<&Conformance>) {
+        if conformance.is_none() {
+            self.violation(path, ViolationKind::MissingMember("rdapConformance"));
+        }
+    }
+
+    fn object(&mut self, path: &str, object: &Object, expected: Option<&'static str>) {
+        let found = object.object_class_name();
+        match expected {
+            Some(expected) if expected != found => {
+                self.violation(
+                    path,
+                    ViolationKind::UnexpectedObjectClass { expected, found },
+                );
+            }
+            _ => {}
+        }
+        match object {
+            Object::AutNum(autnum) => self.autnum(path, autnum),
+            Object::Domain(domain) => self.domain(path, domain),
+            Object::Entity(entity) => self.entity(path, entity),
+            Object::FredKeySet(keyset) => self.fred_keyset(path, keyset),
+            Object::FredNsSet(nsset) => self.fred_nsset(path, nsset),
+            Object::IpNetwork(network) => self.ip_network(path, network),
+            Object::Nameserver(nameserver) => self.nameserver(path, nameserver),
+        }
+    }
+
+    fn objects<'a>(
+        &mut self,
+        path: &str,
+        name: &str,
+        objects: impl IntoIterator<Item = &'a Object>,
+        expected: &'static str,
+    ) {
+        for (index, object) in objects.into_iter().enumerate() {
+            self.object(
+                &format!("{}.{}[{}]", path, name, index),
+                object,
+                Some(expected),
+            );
+        }
+    }
+
+    fn domain(&mut self, path: &str, domain: &Domain) {
+        self.objects(path, "entities", &domain.entities, "entity");
+        self.objects(
+            path,
+            "nameservers",
+            domain.nameservers.iter().flatten(),
+            "nameserver",
+        );
+        if let Some(network) = &domain.network {
+            self.object(&format!("{}.network", path), network, Some("ip network"));
+        }
+        if let Some(keyset) = &domain.fred_keyset {
+            self.object(&format!("{}.fred_keyset", path), keyset, Some("fredkeyset"));
+        }
+        if let Some(nsset) = &domain.fred_nsset {
+            self.object(&format!("{}.fred_nsset", path), nsset, Some("frednsset"));
+        }
+        self.links(path, &domain.links);
+        self.events(path, "events", Some(&domain.events));
+        self.statuses(path, &domain.status);
+        self.notices(path, "remarks", &domain.remarks);
+        self.notices(path, "notices", &domain.notices);
+    }
+
+    fn entity(&mut self, path: &str, entity: &Entity) {
+        self.objects(path, "entities", entity.entities.iter().flatten(), "entity");
+        self.links(path, &entity.links);
+        self.events(path, "events", entity.events.as_ref());
+        self.events(path, "asEventActor", entity.as_event_actor.as_ref());
+        self.statuses(path, &entity.status);
+        self.notices(path, "remarks", &entity.remarks);
+        self.notices(path, "notices", &entity.notices);
+    }
+
+    fn nameserver(&mut self, path: &str, nameserver: &Nameserver) {
+        self.objects(
+            path,
+            "entities",
+            nameserver.entities.iter().flatten(),
+            "entity",
+        );
+        self.links(path, &nameserver.links);
+        self.statuses(path, &nameserver.status);
+        self.notices(path, "remarks", &nameserver.remarks);
+        self.notices(path, "notices", &nameserver.notices);
+    }
+
+    fn ip_network(&mut self, path: &str, network: &IpNetwork) {
+        self.objects(
+            path,
+            "entities",
+            network.entities.iter().flatten(),
+            "entity",
+        );
+        self.links(path, &network.links);
+        self.events(path, "events", network.events.as_ref());
+        self.statuses(path, &network.status);
+        self.notices(path, "remarks", &network.remarks);
+        self.notices(path, "notices", &network.notices);
+    }
+
+    fn autnum(&mut self, path: &str, autnum: &AutNum) {
+        self.objects(path, "entities", &autnum.entities, "entity");
+        self.links(path, &autnum.links);
+        self.events(path, "events", autnum.events.as_ref());
+        self.statuses(path, &autnum.status);
+        self.notices(path, "remarks", &autnum.remarks);
+        self.notices(path, "notices", &autnum.notices);
+    }
+
+    fn fred_keyset(&mut self, path: &str, keyset: &FredKeySet) {
+        for (index, link) in keyset.links.iter().enumerate() {
+            self.link(&format!("{}.links[{}]", path, index), link);
+        }
+    }
+
+    fn fred_nsset(&mut self, path: &str, nsset: &FredNsSet) {
+        for (index, link) in nsset.links.iter().enumerate() {
+            self.link(&format!("{}.links[{}]", path, index), link);
+        }
+        for (index, nameserver) in nsset.nameservers.iter().enumerate() {
+            self.nameserver(&format!("{}.nameservers[{}]", path, index), nameserver);
+        }
+    }
+
+    fn links(&mut self, path: &str, links: &Option<Vec<Link>>) {
+        for (index, link) in links.iter().flatten().enumerate() {
+            self.link(&format!("{}.links[{}]", path, index), link);
+        }
+    }
+
+    /// RFC 9083 section 4.2 made `value` and `rel` mandatory.
+    fn link(&mut self, path: &str, link: &Link) {
+        if link.value.is_none() {
+            self.violation(path, ViolationKind::MissingMember("value"));
+        }
+        if link.rel.is_none() {
+            self.violation(path, ViolationKind::MissingMember("rel"));
+        }
+    }
+
+    fn events(&mut self, path: &str, name: &str, events: Option<&Vec<Event>>) {
+        for (index, event) in events.into_iter().flatten().enumerate() {
+            let path = format!("{}.{}[{}]", path, name, index);
+            if matches!(
+                event.action,
+                EventAction::DelegationSignCheck
+                    | EventAction::SoftExpiration
+                    | EventAction::LastCorrectDelegationSignCheck
+            ) {
+                self.violation(&path, ViolationKind::UnregisteredEventAction(event.action));
+            }
+            if let Some(link) = &event.links {
+                self.link(&format!("{}.links", path), link);
+            }
+        }
+    }
+
+    fn statuses(&mut self, path: &str, statuses: &Option<Vec<Status>>) {
+        for (index, status) in statuses.iter().flatten().enumerate() {
+            let status = match status {
+                Status::Unknown(status) => status.clone(),
+                Status::Ok => "ok".to_owned(),
+                _ => continue,
+            };
+            self.violation(
+                &format!("{}.status[{}]", path, index),
+                ViolationKind::UnregisteredStatus(status),
+            );
+        }
+    }
+
+    /// RFC 9083 section 4.3 requires `description` of notices and remarks.
+    fn notices(&mut self, path: &str, name: &str, notices: &Option<Vec<NoticeOrRemark>>) {
+        for (index, notice) in notices.iter().flatten().enumerate() {
+            let path = format!("{}.{}[{}]", path, name, index);
+            if notice.description.is_none() {
+                self.violation(&path, ViolationKind::MissingMember("description"));
+            }
+            for (link_index, link) in notice.links.iter().flatten().enumerate() {
+                self.link(&format!("{}.links[{}]", path, link_index), link);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Violation, ViolationKind};
+    use crate::{EventAction, Object, TopLevelResponse};
+    use std::fs::File;
+
+    #[test]
+    fn test_validate() {
+        let json = r#"{
+            "objectClassName": "domain",
+            "ldhName": "example.com",
+            "links": [{"value": "https://rdap.example/domain/example.com", "rel": "self", "href": "https://rdap.example/domain/example.com"}],
+            "entities": [{"objectClassName": "nameserver", "ldhName": "ns1.example.com", "links": [{"href": "https://rdap.example/nameserver/ns1.example.com"}]}],
+            "events": [{"eventAction": "soft expiration", "eventDate": "2024-01-01T00:00:00Z"}],
+            "status": ["active", "ok"],
+            "notices": [{"title": "Terms of Use"}]
+        }"#;
+        let object: Object = serde_json::from_str(json).unwrap();
+        let violations = object.validate();
+        assert_eq!(
+            vec![
+                Violation {
+                    path: "$".into(),
+                    kind: ViolationKind::MissingMember("rdapConformance"),
+                },
+                Violation {
+                    path: "$.entities[0]".into(),
+                    kind: ViolationKind::UnexpectedObjectClass {
+                        expected: "entity",
+                        found: "nameserver"
+                    },
+                },
+                Violation {
+                    path: "$.entities[0].links[0]".into(),
+                    kind: ViolationKind::MissingMember("value"),
+                },
+                Violation {
+                    path: "$.entities[0].links[0]".into(),
+                    kind: ViolationKind::MissingMember("rel"),
+                },
+                Violation {
+                    path: "$.events[0]".into(),
+                    kind: ViolationKind::UnregisteredEventAction(EventAction::SoftExpiration),
+                },
+                Violation {
+                    path: "$.status[1]".into(),
+                    kind: ViolationKind::UnregisteredStatus("ok".into()),
+                },
+                Violation {
+                    path: "$.notices[0]".into(),
+                    kind: ViolationKind::MissingMember("description"),
+                },
+            ],
+            violations
+        );
+        assert_eq!(
+            "$.entities[0].links[0]: missing required member `value`",
+            violations[2].to_string()
+        );
+    }
+
+    #[test]
+    fn test_validate_valid_response() {
+        let file = File::open("test_data/domain/domain_redacted.json").unwrap();
+        let response: TopLevelResponse = serde_json::from_reader(file).unwrap();
+        assert_eq!(Vec::<Violation>::new(), response.validate());
+    }
+}
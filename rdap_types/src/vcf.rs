@@ -0,0 +1,251 @@
+//! Export of jCard to vCard 4.0 text format (RFC 6350), that can be imported to address books.
+
+use crate::{Contact, JCard, JCardItem, JCardItemDataType};
+use serde_json::Value;
+
+/// Maximum length of line in octets, longer lines are folded.
+const MAX_LINE_LENGTH: usize = 75;
+
+impl JCard {
+    /// Returns vCard 4.0 text representation (RFC 6350) of jCard, with lines ended by CRLF.
+    pub fn to_vcf(&self) -> String {
+        to_vcf(self.items())
+    }
+}
+
+impl Contact {
+    /// Returns vCard 4.0 text representation of all original jCard items of contact.
+    pub fn to_vcf(&self) -> String {
+        to_vcf(&self.items)
+    }
+}
+
+fn to_vcf(items: &[JCardItem]) -> String {
+    let mut vcf = String::from("BEGIN:VCARD\r\nVERSION:4.0\r\n");
+    for item in items.iter().filter(|item| item.property_name != "version") {
+        fold(&content_line(item), &mut vcf);
+    }
+    vcf.push_str("END:VCARD\r\n");
+    vcf
+}
+
+/// Converts item to content line (RFC 6350 section 3.3) without line ending.
+fn content_line(item: &JCardItem) -> String {
+    let mut line = String::new();
+    if let Some(Value::String(group)) = item.parameters.get("group") {
+        line.push_str(&group.to_uppercase());
+        line.push('.');
+    }
+    line.push_str(&item.property_name.to_uppercase());
+
+    for (name, value) in &item.parameters {
+        if name == "group" {
+            continue;
+        }
+        line.push(';');
+        line.push_str(&name.to_uppercase());
+        line.push('=');
+        let values = match value {
+            Value::Array(values) => &values[..],
+            value => std::slice::from_ref(value),
+        };
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {
+                line.push(',');
+            }
+            parameter_value(&scalar(value), &mut line);
+        }
+    }
+    let value_type = value_type_name(item.type_identifier);
+    if value_type != default_value_type(&item.property_name) && value_type != "unknown" {
+        line.push_str(";VALUE=");
+        line.push_str(value_type);
+    }
+
+    line.push(':');
+    let is_text = matches!(
+        item.type_identifier,
+        JCardItemDataType::Text | JCardItemDataType::TextList | JCardItemDataType::Unknown
+    );
+    let is_structured = matches!(
+        item.property_name.as_str(),
+        "adr" | "n" | "org" | "gender" | "clientpidmap"
+    );
+    // Multiple values of structured property are its components, otherwise they are list.
+    let separator = if is_structured { ';' } else { ',' };
+    for (index, value) in item.values.iter().enumerate() {
+        if index > 0 {
+            line.push(separator);
+        }
+        match value {
+            Value::Array(components) => {
+                for (index, component) in components.iter().enumerate() {
+                    if index > 0 {
+                        line.push(';');
+                    }
+                    match component {
+                        Value::Array(values) => {
+                            for (index, value) in values.iter().enumerate() {
+                                if index > 0 {
+                                    line.push(',');
+                                }
+                                value_text(value, is_text, &mut line);
+                            }
+                        }
+                        component => value_text(component, is_text, &mut line),
+                    }
+                }
+            }
+            value => value_text(value, is_text, &mut line),
+        }
+    }
+    line
+}
+
+/// Returns string representation of JSON scalar value.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(string) => string.clone(),
+        Value::Bool(true) => "TRUE".to_owned(),
+        Value::Bool(false) => "FALSE".to_owned(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+/// Appends value, text is escaped as RFC 6350 section 3.4 requires.
+fn value_text(value: &Value, is_text: bool, out: &mut String) {
+    let value = scalar(value);
+    if !is_text {
+        out.push_str(&value);
+        return;
+    }
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            ',' => out.push_str("\\,"),
+            ';' => out.push_str("\\;"),
+            '\n' => out.push_str("\\n"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+}
+
+/// Appends parameter value, that is quoted when it contains special characters and encoded
+/// according to RFC 6868.
+fn parameter_value(value: &str, out: &mut String) {
+    let quote = value.contains([',', ';', ':']);
+    if quote {
+        out.push('"');
+    }
+    for c in value.chars() {
+        match c {
+            '^' => out.push_str("^^"),
+            '\n' => out.push_str("^n"),
+            '"' => out.push_str("^'"),
+            '\r' => {}
+            c => out.push(c),
+        }
+    }
+    if quote {
+        out.push('"');
+    }
+}
+
+/// Appends line folded to lines of at most 75 octets (RFC 6350 section 3.2).
+fn fold(line: &str, out: &mut String) {
+    let mut length = 0;
+    for c in line.chars() {
+        if length + c.len_utf8() > MAX_LINE_LENGTH {
+            out.push_str("\r\n ");
+            // Continuation line starts with space.
+            length = 1;
+        }
+        out.push(c);
+        length += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+fn value_type_name(value_type: JCardItemDataType) -> &'static str {
+    match value_type {
+        JCardItemDataType::Text | JCardItemDataType::TextList => "text",
+        JCardItemDataType::DateList => "date",
+        JCardItemDataType::TimeList => "time",
+        JCardItemDataType::DateTimeList => "date-time",
+        JCardItemDataType::DateAndOrTimeList => "date-and-or-time",
+        JCardItemDataType::TimestampList => "timestamp",
+        JCardItemDataType::Boolean => "boolean",
+        JCardItemDataType::IntegerList => "integer",
+        JCardItemDataType::FloatList => "float",
+        JCardItemDataType::Uri => "uri",
+        JCardItemDataType::UtcOffset => "utc-offset",
+        JCardItemDataType::LanguageTag => "language-tag",
+        JCardItemDataType::IanaValuespec => "iana-valuespec",
+        JCardItemDataType::Unknown => "unknown",
+    }
+}
+
+/// Returns default value type of property, for which `VALUE` parameter is not written.
+fn default_value_type(property_name: &str) -> &'static str {
+    match property_name {
+        "source" | "photo" | "impp" | "geo" | "logo" | "member" | "related" | "sound" | "uid"
+        | "url" | "key" | "fburl" | "caladruri" | "caluri" => "uri",
+        "lang" => "language-tag",
+        "bday" | "anniversary" => "date-and-or-time",
+        "rev" => "timestamp",
+        _ => "text",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Entity, JCard};
+    use std::fs::File;
+
+    #[test]
+    fn test_to_vcf() {
+        let file = File::open("test_data/entity/entity_15.json").unwrap();
+        let entity: Entity = serde_json::from_reader(file).unwrap();
+        let expected = "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Joe User\r
+N:User;Joe;;;ing. jr,M.Sc.\r
+KIND:individual\r
+LANG;PREF=1:fr\r
+LANG;PREF=2:en\r
+ORG;TYPE=work:Example\r
+TITLE:Research Scientist\r
+ROLE:Project Lead\r
+ADR;TYPE=work:;Suite 1234;4321 Rue Somewhere;Quebec;QC;G1V 2M2;Canada\r
+ADR;LABEL=123 Maple Ave^nSuite 90001^nVancouver^nBC^n1239^n;TYPE=home:;;;;;\r
+ ;\r
+TEL;PREF=1;TYPE=work,voice;VALUE=uri:tel:+1-555-555-1234;ext=102\r
+TEL;TYPE=work,cell,voice,video,text;VALUE=uri:tel:+1-555-555-4321\r
+EMAIL;TYPE=work:joe.user@example.com\r
+GEO;TYPE=work:geo:46.772673,-71.282945\r
+KEY;TYPE=work:http://www.example.com/joe.user/joe.asc\r
+TZ;VALUE=utc-offset:-05:00\r
+URL;TYPE=home:http://example.org\r
+END:VCARD\r
+";
+        assert_eq!(expected, entity.vcard_array.as_ref().unwrap().to_vcf());
+        assert_eq!(expected, entity.contact().unwrap().to_vcf());
+    }
+
+    #[test]
+    fn test_to_vcf_escaping() {
+        let json = r#"["vcard",[["fn",{},"text","Doe, John; Jr.\\"],["note",{"group":"item1","altid":"a:b"},"text","line1\nline2"],["categories",{},"text","a,b","c"],["adr",{"cc":"US"},"text","","","","","Washington","",""]]]"#;
+        let jcard: JCard = serde_json::from_str(json).unwrap();
+        let expected = "BEGIN:VCARD\r
+VERSION:4.0\r
+FN:Doe\\, John\\; Jr.\\\\\r
+ITEM1.NOTE;ALTID=\"a:b\":line1\\nline2\r
+CATEGORIES:a\\,b,c\r
+ADR;CC=US:;;;;Washington;;\r
+END:VCARD\r
+";
+        assert_eq!(expected, jcard.to_vcf());
+    }
+}